@@ -0,0 +1,59 @@
+//! Package metadata amendments.
+//!
+//! `update_package_metadata` lets the admin, or the distributor that created
+//! a package, correct its metadata after creation.  Keys that change how the
+//! package behaves (`claim_starts_at`, `merkle_root`, `campaign_ref`) can only
+//! change while it is `Created`; other keys, such as `receipt_hash`, can be
+//! amended at any time.  Every amendment is appended to a per-package log.
+//!
+//! Storage: `("pkgby", id)` -> creator `Address`, `("pkgamend", id)` ->
+//! `Vec<MetadataAmendment>`.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, String, Symbol, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetadataAmendment {
+    pub actor: Address,
+    pub timestamp: u64,
+    /// Entries added or overwritten.
+    pub set: Map<Symbol, String>,
+    /// Keys removed.
+    pub removed: Vec<Symbol>,
+}
+
+/// Records the operator that created a package.  Nothing is stored for the
+/// admin, who may amend any package anyway.
+pub fn set_creator(env: &Env, package_id: u64, creator: &Address, admin: &Address) {
+    if creator != admin {
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("pkgby"), package_id), creator);
+    }
+}
+
+/// Returns the distributor that created a package, if recorded.  Packages
+/// created by the admin, or before creators were recorded, have none.
+pub fn get_creator(env: &Env, package_id: u64) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("pkgby"), package_id))
+}
+
+/// Returns the amendment log of a package, oldest first.
+pub fn get_log(env: &Env, package_id: u64) -> Vec<MetadataAmendment> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("pkgamend"), package_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Appends an amendment and returns its index in the log.
+pub fn append(env: &Env, package_id: u64, amendment: MetadataAmendment) -> u32 {
+    let mut log = get_log(env, package_id);
+    log.push_back(amendment);
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("pkgamend"), package_id), &log);
+    log.len() - 1
+}
@@ -0,0 +1,25 @@
+//! Oracle-gated release.
+//!
+//! A package whose terms name an `attestor` contract and a `condition_id`
+//! stays locked until the attestor reports the condition as attested, e.g. a
+//! drought index crossing its trigger.  Every payout path, including
+//! disbursement and merchant redemption, asks the attestor first.
+
+use soroban_sdk::{contractclient, Address, Env, Symbol};
+
+/// Interface an attestor contract implements.  The escrow calls it through
+/// `AttestorClient::try_is_attested`, so an attestor that is missing, fails or
+/// returns anything but `true` keeps the package locked.
+#[contractclient(name = "AttestorClient")]
+pub trait Attestor {
+    /// Returns `true` once `condition_id` has been attested.
+    fn is_attested(env: Env, condition_id: Symbol) -> bool;
+}
+
+/// Asks `attestor` whether `condition_id` has been attested.
+pub fn is_attested(env: &Env, attestor: &Address, condition_id: &Symbol) -> bool {
+    matches!(
+        AttestorClient::new(env, attestor).try_is_attested(condition_id),
+        Ok(Ok(true))
+    )
+}
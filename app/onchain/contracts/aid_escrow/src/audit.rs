@@ -0,0 +1,123 @@
+//! Tamper-evident audit hash chain.
+//!
+//! Every state change appends one `AuditEntry` to a running commitment:
+//! `hash_n = sha256(hash_{n-1} || xdr(entry_n))`, starting from 32 zero
+//! bytes at `seq` 0.  Package status changes are recorded by `lifecycle`
+//! (action = the transition reason); every other mutating entrypoint records
+//! itself (action = the entrypoint name).
+//!
+//! `entry.change` binds the content of the change: `sha256(xdr(change))`,
+//! where `change` is the entrypoint's arguments in declaration order (a
+//! single value as is, none as `()`), or the package as stored after a
+//! status change.
+//!
+//! Each record emits `AuditRecorded` carrying the entry fields together with
+//! the previous and new hash, so an off-chain replay of the event stream can
+//! recompute the head returned by `get_audit_head` and detect any gap in `seq`,
+//! and check that the amounts and addresses shown by the business events are
+//! the ones that were committed.
+
+use soroban_sdk::{
+    contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Symbol, Val,
+};
+
+use crate::{AuditRecorded, EVENT_SCHEMA_VERSION};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditHead {
+    /// Number of entries recorded so far.
+    pub seq: u64,
+    pub hash: BytesN<32>,
+}
+
+/// The change committed to by one link of the chain.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub action: Symbol,
+    pub actor: Address,
+    pub package_id: Option<u64>,
+    pub timestamp: u64,
+    /// `sha256(xdr(change))`.
+    pub change: BytesN<32>,
+}
+
+/// Returns the current head (`seq` 0 and a zero hash before any change).
+pub fn get_head(env: &Env) -> AuditHead {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("audit"))
+        .unwrap_or(AuditHead {
+            seq: 0,
+            hash: BytesN::from_array(env, &[0u8; 32]),
+        })
+}
+
+/// Appends a change made by the entrypoint `action` with arguments `change`.
+pub fn record(
+    env: &Env,
+    action: &str,
+    actor: &Address,
+    package_id: Option<u64>,
+    change: impl IntoVal<Env, Val>,
+) {
+    append(
+        env,
+        Symbol::new(env, action),
+        actor,
+        package_id,
+        change.into_val(env),
+    );
+}
+
+/// Appends a change made under the action name `action`.
+pub fn record_symbol(
+    env: &Env,
+    action: Symbol,
+    actor: &Address,
+    package_id: Option<u64>,
+    change: impl IntoVal<Env, Val>,
+) {
+    append(env, action, actor, package_id, change.into_val(env));
+}
+
+/// Appends a change and emits `AuditRecorded`.  Kept apart from the generic
+/// entry points so the contract carries one copy of it.
+fn append(env: &Env, action: Symbol, actor: &Address, package_id: Option<u64>, change: Val) {
+    let head = get_head(env);
+    let entry = AuditEntry {
+        seq: head.seq + 1,
+        action,
+        actor: actor.clone(),
+        package_id,
+        timestamp: env.ledger().timestamp(),
+        change: env.crypto().sha256(&change.to_xdr(env)).into(),
+    };
+
+    let mut preimage = Bytes::from_array(env, &head.hash.to_array());
+    preimage.append(&entry.clone().to_xdr(env));
+    let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+    env.storage().instance().set(
+        &symbol_short!("audit"),
+        &AuditHead {
+            seq: entry.seq,
+            hash: hash.clone(),
+        },
+    );
+
+    AuditRecorded {
+        seq: entry.seq,
+        action: entry.action,
+        actor: entry.actor,
+        package_id: entry.package_id,
+        timestamp: entry.timestamp,
+        change: entry.change,
+        prev_hash: head.hash,
+        hash,
+        schema_version: EVENT_SCHEMA_VERSION,
+    }
+    .publish(env);
+}
@@ -0,0 +1,196 @@
+//! Beneficiary registry checks at package creation.
+//!
+//! The admin can link a beneficiary registry contract that maps salted
+//! identity hashes to addresses.  When linked, each new package looks up its
+//! recipient's identity: if `require_registered` is set an unregistered
+//! recipient is rejected, and within a campaign an identity may hold only one
+//! live package, whatever address it was enrolled under.  The slot frees up
+//! once that package is cancelled or refunded.  A registry that cannot be
+//! called rejects the package rather than skipping the checks.  Moving a
+//! package to another campaign moves its identity's slot and checks for a
+//! duplicate again, and so does recovering it to a new recipient, whose
+//! identity then holds the slot.
+//!
+//! Hash-locked packages hold the escrow's own address as a placeholder
+//! recipient, which is not looked up at creation.  Their payout address is
+//! checked instead when the secret is redeemed.
+//!
+//! Storage: instance `"benreg"` -> `BeneficiaryPolicy`, `("benid", campaign,
+//! identity_hash)` -> id of the identity's package in the campaign.
+
+use soroban_sdk::{
+    contractclient, contracttype, panic_with_error, symbol_short, Address, BytesN, Env, String,
+};
+
+use crate::{Package, PackageStatus, PolicyError};
+
+/// Interface a beneficiary registry implements.  The escrow calls it through
+/// `BeneficiaryRegistryClient::try_get_identity`.
+#[contractclient(name = "BeneficiaryRegistryClient")]
+pub trait BeneficiaryRegistry {
+    /// Returns the identity hash `beneficiary` is registered under, if any.
+    fn get_identity(env: Env, beneficiary: Address) -> Option<BytesN<32>>;
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BeneficiaryPolicy {
+    /// Contract implementing [`BeneficiaryRegistry`].
+    pub registry: Address,
+    /// Reject packages for recipients the registry does not know.
+    pub require_registered: bool,
+}
+
+/// Returns the linked registry policy, if any.
+pub fn get_policy(env: &Env) -> Option<BeneficiaryPolicy> {
+    env.storage().instance().get(&symbol_short!("benreg"))
+}
+
+/// Links or, with `None`, unlinks the registry.
+pub fn set_policy(env: &Env, policy: &Option<BeneficiaryPolicy>) {
+    match policy {
+        Some(policy) => env
+            .storage()
+            .instance()
+            .set(&symbol_short!("benreg"), policy),
+        None => env.storage().instance().remove(&symbol_short!("benreg")),
+    }
+}
+
+/// Returns the package `identity_hash` holds in `campaign`, if any.
+pub fn get_campaign_package(
+    env: &Env,
+    campaign: &String,
+    identity_hash: &BytesN<32>,
+) -> Option<u64> {
+    env.storage().persistent().get(&(
+        symbol_short!("benid"),
+        campaign.clone(),
+        identity_hash.clone(),
+    ))
+}
+
+/// Checks the recipient of a new package, or the payout address of a
+/// hash-locked one, against the registry and records its identity's package
+/// in `campaign`.  Call after the package is stored.
+pub fn enroll(
+    env: &Env,
+    policy: &BeneficiaryPolicy,
+    package_id: u64,
+    recipient: &Address,
+    campaign: &Option<String>,
+) {
+    if *recipient == env.current_contract_address() {
+        return;
+    }
+    let Some(identity_hash) = identity_of(env, &policy.registry, recipient) else {
+        if policy.require_registered {
+            panic_with_error!(env, PolicyError::RecipientNotRegistered);
+        }
+        return;
+    };
+    if let Some(campaign) = campaign {
+        record(env, package_id, &identity_hash, campaign);
+    }
+}
+
+/// Moves the identity slot of a package from `old` to `new` campaign,
+/// rejecting the move if the identity already holds a live package in `new`.
+#[cfg(any(feature = "amendments", feature = "recovery"))]
+pub fn reassign(
+    env: &Env,
+    policy: &BeneficiaryPolicy,
+    package_id: u64,
+    recipient: &Address,
+    old: &Option<String>,
+    new: &Option<String>,
+) {
+    if old == new {
+        return;
+    }
+    let Some(identity_hash) = identity_of(env, &policy.registry, recipient) else {
+        if policy.require_registered {
+            panic_with_error!(env, PolicyError::RecipientNotRegistered);
+        }
+        return;
+    };
+    if let Some(old) = old {
+        release(env, package_id, &identity_hash, old);
+    }
+    if let Some(new) = new {
+        record(env, package_id, &identity_hash, new);
+    }
+}
+
+/// Moves the identity slot of a recovered package from the `old` to the `new`
+/// recipient, rejecting a `new` recipient that `enroll` would reject.
+#[cfg(any(feature = "amendments", feature = "recovery"))]
+pub fn reassign_recipient(
+    env: &Env,
+    policy: &BeneficiaryPolicy,
+    package_id: u64,
+    old: &Address,
+    new: &Address,
+    campaign: &Option<String>,
+) {
+    let new_identity = identity_of(env, &policy.registry, new);
+    if new_identity.is_none() && policy.require_registered {
+        panic_with_error!(env, PolicyError::RecipientNotRegistered);
+    }
+    let Some(campaign) = campaign else {
+        return;
+    };
+    if let Some(old_identity) = identity_of(env, &policy.registry, old) {
+        release(env, package_id, &old_identity, campaign);
+    }
+    if let Some(new_identity) = new_identity {
+        record(env, package_id, &new_identity, campaign);
+    }
+}
+
+/// Frees the slot of `identity_hash` in `campaign` if `package_id` holds it.
+#[cfg(any(feature = "amendments", feature = "recovery"))]
+fn release(env: &Env, package_id: u64, identity_hash: &BytesN<32>, campaign: &String) {
+    if get_campaign_package(env, campaign, identity_hash) == Some(package_id) {
+        env.storage().persistent().remove(&(
+            symbol_short!("benid"),
+            campaign.clone(),
+            identity_hash.clone(),
+        ));
+    }
+}
+
+/// Records `package_id` as the package of `identity_hash` in `campaign`,
+/// rejecting it if the identity already holds a live one there.
+fn record(env: &Env, package_id: u64, identity_hash: &BytesN<32>, campaign: &String) {
+    if let Some(existing) =
+        get_campaign_package(env, campaign, identity_hash).filter(|id| *id != package_id)
+    {
+        let live = env
+            .storage()
+            .persistent()
+            .get::<_, Package>(&(symbol_short!("pkg"), existing))
+            .is_some_and(|p| matches!(p.status, PackageStatus::Created | PackageStatus::Claimed));
+        if live {
+            panic_with_error!(env, PolicyError::DuplicateIdentity);
+        }
+    }
+    env.storage().persistent().set(
+        &(
+            symbol_short!("benid"),
+            campaign.clone(),
+            identity_hash.clone(),
+        ),
+        &package_id,
+    );
+}
+
+/// Asks `registry` for the identity hash of `recipient`.  Only a `None`
+/// answer means not registered; a registry that is missing or fails rejects
+/// the call.
+fn identity_of(env: &Env, registry: &Address, recipient: &Address) -> Option<BytesN<32>> {
+    match BeneficiaryRegistryClient::new(env, registry).try_get_identity(recipient) {
+        Ok(Ok(identity)) => identity,
+        _ => panic_with_error!(env, PolicyError::RegistryUnavailable),
+    }
+}
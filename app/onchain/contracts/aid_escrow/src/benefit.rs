@@ -0,0 +1,154 @@
+//! Per-recipient benefit caps and payout totals.
+//!
+//! Every payout, whether claim, disbursement or merchant redemption, is added
+//! to its recipient's running totals per token.  The admin can cap what one
+//! recipient may be paid in a token over a rolling window (e.g. 30 days).  A
+//! claim over the cap is rejected or, in `PayPartial` mode, paid up to the cap:
+//! the package keeps its amount, is settled as paid, and the rest is returned
+//! to the pool.  Redemptions are never shortened; one over the cap is rejected.
+//!
+//! Payouts inside the window are kept while a cap is set for the token, so
+//! the window only counts payouts made since the cap was first set.
+//!
+//! Storage: `("bcap", token)` -> `BenefitCap`, `("rtot", recipient, token)` ->
+//! `RecipientTotals`, `("rwin", recipient, token)` -> `Vec<WindowPayout>`,
+//! `("bpaid", package_id)` -> amount paid for a package the cap shortened.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+
+use crate::Package;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CapMode {
+    /// Reject a claim that would exceed the cap.
+    Reject,
+    /// Pay a claim up to the cap and return the rest to the pool.
+    PayPartial,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenefitCap {
+    /// Maximum paid to one recipient within `window`.
+    pub amount: i128,
+    /// Rolling window in seconds.
+    pub window: u64,
+    pub mode: CapMode,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RecipientTotals {
+    /// Total paid out to the recipient.
+    pub claimed: i128,
+    /// Number of payouts (each partial redemption counts).
+    pub payouts: u32,
+    pub last_claimed_at: u64,
+    /// Paid within the current cap window (0 when no cap is set).
+    pub window_claimed: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowPayout {
+    pub at: u64,
+    pub amount: i128,
+}
+
+/// Returns the cap for `token`, if any.
+pub fn get_cap(env: &Env, token: &Address) -> Option<BenefitCap> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("bcap"), token.clone()))
+}
+
+/// Sets or, with `None`, removes the cap for `token`.
+pub fn set_cap(env: &Env, token: &Address, cap: &Option<BenefitCap>) {
+    let key = (symbol_short!("bcap"), token.clone());
+    match cap {
+        Some(cap) => env.storage().persistent().set(&key, cap),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Returns what `recipient` may still be paid in `token` under `cap`.
+pub fn room(env: &Env, cap: &BenefitCap, recipient: &Address, token: &Address) -> i128 {
+    cap.amount - window_sum(&window(env, cap, recipient, token))
+}
+
+/// Returns the payout totals of `recipient` in `token`.
+pub fn get_totals(env: &Env, recipient: &Address, token: &Address) -> RecipientTotals {
+    let mut totals: RecipientTotals = env
+        .storage()
+        .persistent()
+        .get(&(symbol_short!("rtot"), recipient.clone(), token.clone()))
+        .unwrap_or_default();
+    totals.window_claimed = match get_cap(env, token) {
+        Some(cap) => window_sum(&window(env, &cap, recipient, token)),
+        None => 0,
+    };
+    totals
+}
+
+/// Adds a payout of `amount` to the totals of `recipient`, and to its window
+/// when `token` is capped.
+pub fn record(env: &Env, recipient: &Address, token: &Address, amount: i128) {
+    let now = env.ledger().timestamp();
+    let mut totals = get_totals(env, recipient, token);
+    totals.claimed += amount;
+    totals.payouts += 1;
+    totals.last_claimed_at = now;
+
+    if let Some(cap) = get_cap(env, token) {
+        let mut payouts = window(env, &cap, recipient, token);
+        payouts.push_back(WindowPayout { at: now, amount });
+        totals.window_claimed = window_sum(&payouts);
+        env.storage().persistent().set(
+            &(symbol_short!("rwin"), recipient.clone(), token.clone()),
+            &payouts,
+        );
+    }
+    env.storage().persistent().set(
+        &(symbol_short!("rtot"), recipient.clone(), token.clone()),
+        &totals,
+    );
+}
+
+/// Records that the cap shortened the payout of `package_id` to `paid`.
+pub fn set_paid(env: &Env, package_id: u64, paid: i128) {
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("bpaid"), package_id), &paid);
+}
+
+/// Returns what claimed package `package_id` paid out: its amount, unless
+/// the cap shortened the payout.
+pub fn get_paid(env: &Env, package_id: u64, package: &Package) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("bpaid"), package_id))
+        .unwrap_or(package.amount)
+}
+
+/// Returns the stored payouts of `recipient` still inside the cap window.
+fn window(env: &Env, cap: &BenefitCap, recipient: &Address, token: &Address) -> Vec<WindowPayout> {
+    let now = env.ledger().timestamp();
+    let stored: Vec<WindowPayout> = env
+        .storage()
+        .persistent()
+        .get(&(symbol_short!("rwin"), recipient.clone(), token.clone()))
+        .unwrap_or(Vec::new(env));
+
+    let mut payouts = Vec::new(env);
+    for payout in stored.iter() {
+        if payout.at.saturating_add(cap.window) > now {
+            payouts.push_back(payout);
+        }
+    }
+    payouts
+}
+
+fn window_sum(payouts: &Vec<WindowPayout>) -> i128 {
+    payouts.iter().map(|payout| payout.amount).sum()
+}
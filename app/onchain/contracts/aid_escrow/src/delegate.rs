@@ -1,24 +1,78 @@
 //! Delegate / recovery address support for aid packages.
 //!
-//! A package creator may register an optional delegate address for the
-//! recipient.  Either the primary recipient OR the delegate may authorise
-//! a claim.  Once a package is claimed the delegate cannot be changed,
-//! preventing reassignment after funds are disbursed.
+//! A package may carry an ordered set of delegate addresses for the
+//! recipient, each with its own optional expiry.  Either the primary
+//! recipient OR an active delegate may authorise a claim.  Once a package
+//! is claimed the delegate set is cleared, preventing reassignment after
+//! funds are disbursed.
 //!
 //! Enhanced features:
-//! - Delegate expiration support
+//! - Up to `MAX_DELEGATES_PER_PACKAGE` delegates per package, oldest first
+//! - Per-delegate expiration support
+//! - Optional k-of-n approval threshold for delegate-initiated claims
 //! - Package status validation
 //! - Audit trail for delegate changes
-//! - Optimized storage operations
 //! - Comprehensive error handling
+//! - Per-package storage keys, so the cost of a set, clear or claim does not
+//!   grow with the number of delegations made on other packages
+//! - Paged per-package history and cursor-based cleanup
+//! - Recipient self-service nominations that only take effect once the
+//!   nominee accepts
 
 use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, Symbol, Vec};
 
-use crate::{Error, PackageStatus};
+use crate::{payout::PayoutPolicy, Error, PackageStatus};
+
+/// `("dlgset", package_id)` -> `Vec<DelegateEntry>`
+const KEY_DELEGATE_SET: Symbol = symbol_short!("dlgset");
+/// `("dlgthr", package_id)` -> `u32`, absent when any single delegate may claim.
+const KEY_DELEGATE_THRESHOLD: Symbol = symbol_short!("dlgthr");
+/// `("dlgh", package_id, seq)` -> `DelegateHistory`
+const KEY_DELEGATE_HISTORY: Symbol = symbol_short!("dlgh");
+/// `("dlghlen", package_id)` -> `u32`, number of history records of the package.
+const KEY_DELEGATE_HISTORY_LEN: Symbol = symbol_short!("dlghlen");
+/// `("dlgidx", position)` -> `u64`, every package that ever had a delegate, in
+/// first-delegation order.  Walked by [`cleanup_expired_delegates`].
+const KEY_DELEGATE_INDEX: Symbol = symbol_short!("dlgidx");
+/// `u32`, length of the `dlgidx` sequence.
+const KEY_DELEGATE_INDEX_LEN: Symbol = symbol_short!("dlgidxn");
+/// `("dlgin", package_id)` -> `bool`, set once a package is in the `dlgidx` sequence.
+const KEY_DELEGATE_INDEXED: Symbol = symbol_short!("dlgin");
+
+/// Global layout used before per-package keys; read only by [`migrate_legacy`]
+/// and [`migrate_legacy_history`].
+const LEGACY_KEY_DELEGATES: Symbol = symbol_short!("dlgts");
+const LEGACY_KEY_DELEGATE_EXPIRY: Symbol = symbol_short!("dlgexp");
+const LEGACY_KEY_DELEGATE_HISTORY: Symbol = symbol_short!("dlgh");
+/// `u32`, position in the legacy history reached by [`migrate_legacy_history`].
+const KEY_LEGACY_HISTORY_CURSOR: Symbol = symbol_short!("dlghmig");
+
+/// Maximum number of delegates registered on one package.
+#[cfg(any(feature = "multi-delegates", feature = "nominations"))]
+pub const MAX_DELEGATES_PER_PACKAGE: u32 = 5;
+
+/// Maximum number of records returned or visited by one paged call.
+pub const MAX_DELEGATE_PAGE: u32 = 50;
 
-pub const KEY_DELEGATES: Symbol = symbol_short!("dlgts");
-pub const KEY_DELEGATE_HISTORY: Symbol = symbol_short!("dlgh");
-pub const KEY_DELEGATE_EXPIRY: Symbol = symbol_short!("dlgexp");
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DelegateEntry {
+    pub delegate: Address,
+    /// Expiration timestamp (0 = no expiration).
+    pub expires_at: u64,
+    pub added_by: Address,
+    pub added_at: u64,
+}
+
+#[cfg(feature = "nominations")]
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DelegateNomination {
+    pub delegate: Address,
+    pub expires_at: u64,
+    pub nominated_by: Address,
+    pub nominated_at: u64,
+}
 
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -31,72 +85,110 @@ pub struct DelegateHistory {
     pub reason: Symbol,
 }
 
-/// Loads the full delegate map from persistent storage.
-fn load_delegates(env: &Env) -> Map<u64, Address> {
+/// Result of one incremental [`cleanup_expired_delegates`] pass.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DelegateCleanup {
+    /// Expired delegate entries removed in this pass.
+    pub removed: u32,
+    /// Cursor to pass to the next call.
+    pub next_cursor: u32,
+    /// `true` once the cursor has reached the end of the index.
+    pub done: bool,
+}
+
+/// Result of one [`migrate_legacy_history`] pass.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryMigration {
+    /// Legacy history records moved to per-package keys in this pass.
+    pub migrated: u32,
+    /// Cursor to pass to the next call.
+    pub next_cursor: u32,
+    /// `true` once the legacy history has been moved and removed.
+    pub done: bool,
+}
+
+/// Returns the stored delegate set for `package_id`, including expired entries.
+fn load_entries(env: &Env, package_id: u64) -> Vec<DelegateEntry> {
     env.storage()
         .persistent()
-        .get(&KEY_DELEGATES)
-        .unwrap_or_else(|| Map::new(env))
+        .get(&(KEY_DELEGATE_SET, package_id))
+        .unwrap_or_else(|| Vec::new(env))
 }
 
-/// Persists the delegate map.
-fn save_delegates(env: &Env, map: &Map<u64, Address>) {
-    env.storage().persistent().set(&KEY_DELEGATES, map);
+/// Stores the delegate set for `package_id`, dropping the key when it is empty.
+fn save_entries(env: &Env, package_id: u64, entries: &Vec<DelegateEntry>) {
+    let key = (KEY_DELEGATE_SET, package_id);
+    if entries.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, entries);
+        index_package(env, package_id);
+    }
 }
 
-/// Loads delegate expiry information.
-fn load_delegate_expiry(env: &Env) -> Map<u64, u64> {
+/// Appends `package_id` to the cleanup index the first time it gets a delegate.
+fn index_package(env: &Env, package_id: u64) {
+    let flag = (KEY_DELEGATE_INDEXED, package_id);
+    if env.storage().persistent().has(&flag) {
+        return;
+    }
+    let len = index_len(env);
     env.storage()
         .persistent()
-        .get(&KEY_DELEGATE_EXPIRY)
-        .unwrap_or_else(|| Map::new(env))
+        .set(&(KEY_DELEGATE_INDEX, len), &package_id);
+    env.storage()
+        .persistent()
+        .set(&KEY_DELEGATE_INDEX_LEN, &(len + 1));
+    env.storage().persistent().set(&flag, &true);
 }
 
-/// Persists delegate expiry information.
-fn save_delegate_expiry(env: &Env, map: &Map<u64, u64>) {
-    env.storage().persistent().set(&KEY_DELEGATE_EXPIRY, map);
+fn index_len(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&KEY_DELEGATE_INDEX_LEN)
+        .unwrap_or(0)
 }
 
-/// Loads delegate history for audit trail.
-fn load_delegate_history(env: &Env) -> Vec<DelegateHistory> {
+fn history_len(env: &Env, package_id: u64) -> u32 {
     env.storage()
         .persistent()
-        .get(&KEY_DELEGATE_HISTORY)
-        .unwrap_or_else(|| Vec::new(env))
+        .get(&(KEY_DELEGATE_HISTORY_LEN, package_id))
+        .unwrap_or(0)
 }
 
-/// Persists delegate history.
-fn save_delegate_history(env: &Env, history: &Vec<DelegateHistory>) {
+/// Appends a record to the package's history.
+fn append_history(env: &Env, record: &DelegateHistory) {
+    let seq = history_len(env, record.package_id);
     env.storage()
         .persistent()
-        .set(&KEY_DELEGATE_HISTORY, history);
+        .set(&(KEY_DELEGATE_HISTORY, record.package_id, seq), record);
+    env.storage()
+        .persistent()
+        .set(&(KEY_DELEGATE_HISTORY_LEN, record.package_id), &(seq + 1));
 }
 
-/// Checks if a delegate has expired.
-fn is_delegate_expired(env: &Env, package_id: u64) -> bool {
-    let expiry_map = load_delegate_expiry(env);
-    if let Some(expires_at) = expiry_map.get(package_id) {
-        expires_at > 0 && env.ledger().timestamp() > expires_at
-    } else {
-        false
-    }
+/// Checks if a delegate entry has expired.
+fn is_entry_expired(env: &Env, entry: &DelegateEntry) -> bool {
+    entry.expires_at > 0 && env.ledger().timestamp() > entry.expires_at
 }
 
 /// Validates that a package exists and is in a valid state for delegate operations.
-fn validate_package_state(env: &Env, package_id: u64) -> Result<(), Error> {
+fn validate_package_state(env: &Env, package_id: u64) -> Result<crate::Package, Error> {
     let package_key = (symbol_short!("pkg"), package_id);
-    if !env.storage().persistent().has(&package_key) {
-        return Err(Error::PackageNotFound);
-    }
-
-    let package: crate::Package = env.storage().persistent().get(&package_key).unwrap();
+    let package: crate::Package = env
+        .storage()
+        .persistent()
+        .get(&package_key)
+        .ok_or(Error::PackageNotFound)?;
 
     // Cannot modify delegates for claimed packages
     if package.status == PackageStatus::Claimed {
         return Err(Error::PackageNotActive);
     }
 
-    Ok(())
+    Ok(package)
 }
 
 /// Records delegate change in history for audit trail.
@@ -108,41 +200,20 @@ fn record_delegate_change(
     changed_by: &Address,
     reason: Symbol,
 ) {
-    let mut history = load_delegate_history(env);
-    let record = DelegateHistory {
-        package_id,
-        previous_delegate,
-        new_delegate: new_delegate.clone(),
-        changed_by: changed_by.clone(),
-        changed_at: env.ledger().timestamp(),
-        reason,
-    };
-    history.push_back(record);
-    save_delegate_history(env, &history);
-}
-
-/// Records delegate change in history for audit trail (system version).
-fn record_delegate_change_system(
-    env: &Env,
-    package_id: u64,
-    previous_delegate: Option<Address>,
-    new_delegate: &Address,
-    reason: Symbol,
-) {
-    let mut history = load_delegate_history(env);
-    let record = DelegateHistory {
-        package_id,
-        previous_delegate,
-        new_delegate: new_delegate.clone(),
-        changed_by: env.current_contract_address(), // System uses contract address as placeholder
-        changed_at: env.ledger().timestamp(),
-        reason,
-    };
-    history.push_back(record);
-    save_delegate_history(env, &history);
+    append_history(
+        env,
+        &DelegateHistory {
+            package_id,
+            previous_delegate,
+            new_delegate: new_delegate.clone(),
+            changed_by: changed_by.clone(),
+            changed_at: env.ledger().timestamp(),
+            reason,
+        },
+    );
 }
 
-/// Register or update the delegate address for `package_id`.
+/// Replace the delegate set of `package_id` with the single address `delegate`.
 ///
 /// Only callable by the contract admin (caller must already be auth-checked
 /// by the outer contract function).  Fails if the package has already been
@@ -159,135 +230,527 @@ pub fn set_delegate(
     delegate: &Address,
 ) -> Result<(), Error> {
     // Admin auth is done by contract entry point
+    set_delegate_with_expiry(env, admin, package_id, delegate, 0)
+}
 
-    // Validate package state
-    validate_package_state(env, package_id)?;
+/// Replace the delegate set of `package_id` with `delegate`, expiring at `expires_at`.
+///
+/// # Arguments
+/// - `env` - Contract environment
+/// - `admin` - Admin address (must be authenticated)
+/// - `package_id` - Package ID to set delegate for
+/// - `delegate` - Delegate address
+/// - `expires_at` - Optional expiration timestamp (0 = no expiration)
+///
+/// # Errors
+/// - `Error::PackageNotFound` - Package doesn't exist
+/// - `Error::PackageNotActive` - Package already claimed
+/// - `Error::InvalidState` - Invalid delegate address or expiration
+pub fn set_delegate_with_expiry(
+    env: &Env,
+    admin: &Address,
+    package_id: u64,
+    delegate: &Address,
+    expires_at: u64,
+) -> Result<(), Error> {
+    let package = validate_package_state(env, package_id)?;
+    validate_new_entry(env, &package, delegate, expires_at)?;
 
-    // Get package to validate delegate is not the recipient
-    let package_key = (symbol_short!("pkg"), package_id);
-    let package: crate::Package = env.storage().persistent().get(&package_key).unwrap();
+    let previous = load_entries(env, package_id);
+    for entry in previous.iter() {
+        remove_entry_payout(env, package_id, &entry.delegate);
+    }
+    let previous_delegate = previous.first().map(|entry| entry.delegate);
+
+    let mut entries = Vec::new(env);
+    entries.push_back(DelegateEntry {
+        delegate: delegate.clone(),
+        expires_at,
+        added_by: admin.clone(),
+        added_at: env.ledger().timestamp(),
+    });
+    save_entries(env, package_id, &entries);
+
+    record_delegate_change(
+        env,
+        package_id,
+        previous_delegate,
+        delegate,
+        admin,
+        Symbol::new(env, "delegate_set"),
+    );
+
+    Ok(())
+}
 
+/// Rejects the recipient as a delegate and expirations in the past.
+fn validate_new_entry(
+    env: &Env,
+    package: &crate::Package,
+    delegate: &Address,
+    expires_at: u64,
+) -> Result<(), Error> {
     // Prevent setting delegate to the same address as recipient
     if delegate == &package.recipient {
         return Err(Error::InvalidState);
     }
+    if expires_at > 0 && expires_at <= env.ledger().timestamp() {
+        return Err(Error::InvalidState);
+    }
+    Ok(())
+}
 
-    let mut map = load_delegates(env);
-    let previous_delegate = map.get(package_id);
+/// Appends `delegate` to the delegate set of `package_id`.
+///
+/// If `delegate` is already in the set only its expiry is updated, keeping
+/// its position.  The caller (contract entry point) must already have
+/// authenticated `actor`.
+///
+/// # Errors
+/// - `Error::PackageNotFound` - Package doesn't exist
+/// - `Error::PackageNotActive` - Package already claimed
+/// - `Error::InvalidState` - Delegate is the recipient, or expiration is in the past
+/// - `Error::DelegateLimitReached` - The set already holds `MAX_DELEGATES_PER_PACKAGE` delegates
+#[cfg(any(feature = "multi-delegates", feature = "nominations"))]
+pub fn add_delegate(
+    env: &Env,
+    actor: &Address,
+    package_id: u64,
+    delegate: &Address,
+    expires_at: u64,
+    reason: Symbol,
+) -> Result<(), Error> {
+    let package = validate_package_state(env, package_id)?;
+    validate_new_entry(env, &package, delegate, expires_at)?;
+
+    let mut entries = load_entries(env, package_id);
+    let entry = DelegateEntry {
+        delegate: delegate.clone(),
+        expires_at,
+        added_by: actor.clone(),
+        added_at: env.ledger().timestamp(),
+    };
 
-    map.set(package_id, delegate.clone());
-    save_delegates(env, &map);
+    let previous_delegate = match position_of(&entries, delegate) {
+        Some(index) => {
+            entries.set(index, entry);
+            Some(delegate.clone())
+        }
+        None => {
+            if entries.len() >= MAX_DELEGATES_PER_PACKAGE {
+                return Err(Error::DelegateLimitReached);
+            }
+            entries.push_back(entry);
+            remove_entry_payout(env, package_id, delegate);
+            None
+        }
+    };
+    save_entries(env, package_id, &entries);
+
+    record_delegate_change(env, package_id, previous_delegate, delegate, actor, reason);
+
+    Ok(())
+}
+
+/// Removes `delegate` from the delegate set of `package_id`.
+/// The caller (contract entry point) must already have authenticated `actor`.
+///
+/// # Errors
+/// - `Error::DelegateNotFound` - `delegate` is not in the set
+#[cfg(any(feature = "multi-delegates", feature = "recovery"))]
+pub fn remove_delegate(
+    env: &Env,
+    actor: &Address,
+    package_id: u64,
+    delegate: &Address,
+) -> Result<DelegateEntry, Error> {
+    let mut entries = load_entries(env, package_id);
+    let index = position_of(&entries, delegate).ok_or(Error::DelegateNotFound)?;
+    let removed = entries.get(index).unwrap();
+    entries.remove(index);
+    save_entries(env, package_id, &entries);
+    remove_entry_payout(env, package_id, delegate);
 
-    // Record the change in history
     record_delegate_change(
         env,
         package_id,
-        previous_delegate,
+        Some(delegate.clone()),
         delegate,
-        admin,
-        Symbol::new(env, "delegate_set"),
+        actor,
+        Symbol::new(env, "delegate_removed"),
     );
 
-    Ok(())
+    Ok(removed)
+}
+
+#[cfg(any(
+    feature = "multi-delegates",
+    feature = "nominations",
+    feature = "payout-policies",
+    feature = "recovery"
+))]
+fn position_of(entries: &Vec<DelegateEntry>, delegate: &Address) -> Option<u32> {
+    entries
+        .iter()
+        .position(|entry| &entry.delegate == delegate)
+        .map(|index| index as u32)
 }
 
-/// Register or update the delegate address for `package_id` with expiration.
+/// Sets how many distinct active delegates must co-sign a delegate-initiated
+/// claim of `package_id`.  `0` and `1` both mean a single delegate suffices.
 ///
-/// Enhanced version that supports delegate expiration.
+/// # Errors
+/// - `Error::PackageNotFound` - Package doesn't exist
+/// - `Error::PackageNotActive` - Package already claimed
+/// - `Error::InvalidState` - Threshold exceeds `MAX_DELEGATES_PER_PACKAGE`
+#[cfg(feature = "multi-delegates")]
+pub fn set_threshold(env: &Env, package_id: u64, threshold: u32) -> Result<(), Error> {
+    validate_package_state(env, package_id)?;
+    if threshold > MAX_DELEGATES_PER_PACKAGE {
+        return Err(Error::InvalidState);
+    }
+
+    let key = (KEY_DELEGATE_THRESHOLD, package_id);
+    if threshold <= 1 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &threshold);
+    }
+    Ok(())
+}
+
+/// Returns the number of delegate signatures a delegate-initiated claim needs (at least 1).
+pub fn get_threshold(env: &Env, package_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&(KEY_DELEGATE_THRESHOLD, package_id))
+        .unwrap_or(1)
+}
+
+/// Records a recipient's nomination of `delegate` for `package_id`.
 ///
-/// # Arguments
-/// - `env` - Contract environment
-/// - `admin` - Admin address (must be authenticated)
-/// - `package_id` - Package ID to set delegate for
-/// - `delegate` - Delegate address
-/// - `expires_at` - Optional expiration timestamp (0 = no expiration)
+/// The nomination has no effect on claims until the nominee accepts it via
+/// [`accept_nomination`].  A new nomination replaces any pending one.
+/// The caller (contract entry point) must already have authenticated `recipient`.
 ///
 /// # Errors
 /// - `Error::PackageNotFound` - Package doesn't exist
 /// - `Error::PackageNotActive` - Package already claimed
-/// - `Error::InvalidState` - Invalid delegate address or expiration
-pub fn set_delegate_with_expiry(
+/// - `Error::InvalidState` - Nominee is the recipient, or expiration is in the past
+#[cfg(feature = "nominations")]
+pub fn nominate(
     env: &Env,
-    admin: &Address,
+    recipient: &Address,
     package_id: u64,
     delegate: &Address,
     expires_at: u64,
 ) -> Result<(), Error> {
-    // Validate expiration time
-    if expires_at > 0 && expires_at <= env.ledger().timestamp() {
+    validate_package_state(env, package_id)?;
+
+    if delegate == recipient {
         return Err(Error::InvalidState);
     }
 
-    // Set the delegate
-    set_delegate(env, admin, package_id, delegate)?;
-
-    // Set expiration if provided
-    if expires_at > 0 {
-        let mut expiry_map = load_delegate_expiry(env);
-        expiry_map.set(package_id, expires_at);
-        save_delegate_expiry(env, &expiry_map);
+    let now = env.ledger().timestamp();
+    if expires_at > 0 && expires_at <= now {
+        return Err(Error::InvalidState);
     }
 
+    let nomination = DelegateNomination {
+        delegate: delegate.clone(),
+        expires_at,
+        nominated_by: recipient.clone(),
+        nominated_at: now,
+    };
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("dnom"), package_id), &nomination);
+
+    record_delegate_change(
+        env,
+        package_id,
+        get_delegate(env, package_id),
+        delegate,
+        recipient,
+        Symbol::new(env, "delegate_nominated"),
+    );
+
     Ok(())
 }
 
-/// Returns the registered delegate for `package_id`, if any.
-/// Returns None if no delegate is set or if the delegate has expired.
+/// Returns the pending nomination for `package_id`, if any.
+#[cfg(feature = "nominations")]
+pub fn get_nomination(env: &Env, package_id: u64) -> Option<DelegateNomination> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("dnom"), package_id))
+}
+
+/// Adds the nominee of the pending nomination for `package_id` to its delegate set.
+/// The caller must already have authenticated `delegate`.
+///
+/// # Errors
+/// - `Error::NoPendingNomination` - No nomination, or it names another address
+/// - `Error::InvalidState` - The nominated delegation has already expired
+/// - `Error::DelegateLimitReached` - The delegate set is full
+#[cfg(feature = "nominations")]
+pub fn accept_nomination(
+    env: &Env,
+    package_id: u64,
+    delegate: &Address,
+) -> Result<DelegateNomination, Error> {
+    let nomination = get_nomination(env, package_id)
+        .filter(|n| &n.delegate == delegate)
+        .ok_or(Error::NoPendingNomination)?;
+
+    add_delegate(
+        env,
+        delegate,
+        package_id,
+        delegate,
+        nomination.expires_at,
+        Symbol::new(env, "delegate_accepted"),
+    )?;
+
+    env.storage()
+        .persistent()
+        .remove(&(symbol_short!("dnom"), package_id));
+
+    Ok(nomination)
+}
+
+/// Discards the pending nomination for `package_id`, recording `actor` and `reason`.
+/// The caller must already have authenticated `actor`.
+///
+/// # Errors
+/// - `Error::NoPendingNomination` - No nomination is pending
+#[cfg(feature = "nominations")]
+pub fn discard_nomination(
+    env: &Env,
+    package_id: u64,
+    actor: &Address,
+    reason: Symbol,
+) -> Result<DelegateNomination, Error> {
+    let nomination = get_nomination(env, package_id).ok_or(Error::NoPendingNomination)?;
+
+    env.storage()
+        .persistent()
+        .remove(&(symbol_short!("dnom"), package_id));
+
+    record_delegate_change(
+        env,
+        package_id,
+        get_delegate(env, package_id),
+        &nomination.delegate,
+        actor,
+        reason,
+    );
+
+    Ok(nomination)
+}
+
+/// Returns the first active (non-expired) delegate for `package_id`, if any.
 pub fn get_delegate(env: &Env, package_id: u64) -> Option<Address> {
-    // Check if delegate exists
-    let delegate = load_delegates(env).get(package_id)?;
+    load_entries(env, package_id)
+        .iter()
+        .find(|entry| !is_entry_expired(env, entry))
+        .map(|entry| entry.delegate)
+}
 
-    // Check if delegate has expired
-    if is_delegate_expired(env, package_id) {
-        return None;
+/// Returns every active (non-expired) delegate for `package_id`, oldest first.
+pub fn get_delegates(env: &Env, package_id: u64) -> Vec<Address> {
+    let mut delegates = Vec::new(env);
+    for entry in load_entries(env, package_id).iter() {
+        if !is_entry_expired(env, &entry) {
+            delegates.push_back(entry.delegate);
+        }
     }
+    delegates
+}
+
+/// Returns the full delegate set including expiration, expired entries included.
+pub fn get_delegate_info(env: &Env, package_id: u64) -> Vec<DelegateEntry> {
+    load_entries(env, package_id)
+}
 
-    Some(delegate)
+/// Sets or clears the payout policy of one delegate of `package_id`,
+/// stored under `("dpay", package_id, delegate)`.
+///
+/// # Errors
+/// - `Error::PackageNotFound` - Package doesn't exist
+/// - `Error::PackageNotActive` - Package already claimed
+/// - `Error::DelegateNotFound` - `delegate` is not in the set
+#[cfg(feature = "payout-policies")]
+pub fn set_entry_payout(
+    env: &Env,
+    package_id: u64,
+    delegate: &Address,
+    payout: Option<PayoutPolicy>,
+) -> Result<(), Error> {
+    validate_package_state(env, package_id)?;
+    if position_of(&load_entries(env, package_id), delegate).is_none() {
+        return Err(Error::DelegateNotFound);
+    }
+    match payout {
+        Some(policy) => env.storage().persistent().set(
+            &(symbol_short!("dpay"), package_id, delegate.clone()),
+            &policy,
+        ),
+        None => remove_entry_payout(env, package_id, delegate),
+    }
+    Ok(())
 }
 
-/// Returns the delegate information including expiration.
-pub fn get_delegate_info(env: &Env, package_id: u64) -> Option<(Address, Option<u64>)> {
-    let delegate = load_delegates(env).get(package_id)?;
-    let expiry_map = load_delegate_expiry(env);
-    let expires_at = expiry_map.get(package_id);
+/// Returns the payout policy set on one delegate of `package_id`, if any.
+pub fn get_entry_payout(env: &Env, package_id: u64, delegate: &Address) -> Option<PayoutPolicy> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("dpay"), package_id, delegate.clone()))
+}
 
-    Some((delegate, expires_at))
+fn remove_entry_payout(env: &Env, package_id: u64, delegate: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&(symbol_short!("dpay"), package_id, delegate.clone()));
 }
 
-/// Returns the delegate history for a package.
-pub fn get_delegate_history(env: &Env, package_id: u64) -> Vec<DelegateHistory> {
-    let all_history = load_delegate_history(env);
-    let mut package_history = Vec::new(env);
+/// Returns `true` if `address` is an active delegate of `package_id`.
+pub fn is_active_delegate(env: &Env, package_id: u64, address: &Address) -> bool {
+    load_entries(env, package_id)
+        .iter()
+        .any(|entry| &entry.delegate == address && !is_entry_expired(env, &entry))
+}
 
-    for record in all_history.iter() {
-        if record.package_id == package_id {
-            package_history.push_back(record.clone());
+/// Returns up to `limit` history records of a package, oldest first,
+/// starting at record number `cursor`.  `limit` is capped at `MAX_DELEGATE_PAGE`.
+pub fn get_delegate_history(
+    env: &Env,
+    package_id: u64,
+    cursor: u32,
+    limit: u32,
+) -> Vec<DelegateHistory> {
+    let len = history_len(env, package_id);
+    let end = cursor.saturating_add(limit.min(MAX_DELEGATE_PAGE)).min(len);
+
+    let mut page = Vec::new(env);
+    for seq in cursor..end {
+        if let Some(record) =
+            env.storage()
+                .persistent()
+                .get(&(KEY_DELEGATE_HISTORY, package_id, seq))
+        {
+            page.push_back(record);
         }
     }
+    page
+}
 
-    package_history
+/// Returns the number of history records stored for a package.
+pub fn get_delegate_history_len(env: &Env, package_id: u64) -> u32 {
+    history_len(env, package_id)
 }
 
-/// Returns `true` when `claimer` is authorised to claim `package_id`.
+/// How a claimer is entitled to act on a package.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ClaimAuthority {
+    Recipient,
+    PackageDelegate,
+    StandingDelegate,
+}
+
+/// Checks that `claimer` may claim `package_id` on its own signature.
 ///
-/// Authorised means: claimer == primary_recipient OR claimer == delegate (and not expired).
-#[allow(dead_code)]
-pub fn is_authorised_claimer(
+/// Precedence: the primary recipient, then the package's own delegate set,
+/// then a standing delegation of the recipient whose scope covers the package.
+/// When the package requires co-signed delegate claims, neither kind of
+/// delegate may claim alone.
+///
+/// # Errors
+/// - `Error::NotAuthorized` - Claimer is neither the recipient nor an active delegate
+/// - `Error::DelegateThresholdNotMet` - Claimer is a delegate but the package requires co-signers
+pub fn check_claimer(
     env: &Env,
     package_id: u64,
     primary_recipient: &Address,
     claimer: &Address,
-) -> bool {
+) -> Result<ClaimAuthority, Error> {
     // Primary recipient is always authorized
     if claimer == primary_recipient {
-        return true;
+        return Ok(ClaimAuthority::Recipient);
     }
 
-    // Check delegate (includes expiration check)
-    match get_delegate(env, package_id) {
-        Some(delegate) => &delegate == claimer,
-        None => false,
+    // Check delegate (includes expiration check), then standing delegations
+    let authority = if is_active_delegate(env, package_id, claimer) {
+        ClaimAuthority::PackageDelegate
+    } else if is_standing_delegate(env, package_id, claimer) {
+        ClaimAuthority::StandingDelegate
+    } else {
+        return Err(Error::NotAuthorized);
+    };
+
+    if get_threshold(env, package_id) > 1 {
+        return Err(Error::DelegateThresholdNotMet);
     }
+
+    Ok(authority)
+}
+
+/// Returns `true` if a standing delegation of the package recipient covers `claimer`.
+#[cfg(feature = "standing-delegates")]
+pub fn is_standing_delegate(env: &Env, package_id: u64, claimer: &Address) -> bool {
+    let package: Option<crate::Package> = env
+        .storage()
+        .persistent()
+        .get(&(symbol_short!("pkg"), package_id));
+    package.is_some_and(|p| crate::standing::find_for_package(env, &p, claimer).is_some())
+}
+
+/// Without standing delegations no claimer is a standing delegate.
+#[cfg(not(feature = "standing-delegates"))]
+pub fn is_standing_delegate(_env: &Env, _package_id: u64, _claimer: &Address) -> bool {
+    false
+}
+
+/// Checks that `signers` are distinct active delegates of `package_id` and
+/// that there are at least as many as the package threshold.
+///
+/// # Errors
+/// - `Error::NotAuthorized` - Empty list, duplicate signer, or a signer is not an active delegate
+/// - `Error::DelegateThresholdNotMet` - Fewer signers than the package threshold
+#[cfg(feature = "multi-delegates")]
+pub fn check_cosigners(env: &Env, package_id: u64, signers: &Vec<Address>) -> Result<(), Error> {
+    if signers.is_empty() {
+        return Err(Error::NotAuthorized);
+    }
+
+    for (i, signer) in signers.iter().enumerate() {
+        if !is_active_delegate(env, package_id, &signer) {
+            return Err(Error::NotAuthorized);
+        }
+        if signers.iter().skip(i + 1).any(|other| other == signer) {
+            return Err(Error::NotAuthorized);
+        }
+    }
+
+    if signers.len() < get_threshold(env, package_id) {
+        return Err(Error::DelegateThresholdNotMet);
+    }
+
+    Ok(())
+}
+
+/// Returns `true` when `claimer` is authorised to claim `package_id`.
+///
+/// Authorised means: claimer == primary_recipient OR claimer is an active
+/// package or standing delegate and the package does not require co-signers.
+#[allow(dead_code)]
+pub fn is_authorised_claimer(
+    env: &Env,
+    package_id: u64,
+    primary_recipient: &Address,
+    claimer: &Address,
+) -> bool {
+    check_claimer(env, package_id, primary_recipient, claimer).is_ok()
 }
 
 /// Returns detailed authorization information for debugging/auditing.
@@ -304,84 +767,215 @@ pub fn get_authorization_info(
     }
 
     // Check delegate status
-    let delegate_info = get_delegate_info(env, package_id);
-    match delegate_info {
-        Some((delegate, expires_at)) => {
-            if &delegate == claimer {
-                if let Some(expiry) = expires_at {
-                    if expiry > env.ledger().timestamp() {
-                        (true, Some(Symbol::new(env, "delegate_with_expiry")))
-                    } else {
-                        (false, Some(Symbol::new(env, "delegate_expired")))
-                    }
-                } else {
-                    (true, Some(Symbol::new(env, "delegate_no_expiry")))
-                }
-            } else {
-                (false, Some(Symbol::new(env, "not_registered_delegate")))
-            }
+    let entries = load_entries(env, package_id);
+    if !entries.iter().any(|entry| &entry.delegate == claimer)
+        && is_standing_delegate(env, package_id, claimer)
+    {
+        return if get_threshold(env, package_id) > 1 {
+            (false, Some(Symbol::new(env, "cosigners_required")))
+        } else {
+            (true, Some(Symbol::new(env, "standing_delegate")))
+        };
+    }
+    if entries.is_empty() {
+        return (false, Some(Symbol::new(env, "no_delegate_registered")));
+    }
+
+    match entries.iter().find(|entry| &entry.delegate == claimer) {
+        Some(entry) if is_entry_expired(env, &entry) => {
+            (false, Some(Symbol::new(env, "delegate_expired")))
+        }
+        Some(_) if get_threshold(env, package_id) > 1 => {
+            (false, Some(Symbol::new(env, "cosigners_required")))
+        }
+        Some(entry) if entry.expires_at > 0 => {
+            (true, Some(Symbol::new(env, "delegate_with_expiry")))
         }
-        None => (false, Some(Symbol::new(env, "no_delegate_registered"))),
+        Some(_) => (true, Some(Symbol::new(env, "delegate_no_expiry"))),
+        None => (false, Some(Symbol::new(env, "not_registered_delegate"))),
     }
 }
 
-/// Remove the delegate for `package_id` (call after a successful claim to
-/// prevent any further reassignment).
-pub fn clear_delegate(env: &Env, package_id: u64) {
-    let mut map = load_delegates(env);
-    let previous_delegate = map.get(package_id);
-
-    map.remove(package_id);
-    save_delegates(env, &map);
+/// Remove every delegate and the approval threshold for `package_id` (call
+/// after a successful claim to prevent any further reassignment).
+/// Returns the removed entries.
+pub fn clear_delegate(env: &Env, package_id: u64) -> Vec<DelegateEntry> {
+    let entries = load_entries(env, package_id);
+    if !entries.is_empty() {
+        save_entries(env, package_id, &Vec::new(env));
+    }
 
-    // Also clear expiration
-    let mut expiry_map = load_delegate_expiry(env);
-    expiry_map.remove(package_id);
-    save_delegate_expiry(env, &expiry_map);
+    env.storage()
+        .persistent()
+        .remove(&(KEY_DELEGATE_THRESHOLD, package_id));
 
-    // Record the removal in history if there was a delegate
-    if let Some(delegate) = previous_delegate {
-        record_delegate_change_system(
+    // Record the removal in history; the contract address stands in for the system actor
+    let system = env.current_contract_address();
+    for entry in entries.iter() {
+        remove_entry_payout(env, package_id, &entry.delegate);
+        record_delegate_change(
             env,
             package_id,
-            Some(delegate),
-            &env.current_contract_address(),
+            Some(entry.delegate),
+            &system,
+            &system,
             Symbol::new(env, "delegate_cleared"),
         );
     }
+
+    entries
 }
 
-/// Cleanup expired delegates to reclaim storage.
-/// This should be called periodically or as part of maintenance operations.
-#[allow(dead_code)]
-pub fn cleanup_expired_delegates(env: &Env, _caller: &Address) -> Result<u32, Error> {
+/// Removes expired delegate entries from up to `limit` indexed packages,
+/// starting at index position `cursor`.  `limit` is capped at `MAX_DELEGATE_PAGE`,
+/// so each call does bounded work; callers resume from `next_cursor` until `done`.
+/// Each removal is recorded in the package history with reason `expired` and
+/// emits `DelegateRemoved`, with `caller` as the actor.
+pub fn cleanup_expired_delegates(
+    env: &Env,
+    caller: &Address,
+    cursor: u32,
+    limit: u32,
+) -> Result<DelegateCleanup, Error> {
     // Authentication is enforced by the calling contract entry point.
 
-    let mut delegate_map = load_delegates(env);
-    let mut expiry_map = load_delegate_expiry(env);
-    let mut cleaned_count = 0u32;
-    let now = env.ledger().timestamp();
+    let len = index_len(env);
+    let end = cursor.saturating_add(limit.min(MAX_DELEGATE_PAGE)).min(len);
+    let mut removed = 0u32;
+    let reason = symbol_short!("expired");
+
+    for position in cursor..end {
+        let package_id: u64 = match env
+            .storage()
+            .persistent()
+            .get(&(KEY_DELEGATE_INDEX, position))
+        {
+            Some(id) => id,
+            None => continue,
+        };
+        let package: crate::Package = match env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("pkg"), package_id))
+        {
+            Some(package) => package,
+            None => continue,
+        };
 
-    // Collect expired delegate IDs first to avoid modifying map during iteration
-    let mut expired_ids = Vec::new(env);
-    for (package_id, expires_at) in expiry_map.iter() {
-        if expires_at > 0 && now > expires_at {
-            expired_ids.push_back(package_id);
+        let entries = load_entries(env, package_id);
+        let mut kept = Vec::new(env);
+        let mut expired = Vec::new(env);
+        for entry in entries.iter() {
+            if is_entry_expired(env, &entry) {
+                expired.push_back(entry);
+            } else {
+                kept.push_back(entry);
+            }
         }
+        if expired.is_empty() {
+            continue;
+        }
+
+        removed += expired.len();
+        save_entries(env, package_id, &kept);
+        for entry in expired.iter() {
+            remove_entry_payout(env, package_id, &entry.delegate);
+            record_delegate_change(
+                env,
+                package_id,
+                Some(entry.delegate.clone()),
+                &entry.delegate,
+                caller,
+                reason.clone(),
+            );
+            crate::DelegateRemoved {
+                schema_version: crate::EVENT_SCHEMA_VERSION,
+                package_id,
+                recipient: package.recipient.clone(),
+                delegate: entry.delegate,
+                reason: reason.clone(),
+                actor: caller.clone(),
+                timestamp: env.ledger().timestamp(),
+            }
+            .publish(env);
+        }
+    }
+
+    Ok(DelegateCleanup {
+        removed,
+        next_cursor: end,
+        done: end >= len,
+    })
+}
+
+/// Moves the delegates kept in global maps by earlier versions into
+/// per-package keys.  Their global history is moved separately, page by page,
+/// by [`migrate_legacy_history`].
+pub fn migrate_legacy(env: &Env) {
+    let storage = env.storage().persistent();
+    let system = env.current_contract_address();
+
+    let single: Map<u64, Address> = storage
+        .get(&LEGACY_KEY_DELEGATES)
+        .unwrap_or_else(|| Map::new(env));
+    let expiry: Map<u64, u64> = storage
+        .get(&LEGACY_KEY_DELEGATE_EXPIRY)
+        .unwrap_or_else(|| Map::new(env));
+    for (package_id, delegate) in single.iter() {
+        let mut entries = Vec::new(env);
+        entries.push_back(DelegateEntry {
+            delegate,
+            expires_at: expiry.get(package_id).unwrap_or(0),
+            added_by: system.clone(),
+            added_at: env.ledger().timestamp(),
+        });
+        save_entries(env, package_id, &entries);
+    }
+
+    storage.remove(&LEGACY_KEY_DELEGATES);
+    storage.remove(&LEGACY_KEY_DELEGATE_EXPIRY);
+}
+
+/// Appends at most `limit` (capped at [`MAX_DELEGATE_PAGE`]) records of the
+/// global history kept by earlier versions to the per-package history,
+/// starting at `cursor`, which must be where the previous pass stopped.  The
+/// global history is removed once every record has been moved.
+///
+/// # Errors
+/// - `Error::InvalidState` - `cursor` is not where the previous pass stopped
+pub fn migrate_legacy_history(
+    env: &Env,
+    cursor: u32,
+    limit: u32,
+) -> Result<HistoryMigration, Error> {
+    let storage = env.storage().persistent();
+    let reached: u32 = storage.get(&KEY_LEGACY_HISTORY_CURSOR).unwrap_or(0);
+    if cursor != reached {
+        return Err(Error::InvalidState);
     }
 
-    // Remove expired delegates
-    for package_id in expired_ids.iter() {
-        delegate_map.remove(package_id);
-        expiry_map.remove(package_id);
-        cleaned_count += 1;
+    let history: Vec<DelegateHistory> = storage
+        .get(&LEGACY_KEY_DELEGATE_HISTORY)
+        .unwrap_or_else(|| Vec::new(env));
+    let len = history.len();
+    let end = cursor.saturating_add(limit.min(MAX_DELEGATE_PAGE)).min(len);
+    for position in cursor..end {
+        append_history(env, &history.get_unchecked(position));
     }
 
-    // Save changes
-    save_delegates(env, &delegate_map);
-    save_delegate_expiry(env, &expiry_map);
+    let done = end >= len;
+    if done {
+        storage.remove(&LEGACY_KEY_DELEGATE_HISTORY);
+        storage.remove(&KEY_LEGACY_HISTORY_CURSOR);
+    } else {
+        storage.set(&KEY_LEGACY_HISTORY_CURSOR, &end);
+    }
 
-    Ok(cleaned_count)
+    Ok(HistoryMigration {
+        migrated: end - cursor,
+        next_cursor: end,
+        done,
+    })
 }
 
 #[cfg(test)]
@@ -534,7 +1128,7 @@ mod tests {
             set_delegate(&env, &admin, 1, &delegate1).unwrap();
             set_delegate(&env, &admin, 1, &delegate2).unwrap();
 
-            let history = get_delegate_history(&env, 1);
+            let history = get_delegate_history(&env, 1, 0, 50);
             assert_eq!(history.len(), 2);
 
             let first_record = history.get(0).unwrap();
@@ -603,13 +1197,121 @@ mod tests {
         env.ledger().with_mut(|li| li.timestamp = now + 100);
 
         let cleaned = env
-            .as_contract(&contract, || cleanup_expired_delegates(&env, &admin))
+            .as_contract(&contract, || cleanup_expired_delegates(&env, &admin, 0, 10))
             .unwrap();
-        assert_eq!(cleaned, 1);
+        assert_eq!(cleaned.removed, 1);
 
         env.as_contract(&contract, || {
             assert_eq!(get_delegate(&env, 1), None);
             assert_eq!(get_delegate(&env, 2), Some(delegate2));
         });
     }
+
+    #[test]
+    #[cfg(feature = "multi-delegates")]
+    fn threshold_blocks_single_delegate_authorisation() {
+        let (env, contract) = setup();
+        let recipient = Address::generate(&env);
+        let delegate1 = Address::generate(&env);
+        let delegate2 = Address::generate(&env);
+        let admin = Address::generate(&env);
+
+        create_test_package(&env, &contract, 1, &recipient, PackageStatus::Created);
+        env.as_contract(&contract, || {
+            let reason = Symbol::new(&env, "delegate_added");
+            add_delegate(&env, &admin, 1, &delegate1, 0, reason.clone()).unwrap();
+            add_delegate(&env, &admin, 1, &delegate2, 0, reason).unwrap();
+            set_threshold(&env, 1, 2).unwrap();
+
+            assert!(is_authorised_claimer(&env, 1, &recipient, &recipient));
+            assert_eq!(
+                check_claimer(&env, 1, &recipient, &delegate1),
+                Err(Error::DelegateThresholdNotMet)
+            );
+
+            let mut signers = Vec::new(&env);
+            signers.push_back(delegate1.clone());
+            assert_eq!(
+                check_cosigners(&env, 1, &signers),
+                Err(Error::DelegateThresholdNotMet)
+            );
+            signers.push_back(delegate2.clone());
+            assert_eq!(check_cosigners(&env, 1, &signers), Ok(()));
+
+            clear_delegate(&env, 1);
+            assert_eq!(get_threshold(&env, 1), 1);
+            assert_eq!(get_delegate_info(&env, 1).len(), 0);
+        });
+    }
+
+    #[test]
+    fn migrate_legacy_moves_global_maps_to_package_keys() {
+        let (env, contract) = setup();
+        let recipient = Address::generate(&env);
+        let delegate = Address::generate(&env);
+
+        create_test_package(&env, &contract, 3, &recipient, PackageStatus::Created);
+        env.as_contract(&contract, || {
+            let mut single = Map::new(&env);
+            single.set(3u64, delegate.clone());
+            let mut expiry = Map::new(&env);
+            expiry.set(3u64, 5_000u64);
+            env.storage()
+                .persistent()
+                .set(&LEGACY_KEY_DELEGATES, &single);
+            env.storage()
+                .persistent()
+                .set(&LEGACY_KEY_DELEGATE_EXPIRY, &expiry);
+
+            migrate_legacy(&env);
+
+            let info = get_delegate_info(&env, 3);
+            assert_eq!(info.len(), 1);
+            assert_eq!(info.get(0).unwrap().delegate, delegate);
+            assert_eq!(info.get(0).unwrap().expires_at, 5_000);
+            assert!(!env.storage().persistent().has(&LEGACY_KEY_DELEGATES));
+        });
+    }
+
+    #[test]
+    fn migrate_legacy_history_moves_records_in_pages() {
+        let (env, contract) = setup();
+        let recipient = Address::generate(&env);
+        let delegate = Address::generate(&env);
+
+        create_test_package(&env, &contract, 3, &recipient, PackageStatus::Created);
+        env.as_contract(&contract, || {
+            let mut history = Vec::new(&env);
+            for changed_at in 0..3u64 {
+                history.push_back(DelegateHistory {
+                    package_id: 3,
+                    previous_delegate: None,
+                    new_delegate: delegate.clone(),
+                    changed_by: recipient.clone(),
+                    changed_at,
+                    reason: symbol_short!("set"),
+                });
+            }
+            env.storage()
+                .persistent()
+                .set(&LEGACY_KEY_DELEGATE_HISTORY, &history);
+
+            let first = migrate_legacy_history(&env, 0, 2).unwrap();
+            assert_eq!(
+                (first.migrated, first.next_cursor, first.done),
+                (2, 2, false)
+            );
+            assert_eq!(migrate_legacy_history(&env, 0, 2), Err(Error::InvalidState));
+            let second = migrate_legacy_history(&env, 2, 2).unwrap();
+            assert_eq!(
+                (second.migrated, second.next_cursor, second.done),
+                (1, 3, true)
+            );
+
+            let moved = get_delegate_history(&env, 3, 0, 10);
+            assert_eq!(moved.len(), 3);
+            assert_eq!(moved.get(2).unwrap().changed_at, 2);
+            assert!(!env.storage().persistent().has(&LEGACY_KEY_DELEGATE_HISTORY));
+        });
+    }
 }
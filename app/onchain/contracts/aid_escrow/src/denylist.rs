@@ -0,0 +1,129 @@
+//! Address denylist.
+//!
+//! The admin or a `Compliance` role holder can deny an address with a reason
+//! code (its meaning, e.g. sanctions match or fraud investigation, is agreed
+//! off-chain).  A denied address cannot be given new packages, collect,
+//! relay or be paid a payout, redeem or be redeemed at, or receive a surplus
+//! withdrawal.  Its packages are not touched, so removing it from the list
+//! restores them as they were.
+//!
+//! Storage: `("deny", address)` -> `DenyEntry`, `("denyidx", i)` -> the
+//! address at position `i` of the listing, `"denycnt"` (instance) -> number of
+//! denied addresses.  Removal moves the last address into the freed slot, so
+//! the listing order is not stable across removals.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+/// Maximum entries returned by one `get_denylist` page.
+pub const MAX_DENYLIST_PAGE: u32 = 50;
+
+const KEY_DENY_COUNT: Symbol = symbol_short!("denycnt");
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DenyEntry {
+    pub address: Address,
+    pub reason: u32,
+    pub added_by: Address,
+    pub added_at: u64,
+    /// Position in the listing.
+    pub index: u32,
+}
+
+/// Returns the number of denied addresses.
+pub fn count(env: &Env) -> u32 {
+    env.storage().instance().get(&KEY_DENY_COUNT).unwrap_or(0)
+}
+
+/// Returns the entry of `address`, if denied.
+pub fn get(env: &Env, address: &Address) -> Option<DenyEntry> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("deny"), address.clone()))
+}
+
+/// Returns `true` if `address` is denied.  Skips the lookup while the list
+/// is empty.
+pub fn is_denied(env: &Env, address: &Address) -> bool {
+    count(env) > 0
+        && env
+            .storage()
+            .persistent()
+            .has(&(symbol_short!("deny"), address.clone()))
+}
+
+/// Denies `address`, or updates its reason if already denied.
+pub fn add(env: &Env, address: &Address, reason: u32, added_by: &Address) -> DenyEntry {
+    let index = match get(env, address) {
+        Some(entry) => entry.index,
+        None => {
+            let index = count(env);
+            env.storage()
+                .persistent()
+                .set(&(symbol_short!("denyidx"), index), address);
+            env.storage().instance().set(&KEY_DENY_COUNT, &(index + 1));
+            index
+        }
+    };
+    let entry = DenyEntry {
+        address: address.clone(),
+        reason,
+        added_by: added_by.clone(),
+        added_at: env.ledger().timestamp(),
+        index,
+    };
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("deny"), address.clone()), &entry);
+    entry
+}
+
+/// Removes `address` from the list.  Returns its entry, or `None` if it was
+/// not denied.
+pub fn remove(env: &Env, address: &Address) -> Option<DenyEntry> {
+    let entry = get(env, address)?;
+    let last = count(env) - 1;
+    if entry.index != last {
+        let moved: Address = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("denyidx"), last))
+            .unwrap();
+        let mut moved_entry = get(env, &moved).unwrap();
+        moved_entry.index = entry.index;
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("deny"), moved.clone()), &moved_entry);
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("denyidx"), entry.index), &moved);
+    }
+    env.storage()
+        .persistent()
+        .remove(&(symbol_short!("denyidx"), last));
+    env.storage()
+        .persistent()
+        .remove(&(symbol_short!("deny"), address.clone()));
+    env.storage().instance().set(&KEY_DENY_COUNT, &last);
+    Some(entry)
+}
+
+/// Returns up to `limit` entries (capped at `MAX_DENYLIST_PAGE`) starting at
+/// position `cursor`.
+pub fn page(env: &Env, cursor: u32, limit: u32) -> Vec<DenyEntry> {
+    let end = cursor
+        .saturating_add(limit.min(MAX_DENYLIST_PAGE))
+        .min(count(env));
+
+    let mut entries = Vec::new(env);
+    for index in cursor..end {
+        let address: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("denyidx"), index));
+        if let Some(entry) = address.and_then(|address| get(env, &address)) {
+            entries.push_back(entry);
+        }
+    }
+    entries
+}
@@ -0,0 +1,44 @@
+//! Per-campaign eligibility checks against a credential issuer.
+//!
+//! The admin can point a campaign at an issuer contract.  Before a package in
+//! that campaign is paid out, the escrow asks the issuer whether the package
+//! recipient is still eligible; a revoked or expired credential blocks the
+//! payout.  A rejected payout rolls back its own events, so failures are
+//! published by `check_eligibility`, which wallets and monitors call instead.
+//!
+//! Storage: `("eligiss", campaign)` -> issuer `Address`.
+
+use soroban_sdk::{contractclient, symbol_short, Address, Env, String};
+
+/// Returns the issuer configured for `campaign`, if any.
+pub fn get_issuer(env: &Env, campaign: &String) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("eligiss"), campaign.clone()))
+}
+
+/// Sets or, with `None`, removes the issuer of `campaign`.
+pub fn set_issuer(env: &Env, campaign: &String, issuer: &Option<Address>) {
+    let key = (symbol_short!("eligiss"), campaign.clone());
+    match issuer {
+        Some(issuer) => env.storage().persistent().set(&key, issuer),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Interface an eligibility issuer implements.  The escrow calls it through
+/// `EligibilityIssuerClient::try_is_eligible`, so an issuer that is missing,
+/// fails or returns anything but `true` blocks the payout.
+#[contractclient(name = "EligibilityIssuerClient")]
+pub trait EligibilityIssuer {
+    /// Returns `true` while `recipient` holds a valid credential for `campaign`.
+    fn is_eligible(env: Env, recipient: Address, campaign: String) -> bool;
+}
+
+/// Asks `issuer` whether `recipient` is eligible in `campaign`.
+pub fn is_eligible(env: &Env, issuer: &Address, recipient: &Address, campaign: &String) -> bool {
+    matches!(
+        EligibilityIssuerClient::new(env, issuer).try_is_eligible(recipient, campaign),
+        Ok(Ok(true))
+    )
+}
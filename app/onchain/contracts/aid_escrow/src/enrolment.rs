@@ -0,0 +1,102 @@
+//! Per-campaign enrolment limits.
+//!
+//! Every package in a campaign is counted against its recipient in a
+//! `(campaign, recipient)` index.  The admin can cap, per campaign, how many
+//! packages and how much in total a recipient may be allocated; one package
+//! per recipient is `max_packages = 1`.  Cancelling or refunding a package
+//! gives back what it had not paid out, and its slot if it paid out nothing.
+//! Moving a package to another campaign (metadata amendment) or recipient
+//! (recovery) moves its enrolment, and the new campaign's limits are checked
+//! again.  Hash-locked packages have no recipient yet; they are counted
+//! against their payout address when the secret is redeemed.
+//!
+//! Storage: `("enrpol", campaign)` -> `EnrolmentPolicy`, `("enrol", campaign,
+//! recipient)` -> `Enrolment`.
+
+use soroban_sdk::{contracttype, panic_with_error, symbol_short, Address, Env, String};
+
+use crate::PolicyError;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnrolmentPolicy {
+    /// Maximum packages per recipient (0 = unlimited).
+    pub max_packages: u32,
+    /// Maximum total allocated per recipient (0 = unlimited).  Amounts of
+    /// different tokens are added as they are.
+    pub max_amount: i128,
+}
+
+/// What a recipient has been allocated in a campaign.
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Enrolment {
+    pub packages: u32,
+    pub amount: i128,
+}
+
+/// Returns the policy of `campaign`, if any.
+pub fn get_policy(env: &Env, campaign: &String) -> Option<EnrolmentPolicy> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("enrpol"), campaign.clone()))
+}
+
+/// Sets or, with `None`, removes the policy of `campaign`.
+pub fn set_policy(env: &Env, campaign: &String, policy: &Option<EnrolmentPolicy>) {
+    let key = (symbol_short!("enrpol"), campaign.clone());
+    match policy {
+        Some(policy) => env.storage().persistent().set(&key, policy),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Returns what `recipient` has been allocated in `campaign`.
+pub fn get(env: &Env, campaign: &String, recipient: &Address) -> Enrolment {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("enrol"), campaign.clone(), recipient.clone()))
+        .unwrap_or_default()
+}
+
+fn save(env: &Env, campaign: &String, recipient: &Address, enrolment: &Enrolment) {
+    env.storage().persistent().set(
+        &(symbol_short!("enrol"), campaign.clone(), recipient.clone()),
+        enrolment,
+    );
+}
+
+/// Counts a new package of `amount` for `recipient` in `campaign`, rejecting
+/// it if the campaign policy would be exceeded.
+pub fn enroll(env: &Env, campaign: &String, recipient: &Address, amount: i128) {
+    if *recipient == env.current_contract_address() {
+        return;
+    }
+
+    let mut enrolment = get(env, campaign, recipient);
+    enrolment.packages += 1;
+    enrolment.amount += amount;
+
+    if let Some(policy) = get_policy(env, campaign) {
+        if (policy.max_packages > 0 && enrolment.packages > policy.max_packages)
+            || (policy.max_amount > 0 && enrolment.amount > policy.max_amount)
+        {
+            panic_with_error!(env, PolicyError::EnrolmentLimitReached);
+        }
+    }
+    save(env, campaign, recipient, &enrolment);
+}
+
+/// Gives back `amount` of a cancelled or refunded package, and its slot when
+/// `whole` (nothing was paid out).
+pub fn release(env: &Env, campaign: &String, recipient: &Address, amount: i128, whole: bool) {
+    let mut enrolment = get(env, campaign, recipient);
+    if enrolment == Enrolment::default() {
+        return;
+    }
+    enrolment.amount = (enrolment.amount - amount).max(0);
+    if whole {
+        enrolment.packages = enrolment.packages.saturating_sub(1);
+    }
+    save(env, campaign, recipient, &enrolment);
+}
@@ -0,0 +1,159 @@
+//! Protocol fees routed to the treasury.
+//!
+//! A fee is configured in basis points per token or per campaign (the
+//! package `campaign_id` term); a campaign fee wins over the token fee.
+//! It is charged either when the package is created, on top of the package
+//! amount, or when the package is paid out, deducted from the payout.  The
+//! claim-time rate is snapshotted on the package at creation so later
+//! configuration changes do not affect existing packages.
+//!
+//! Charged fees stay in the contract until `collect_fees` sweeps them to the
+//! treasury; until then they are excluded from the `withdraw_surplus` surplus.
+//! Creation-time fees are not returned when a package is revoked or refunded.
+//!
+//! Storage: `("pfee", scope)` -> `ProtocolFee`, `("pkgfee", id)` -> claim-time
+//! bps, `("feetot", token)` -> `FeeTotals`, instance `"treasury"` -> `Address`.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
+
+use crate::Error;
+
+/// Basis-point denominator for `ProtocolFee::bps`.
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+const KEY_TREASURY: Symbol = symbol_short!("treasury");
+
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u32)]
+pub enum FeeTiming {
+    /// Charged at creation, on top of the package amount.
+    AtCreation = 0,
+    /// Deducted from the payout when the package is claimed or disbursed.
+    AtClaim = 1,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProtocolFee {
+    pub bps: u32,
+    pub timing: FeeTiming,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum FeeScope {
+    Token(Address),
+    /// Packages whose `campaign_id` term equals this value.
+    Campaign(String),
+}
+
+/// Per-token fee aggregate.
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeeTotals {
+    /// Fees charged so far.
+    pub accrued: i128,
+    /// Fees swept to the treasury so far.
+    pub collected: i128,
+}
+
+impl FeeTotals {
+    /// Fees charged but still held by the contract.
+    pub fn held(&self) -> i128 {
+        self.accrued - self.collected
+    }
+}
+
+/// Rejects rates above 100%.
+pub fn validate(fee: &ProtocolFee) -> Result<(), Error> {
+    if fee.bps > BPS_DENOMINATOR {
+        return Err(Error::InvalidProtocolFee);
+    }
+    Ok(())
+}
+
+/// Sets or removes the fee for `scope`.
+pub fn set(env: &Env, scope: &FeeScope, fee: &Option<ProtocolFee>) {
+    let key = (symbol_short!("pfee"), scope.clone());
+    match fee {
+        Some(fee) => env.storage().persistent().set(&key, fee),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Returns the fee configured for `scope`, if any.
+pub fn get(env: &Env, scope: &FeeScope) -> Option<ProtocolFee> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("pfee"), scope.clone()))
+}
+
+/// Returns the fee that applies to a new package: the fee of its campaign if
+/// it has one, else the token fee.
+pub fn resolve(env: &Env, token: &Address, campaign: &Option<String>) -> Option<ProtocolFee> {
+    campaign
+        .clone()
+        .and_then(|campaign| get(env, &FeeScope::Campaign(campaign)))
+        .or_else(|| get(env, &FeeScope::Token(token.clone())))
+}
+
+/// Fee owed on `amount` at `bps`.
+pub fn compute(amount: i128, bps: u32) -> i128 {
+    amount * (bps as i128) / (BPS_DENOMINATOR as i128)
+}
+
+/// Records the claim-time rate for a new package.
+pub fn set_claim_bps(env: &Env, package_id: u64, bps: u32) {
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("pkgfee"), package_id), &bps);
+}
+
+/// Returns the claim-time rate snapshotted for a package (0 if none).
+pub fn get_claim_bps(env: &Env, package_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("pkgfee"), package_id))
+        .unwrap_or(0)
+}
+
+/// Returns the fee aggregate for `token`.
+pub fn get_totals(env: &Env, token: &Address) -> FeeTotals {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("feetot"), token.clone()))
+        .unwrap_or_default()
+}
+
+fn save_totals(env: &Env, token: &Address, totals: &FeeTotals) {
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("feetot"), token.clone()), totals);
+}
+
+/// Adds a charged fee to the aggregate for `token`.
+pub fn accrue(env: &Env, token: &Address, amount: i128) {
+    let mut totals = get_totals(env, token);
+    totals.accrued += amount;
+    save_totals(env, token, &totals);
+}
+
+/// Marks every held fee for `token` as collected and returns the amount.
+pub fn take_held(env: &Env, token: &Address) -> i128 {
+    let mut totals = get_totals(env, token);
+    let held = totals.held();
+    totals.collected = totals.accrued;
+    save_totals(env, token, &totals);
+    held
+}
+
+/// Returns the treasury address, if set.
+pub fn get_treasury(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&KEY_TREASURY)
+}
+
+/// Sets the treasury address.
+pub fn set_treasury(env: &Env, treasury: &Address) {
+    env.storage().instance().set(&KEY_TREASURY, treasury);
+}
@@ -0,0 +1,76 @@
+//! Hash-locked claim codes for recipients without a pre-registered address.
+//!
+//! A hash-locked package stores `sha256(secret)` instead of a real
+//! recipient (the escrow contract address is used as a placeholder in
+//! `Package.recipient`).  The secret is delivered out-of-band, e.g. by SMS,
+//! and can be redeemed to any payout address once the beneficiary has a
+//! wallet.
+//!
+//! Redemption is a two-step commit-reveal so a revealed secret cannot be
+//! front-run:
+//! 1. `commit_secret_claim` stores `sha256(secret || payout_address_xdr)`
+//!    bound to the payout address and the current ledger sequence.
+//! 2. `claim_with_secret`, in a later ledger, reveals the secret.  Both the
+//!    stored claim hash and the payout-bound commitment must match.
+
+use soroban_sdk::{contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SecretCommitment {
+    pub commitment: BytesN<32>,
+    /// Ledger sequence in which the commitment was recorded.
+    pub ledger: u32,
+}
+
+/// Stores the claim hash for a hash-locked package.
+pub fn set_claim_hash(env: &Env, package_id: u64, claim_hash: &BytesN<32>) {
+    let key = (symbol_short!("hlock"), package_id);
+    env.storage().persistent().set(&key, claim_hash);
+}
+
+/// Returns the claim hash if `package_id` is hash-locked.
+pub fn get_claim_hash(env: &Env, package_id: u64) -> Option<BytesN<32>> {
+    let key = (symbol_short!("hlock"), package_id);
+    env.storage().persistent().get(&key)
+}
+
+/// Returns `true` if `package_id` can only be claimed with a secret.
+pub fn is_hash_locked(env: &Env, package_id: u64) -> bool {
+    let key = (symbol_short!("hlock"), package_id);
+    env.storage().persistent().has(&key)
+}
+
+/// Records a payout-bound commitment in the current ledger.
+pub fn set_commitment(env: &Env, package_id: u64, payout: &Address, commitment: &BytesN<32>) {
+    let key = (symbol_short!("hcommit"), package_id, payout.clone());
+    let record = SecretCommitment {
+        commitment: commitment.clone(),
+        ledger: env.ledger().sequence(),
+    };
+    env.storage().persistent().set(&key, &record);
+}
+
+/// Returns the commitment recorded by `payout` for `package_id`, if any.
+pub fn get_commitment(env: &Env, package_id: u64, payout: &Address) -> Option<SecretCommitment> {
+    let key = (symbol_short!("hcommit"), package_id, payout.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Removes a consumed commitment.
+pub fn remove_commitment(env: &Env, package_id: u64, payout: &Address) {
+    let key = (symbol_short!("hcommit"), package_id, payout.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// `sha256(secret)`, the value stored at package creation.
+pub fn hash_secret(env: &Env, secret: &Bytes) -> BytesN<32> {
+    env.crypto().sha256(secret).into()
+}
+
+/// `sha256(secret || payout_address_xdr)`, the value committed before reveal.
+pub fn commitment_for(env: &Env, secret: &Bytes, payout: &Address) -> BytesN<32> {
+    let mut data = secret.clone();
+    data.append(&payout.clone().to_xdr(env));
+    env.crypto().sha256(&data).into()
+}
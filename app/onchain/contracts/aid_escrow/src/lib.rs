@@ -1,4 +1,7 @@
 #![no_std]
+// Entry points taking eight arguments trip this lint in the `contractargs`
+// code generated by `#[contractimpl]`, where no local `allow` reaches.
+#![allow(clippy::too_many_arguments)]
 
 //! # Token Amount Normalization & Validation Policy
 //!
@@ -21,11 +24,84 @@
 //! - Tests for invalid/edge cases are in `tests/aid_escrow_tests.rs`.
 
 use soroban_sdk::{
-    contract, contracterror, contractevent, contractimpl, contracttype, symbol_short, Address,
-    Bytes, Env, IntoVal, Map, String, Symbol, Val, Vec,
+    contract, contracterror, contractevent, contractimpl, contracttype, panic_with_error,
+    symbol_short, Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec,
 };
 
+#[cfg(feature = "amendments")]
+mod amendment;
+mod attestor;
+mod audit;
+#[cfg(feature = "registry")]
+mod beneficiary;
+#[cfg(feature = "benefit-caps")]
+mod benefit;
 mod delegate;
+#[cfg(feature = "denylist")]
+mod denylist;
+#[cfg(feature = "registry")]
+mod eligibility;
+#[cfg(feature = "registry")]
+mod enrolment;
+#[cfg(feature = "fees")]
+mod fee;
+#[cfg(feature = "hash-locks")]
+mod hashlock;
+mod lifecycle;
+#[cfg(feature = "merchants")]
+mod merchant;
+#[cfg(feature = "metadata-rules")]
+mod metadata;
+mod payout;
+#[cfg(feature = "receipts")]
+mod receipt;
+#[cfg(feature = "recovery")]
+mod recovery;
+#[cfg(feature = "relayers")]
+mod relayer;
+mod roles;
+#[cfg(feature = "standing-delegates")]
+mod standing;
+mod terms;
+#[cfg(feature = "vouchers")]
+mod voucher;
+
+#[cfg(feature = "amendments")]
+pub use amendment::MetadataAmendment;
+pub use attestor::{Attestor, AttestorClient};
+pub use audit::{AuditEntry, AuditHead};
+#[cfg(feature = "registry")]
+pub use beneficiary::{BeneficiaryPolicy, BeneficiaryRegistry, BeneficiaryRegistryClient};
+#[cfg(feature = "benefit-caps")]
+pub use benefit::{BenefitCap, CapMode, RecipientTotals};
+#[cfg(feature = "denylist")]
+pub use denylist::DenyEntry;
+#[cfg(feature = "registry")]
+pub use eligibility::{EligibilityIssuer, EligibilityIssuerClient};
+#[cfg(feature = "registry")]
+pub use enrolment::{Enrolment, EnrolmentPolicy};
+#[cfg(feature = "fees")]
+pub use fee::{FeeScope, FeeTiming, FeeTotals, ProtocolFee};
+#[cfg(feature = "merchants")]
+pub use merchant::MerchantInfo;
+#[cfg(feature = "metadata-rules")]
+pub use metadata::MetadataRules;
+pub use payout::PayoutPolicy;
+use payout::PayoutSplit;
+#[cfg(feature = "receipts")]
+pub use receipt::{AckStats, ReceiptAck};
+#[cfg(feature = "recovery")]
+pub use recovery::{
+    RecipientChange, RecoveryProgress, RecoveryRequest, RecoveryScope, RecoveryStatus,
+};
+#[cfg(feature = "relayers")]
+pub use relayer::{RelayerFee, RelayerFeeKind, RelayerFeeSource, RelayerInfo};
+pub use roles::Role;
+#[cfg(feature = "standing-delegates")]
+pub use standing::{DelegationScope, StandingDelegation};
+pub use terms::PackageTerms;
+#[cfg(feature = "vouchers")]
+pub use voucher::ClaimVoucher;
 
 // --- Storage Keys ---
 const KEY_ADMIN: Symbol = symbol_short!("admin");
@@ -41,7 +117,6 @@ const KEY_PAUSE_CLAIM: Symbol = symbol_short!("p_claim");
 const KEY_PAUSE_WITHDRAW: Symbol = symbol_short!("p_wdrw");
 const KEY_TOTAL_CLAIMED: Symbol = symbol_short!("claimed"); // Map<Address, i128>
 const KEY_PENDING_ADMIN: Symbol = symbol_short!("pend_adm");
-const META_MERKLE_ROOT_KEY: &str = "merkle_root";
 
 // --- Data Types ---
 
@@ -110,10 +185,91 @@ pub enum Error {
     TokenTransferFailed = 18,
     NoPendingTransfer = 19,
     InvalidPendingAdmin = 20,
+    VoucherExpired = 21,
+    VoucherAlreadyUsed = 22,
+    SigningKeyNotRegistered = 23,
+    HashLockedPackage = 24,
+    InvalidSecret = 25,
+    CommitmentNotFound = 26,
+    CommitmentTooRecent = 27,
+    RecoveryNotFound = 28,
+    RecoveryNotReady = 29,
+    NoPendingNomination = 30,
+    DelegateLimitReached = 31,
+    DelegateNotFound = 32,
+    DelegateThresholdNotMet = 33,
+    InvalidPayoutPolicy = 34,
+    RelayerDisabled = 35,
+    RelayerNotRegistered = 36,
+    InvalidRelayerFee = 37,
+    InvalidProtocolFee = 38,
+    TreasuryNotSet = 39,
+    InvalidClaimWindow = 40,
+    MalformedTerm = 41,
+    ConflictingTerms = 42,
+    MetadataKeyNotAllowed = 43,
+    MetadataTooManyKeys = 44,
+    MetadataValueTooLong = 45,
+    MissingRequiredMetadata = 46,
+    AlreadyAcknowledged = 47,
+    InvalidRating = 48,
+    RestrictedPackage = 49,
+    MerchantUnavailable = 50,
+}
+
+/// Rejections by spending, eligibility and compliance policies.
+///
+/// The contract spec caps an error enum at 50 cases, which `Error` has
+/// reached, so these continue its numbering here.  Entry points declared to
+/// return `Error` raise them with `panic_with_error!`; callers see the same
+/// `Error(Contract, code)` either way.
+#[contracterror]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PolicyError {
+    CategoryNotAllowed = 51,
+    ConditionNotAttested = 52,
+    NotEligible = 53,
+    RecipientNotRegistered = 54,
+    DuplicateIdentity = 55,
+    EnrolmentLimitReached = 56,
+    BenefitCapExceeded = 57,
+    AddressDenied = 58,
+    RegistryUnavailable = 59,
+}
+
+/// Which packages a payout entrypoint may pay out, checked by
+/// `AidEscrow::load_claimable`.
+#[derive(Clone, Copy, PartialEq)]
+enum ClaimRoute {
+    /// Address-based claim: the package is neither hash-locked nor
+    /// Merkle-allowlisted.
+    Address,
+    /// `claim_with_proof`: the package is not hash-locked; the caller checks
+    /// any allowlist.
+    Proof,
+    /// `claim_with_secret` and `redeem`, which check the package kind
+    /// themselves.
+    Own,
+}
+
+/// How a package is paid out by `AidEscrow::settle`.
+enum Settlement {
+    /// Claim by the recipient, a delegate, an allowlisted claimant or a
+    /// hash-lock payout address; the claimant is paid.
+    Claim(Address),
+    /// Claim by `claimant` submitted through `relayer`.
+    #[cfg(feature = "relayers")]
+    Relayed { claimant: Address, relayer: Address },
+    /// Admin disbursement to the recipient; carries the admin.
+    Disburse(Address),
 }
 
 // --- Contract Events (indexer-friendly; stable topics & payloads) ---
 // Topic = struct name in snake_case (e.g. package_created). Do not rename without versioning.
+// Every event carries `schema_version`; it is bumped whenever a payload changes shape.
+
+/// Version of the event payload schema carried by every event.
+pub const EVENT_SCHEMA_VERSION: u32 = 2;
 
 /// Emitted when the escrow pool is funded. Actor = funder.
 #[contractevent]
@@ -122,6 +278,7 @@ pub struct EscrowFunded {
     pub token: Address,
     pub amount: i128,
     pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 #[contractevent]
@@ -131,6 +288,7 @@ pub struct PackageCreated {
     pub amount: i128,
     pub actor: Address,
     pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 #[contractevent]
@@ -143,8 +301,14 @@ pub struct PackageClaimed {
     /// Optional off-chain receipt hash for anchoring external records.
     /// Empty string when not provided.
     pub receipt_hash: String,
+    /// Amount paid to the recipient (or to the claimant, for non-delegate claims).
+    pub paid_to_recipient: i128,
+    /// Amount paid to the claiming delegate under its payout policy.
+    pub paid_to_delegate: i128,
+    pub schema_version: u32,
 }
 
+#[cfg(feature = "relayers")]
 #[contractevent]
 pub struct PackageClaimedByRelayer {
     pub package_id: u64,
@@ -152,6 +316,205 @@ pub struct PackageClaimedByRelayer {
     pub relayer: Address,
     pub amount: i128,
     pub timestamp: u64,
+    /// Fee paid to the relayer (0 for unregistered relayers or tokens without a fee).
+    pub relayer_fee: i128,
+    pub fee_source: RelayerFeeSource,
+    /// Amount paid out for the package after any payout-sourced fee.
+    pub net_amount: i128,
+    /// Optional off-chain receipt hash for anchoring external records.
+    /// Empty string when not provided.
+    pub receipt_hash: String,
+    pub schema_version: u32,
+}
+
+/// Emitted for every link appended to the audit hash chain.
+/// `hash = sha256(prev_hash || xdr(AuditEntry { seq, action, actor, package_id,
+/// timestamp, change }))`.
+#[contractevent]
+pub struct AuditRecorded {
+    pub seq: u64,
+    pub action: Symbol,
+    pub actor: Address,
+    pub package_id: Option<u64>,
+    pub timestamp: u64,
+    pub change: BytesN<32>,
+    pub prev_hash: BytesN<32>,
+    pub hash: BytesN<32>,
+    pub schema_version: u32,
+}
+
+/// Emitted when the admin replaces the metadata rules.
+#[cfg(feature = "metadata-rules")]
+#[contractevent]
+pub struct MetadataRulesSet {
+    pub allowed_keys: Vec<Symbol>,
+    pub max_keys: u32,
+    pub max_value_len: u32,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the admin sets or removes (`cap` = `None`) the benefit cap of a token.
+#[cfg(feature = "benefit-caps")]
+#[contractevent]
+pub struct BenefitCapSet {
+    pub token: Address,
+    pub cap: Option<BenefitCap>,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when a claim is paid only up to the recipient's benefit cap
+/// (before the payout event, whose `amount` is `paid`).  `withheld` is
+/// returned to the pool.
+#[cfg(feature = "benefit-caps")]
+#[contractevent]
+pub struct BenefitCapApplied {
+    pub package_id: u64,
+    pub recipient: Address,
+    pub token: Address,
+    pub requested: i128,
+    pub paid: i128,
+    pub withheld: i128,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the admin sets or removes (`policy` = `None`) the enrolment
+/// limits of a campaign.
+#[cfg(feature = "registry")]
+#[contractevent]
+pub struct EnrolmentPolicySet {
+    pub campaign: String,
+    pub policy: Option<EnrolmentPolicy>,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the admin links or unlinks (`policy` = `None`) the beneficiary registry.
+#[cfg(feature = "registry")]
+#[contractevent]
+pub struct BeneficiaryRegistrySet {
+    pub policy: Option<BeneficiaryPolicy>,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the admin sets or removes (`issuer` = `None`) the eligibility
+/// issuer of a campaign.
+#[cfg(feature = "registry")]
+#[contractevent]
+pub struct EligibilityIssuerSet {
+    pub campaign: String,
+    pub issuer: Option<Address>,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted by `check_eligibility` when the campaign issuer does not confirm
+/// the recipient of a package.
+#[cfg(feature = "registry")]
+#[contractevent]
+pub struct EligibilityCheckFailed {
+    pub package_id: u64,
+    pub recipient: Address,
+    pub campaign: String,
+    pub issuer: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the admin sets the metadata keys required for a campaign
+/// (`keys` empty = requirement cleared).
+#[cfg(feature = "metadata-rules")]
+#[contractevent]
+pub struct RequiredMetadataSet {
+    pub campaign: String,
+    pub keys: Vec<Symbol>,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when a protocol fee is charged, at creation or at payout.
+#[cfg(feature = "fees")]
+#[contractevent]
+pub struct ProtocolFeeCharged {
+    pub package_id: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub timing: FeeTiming,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the protocol fee for a token or campaign is set or removed (`fee` = `None`).
+#[cfg(feature = "fees")]
+#[contractevent]
+pub struct ProtocolFeeSet {
+    pub scope: FeeScope,
+    pub fee: Option<ProtocolFee>,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the admin sets the treasury address.
+#[cfg(feature = "fees")]
+#[contractevent]
+pub struct TreasurySet {
+    pub treasury: Address,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when held protocol fees are swept to the treasury.
+#[cfg(feature = "fees")]
+#[contractevent]
+pub struct FeesCollected {
+    pub token: Address,
+    pub treasury: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the admin registers a relayer.
+#[cfg(feature = "relayers")]
+#[contractevent]
+pub struct RelayerRegistered {
+    pub relayer: Address,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the admin enables or disables a registered relayer.
+#[cfg(feature = "relayers")]
+#[contractevent]
+pub struct RelayerStatusChanged {
+    pub relayer: Address,
+    pub enabled: bool,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the relayer fee for a token is set or removed (`fee` = `None`).
+#[cfg(feature = "relayers")]
+#[contractevent]
+pub struct RelayerFeeSet {
+    pub token: Address,
+    pub fee: Option<RelayerFee>,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 #[contractevent]
@@ -164,6 +527,7 @@ pub struct PackageDisbursed {
     /// Optional off-chain receipt hash for anchoring external records.
     /// Empty string when not provided.
     pub receipt_hash: String,
+    pub schema_version: u32,
 }
 
 #[contractevent]
@@ -173,6 +537,31 @@ pub struct PackageRevoked {
     pub amount: i128,
     pub actor: Address,
     pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted by `cancel_package`; `revoke` emits `PackageRevoked`.
+#[contractevent]
+pub struct PackageCancelled {
+    pub package_id: u64,
+    pub recipient: Address,
+    pub amount: i128,
+    pub actor: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted once per package status change, alongside the specific lifecycle
+/// event. `from` is `None` when the package is created.
+#[contractevent]
+pub struct PackageStatusChanged {
+    pub package_id: u64,
+    pub from: Option<PackageStatus>,
+    pub to: PackageStatus,
+    pub reason: Symbol,
+    pub actor: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 #[contractevent]
@@ -182,6 +571,7 @@ pub struct PackageRefunded {
     pub amount: i128,
     pub actor: Address,
     pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 #[contractevent]
@@ -189,6 +579,7 @@ pub struct BatchCreatedEvent {
     pub ids: Vec<u64>,
     pub admin: Address,
     pub total_amount: i128,
+    pub schema_version: u32,
 }
 
 #[contractevent]
@@ -197,6 +588,76 @@ pub struct ExtendedEvent {
     pub admin: Address,
     pub old_expires_at: u64,
     pub new_expires_at: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when a recipient or delegate acknowledges receipt of a paid-out package.
+#[cfg(feature = "receipts")]
+#[contractevent]
+pub struct ReceiptAcknowledged {
+    pub package_id: u64,
+    pub by: Address,
+    pub receipt_hash: String,
+    pub rating: u32,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the admin registers a merchant for restricted-spend redemptions.
+#[cfg(feature = "merchants")]
+#[contractevent]
+pub struct MerchantAdded {
+    pub merchant: Address,
+    pub categories: Vec<Symbol>,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the admin suspends or reinstates a merchant, or changes its categories.
+#[cfg(feature = "merchants")]
+#[contractevent]
+pub struct MerchantUpdated {
+    pub merchant: Address,
+    pub suspended: bool,
+    pub categories: Vec<Symbol>,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted for each redemption of a restricted package at a merchant.
+/// `amount` leaves the package; the merchant receives `net_amount` after any
+/// claim-time protocol fee.
+#[cfg(feature = "merchants")]
+#[contractevent]
+pub struct PackageRedeemed {
+    pub package_id: u64,
+    pub recipient: Address,
+    pub merchant: Address,
+    pub amount: i128,
+    pub net_amount: i128,
+    /// Amount still redeemable; the package is `Claimed` once it reaches 0.
+    pub remaining: i128,
+    /// Category the spend is counted under (`None` for an untagged merchant).
+    pub category: Option<Symbol>,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when package metadata is amended. Only key names are included;
+/// values are in the amendment log (`get_metadata_amendments`).
+#[cfg(feature = "amendments")]
+#[contractevent]
+pub struct PackageMetadataUpdated {
+    pub package_id: u64,
+    pub actor: Address,
+    pub set_keys: Vec<Symbol>,
+    pub removed_keys: Vec<Symbol>,
+    /// Index of this amendment in the package's amendment log.
+    pub amendment: u32,
+    pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 #[contractevent]
@@ -204,28 +665,85 @@ pub struct SurplusWithdrawnEvent {
     pub to: Address,
     pub token: Address,
     pub amount: i128,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 #[contractevent]
 pub struct ContractPausedEvent {
     pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 #[contractevent]
 pub struct ContractUnpausedEvent {
     pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 #[contractevent]
 pub struct ActionPausedEvent {
     pub admin: Address,
     pub action: Symbol,
+    pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 #[contractevent]
 pub struct ActionUnpausedEvent {
     pub admin: Address,
     pub action: Symbol,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted once by `init`.
+#[contractevent]
+pub struct ContractInitialized {
+    pub admin: Address,
+    pub version: u32,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted by `migrate`.
+#[contractevent]
+pub struct ContractMigrated {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted by `set_config` with the full new configuration.
+#[contractevent]
+pub struct ConfigUpdated {
+    pub min_amount: i128,
+    pub max_expires_in: u64,
+    pub allowed_tokens: Vec<Address>,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+#[contractevent]
+pub struct DistributorAdded {
+    pub distributor: Address,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+#[contractevent]
+pub struct DistributorRemoved {
+    pub distributor: Address,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 /// Emitted when a delegate is added/updated for a package.
@@ -238,6 +756,7 @@ pub struct DelegateAdded {
     pub actor: Address,
     pub expires_at: u64,
     pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 /// Emitted when a delegate is revoked/removed for a package.
@@ -249,6 +768,20 @@ pub struct DelegateRevoked {
     pub delegate: Address,
     pub actor: Address,
     pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted for each expired delegate pruned by `cleanup_expired_delegates`.
+/// `reason` is `expired`; `actor` is the caller of the cleanup.
+#[contractevent]
+pub struct DelegateRemoved {
+    pub package_id: u64,
+    pub recipient: Address,
+    pub delegate: Address,
+    pub reason: Symbol,
+    pub actor: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 /// Emitted when a delegate claims a package on behalf of the recipient.
@@ -261,6 +794,109 @@ pub struct DelegateClaimed {
     pub amount: i128,
     pub actor: Address,
     pub timestamp: u64,
+    pub payout_policy: PayoutPolicy,
+    pub paid_to_recipient: i128,
+    pub paid_to_delegate: i128,
+    pub schema_version: u32,
+}
+
+/// Emitted when the number of delegates required to co-sign a claim changes.
+#[cfg(feature = "multi-delegates")]
+#[contractevent]
+pub struct DelegateThresholdSet {
+    pub package_id: u64,
+    pub threshold: u32,
+    pub actor: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when delegates jointly claim a package that requires co-signers
+/// (before `DelegateClaimed`). `signers[0]` receives the payout.
+#[cfg(feature = "multi-delegates")]
+#[contractevent]
+pub struct DelegateClaimCosigned {
+    pub package_id: u64,
+    pub recipient: Address,
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when a standing recipient-level delegation is granted or replaced.
+#[cfg(feature = "standing-delegates")]
+#[contractevent]
+pub struct StandingDelegateGranted {
+    pub recipient: Address,
+    pub delegate: Address,
+    pub scope: DelegationScope,
+    pub expires_at: u64,
+    pub actor: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when a standing recipient-level delegation is revoked.
+#[cfg(feature = "standing-delegates")]
+#[contractevent]
+pub struct StandingDelegateRevoked {
+    pub recipient: Address,
+    pub delegate: Address,
+    pub actor: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when a package is claimed under a standing delegation
+/// (after `DelegateClaimed`, in place of `DelegateRevoked`).
+#[cfg(feature = "standing-delegates")]
+#[contractevent]
+pub struct StandingDelegationUsed {
+    pub package_id: u64,
+    pub recipient: Address,
+    pub delegate: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when a package-level or per-delegation payout policy changes.
+/// `delegate` is `None` for the package-level policy.
+#[cfg(feature = "payout-policies")]
+#[contractevent]
+pub struct PayoutPolicySet {
+    pub package_id: u64,
+    pub delegate: Option<Address>,
+    pub policy: Option<PayoutPolicy>,
+    pub actor: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when a recipient nominates a delegate for their package.
+/// The delegate is not active until the nominee accepts.
+#[cfg(feature = "nominations")]
+#[contractevent]
+pub struct DelegateNominated {
+    pub package_id: u64,
+    pub recipient: Address,
+    pub delegate: Address,
+    pub expires_at: u64,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when a pending nomination is rejected by the nominee or cancelled by the recipient.
+#[cfg(feature = "nominations")]
+#[contractevent]
+pub struct DelegateNominationDiscarded {
+    pub package_id: u64,
+    pub recipient: Address,
+    pub delegate: Address,
+    pub actor: Address,
+    pub reason: Symbol,
+    pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 /// Emitted when the current admin nominates a pending admin.
@@ -269,6 +905,7 @@ pub struct AdminTransferInitiated {
     pub admin: Address,
     pub pending_admin: Address,
     pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 /// Emitted when the pending admin accepts the admin role.
@@ -276,6 +913,7 @@ pub struct AdminTransferInitiated {
 pub struct AdminTransferAccepted {
     pub admin: Address,
     pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 /// Emitted when the current admin cancels a pending admin transfer.
@@ -283,6 +921,7 @@ pub struct AdminTransferAccepted {
 pub struct AdminTransferCancelled {
     pub admin: Address,
     pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 /// Emitted when a token is added to the allowed tokens allowlist.
@@ -291,6 +930,7 @@ pub struct TokenAdded {
     pub admin: Address,
     pub token: Address,
     pub timestamp: u64,
+    pub schema_version: u32,
 }
 
 /// Emitted when a token is removed from the allowed tokens allowlist.
@@ -299,17 +939,178 @@ pub struct TokenRemoved {
     pub admin: Address,
     pub token: Address,
     pub timestamp: u64,
+    pub schema_version: u32,
 }
 
-#[contract]
-pub struct AidEscrow;
-
-#[contractimpl]
-impl AidEscrow {
-    // --- Admin & Config ---
+/// Emitted when a distributor registers or rotates its voucher signing key.
+#[cfg(feature = "vouchers")]
+#[contractevent]
+pub struct DistributorKeySet {
+    pub distributor: Address,
+    pub public_key: BytesN<32>,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
 
-    /// Initializes the contract.
-    ///
+/// Emitted when a distributor-signed voucher is redeemed.
+/// Followed by the regular `PackageClaimed` event for the payout.
+#[cfg(feature = "vouchers")]
+#[contractevent]
+pub struct VoucherRedeemed {
+    pub package_id: u64,
+    pub distributor: Address,
+    pub claimant: Address,
+    pub nonce: u64,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted alongside `PackageCreated` when a package is locked to a claim hash
+/// instead of a recipient address.
+#[cfg(feature = "hash-locks")]
+#[contractevent]
+pub struct HashLockedPackageCreated {
+    pub package_id: u64,
+    pub claim_hash: BytesN<32>,
+    pub actor: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when a payout address commits to a secret for a hash-locked package.
+#[cfg(feature = "hash-locks")]
+#[contractevent]
+pub struct SecretClaimCommitted {
+    pub package_id: u64,
+    pub payout_address: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when an address is added to the denylist, or its reason changed.
+#[cfg(feature = "denylist")]
+#[contractevent]
+pub struct DenylistEntryAdded {
+    pub address: Address,
+    pub reason: u32,
+    pub operator: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when an address is removed from the denylist.  `reason` is the
+/// code it was denied with.
+#[cfg(feature = "denylist")]
+#[contractevent]
+pub struct DenylistEntryRemoved {
+    pub address: Address,
+    pub reason: u32,
+    pub operator: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the admin grants an operational role.
+#[contractevent]
+pub struct RoleGranted {
+    pub role: Role,
+    pub account: Address,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the admin revokes an operational role.
+#[contractevent]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub account: Address,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the admin changes the recipient recovery waiting period.
+#[cfg(feature = "recovery")]
+#[contractevent]
+pub struct RecoveryDelaySet {
+    pub old: u64,
+    pub new: u64,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when a recipient recovery is proposed.
+#[cfg(feature = "recovery")]
+#[contractevent]
+pub struct RecoveryProposed {
+    pub recovery_id: u64,
+    pub old_recipient: Address,
+    pub new_recipient: Address,
+    pub scope: RecoveryScope,
+    pub actor: Address,
+    pub executable_at: u64,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when the original recipient objects to a pending recovery.
+#[cfg(feature = "recovery")]
+#[contractevent]
+pub struct RecoveryObjected {
+    pub recovery_id: u64,
+    pub old_recipient: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when a pending recovery is cancelled by the admin or a recovery agent.
+#[cfg(feature = "recovery")]
+#[contractevent]
+pub struct RecoveryCancelled {
+    pub recovery_id: u64,
+    pub actor: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted once a recovery has been executed.
+/// One `PackageRecipientChanged` is emitted per rotated package beforehand.
+#[cfg(feature = "recovery")]
+#[contractevent]
+pub struct RecoveryExecuted {
+    pub recovery_id: u64,
+    pub old_recipient: Address,
+    pub new_recipient: Address,
+    pub packages_rotated: u32,
+    pub actor: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+/// Emitted when a package's recipient is rotated by a recovery.
+#[cfg(feature = "recovery")]
+#[contractevent]
+pub struct PackageRecipientChanged {
+    pub package_id: u64,
+    pub old_recipient: Address,
+    pub new_recipient: Address,
+    pub recovery_id: u64,
+    pub actor: Address,
+    pub timestamp: u64,
+    pub schema_version: u32,
+}
+
+#[contract]
+pub struct AidEscrow;
+
+#[contractimpl]
+impl AidEscrow {
+    // --- Admin & Config ---
+
+    /// Initializes the contract.
+    ///
     /// # Arguments
     /// * `admin` — The address that will own the contract (can pause, config, disburse, etc.).
     ///
@@ -321,12 +1122,22 @@ impl AidEscrow {
         }
         env.storage().instance().set(&KEY_ADMIN, &admin);
         env.storage().instance().set(&KEY_VERSION, &1u32);
+        audit::record(&env, "init", &admin, None, admin.clone());
         let config = Config {
             min_amount: 1,
             max_expires_in: 0,
             allowed_tokens: Vec::new(&env),
         };
         env.storage().instance().set(&KEY_CONFIG, &config);
+
+        ContractInitialized {
+            schema_version: EVENT_SCHEMA_VERSION,
+            admin,
+            version: 1,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
         Ok(())
     }
 
@@ -361,6 +1172,7 @@ impl AidEscrow {
     pub fn transfer_admin(env: Env, new_admin: Address) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
+        audit::record(&env, "transfer_admin", &admin, None, new_admin.clone());
 
         if new_admin == admin {
             return Err(Error::InvalidPendingAdmin);
@@ -370,6 +1182,7 @@ impl AidEscrow {
 
         let timestamp = env.ledger().timestamp();
         AdminTransferInitiated {
+            schema_version: EVENT_SCHEMA_VERSION,
             admin,
             pending_admin: new_admin,
             timestamp,
@@ -393,12 +1206,14 @@ impl AidEscrow {
             .ok_or(Error::NoPendingTransfer)?;
 
         pending_admin.require_auth();
+        audit::record(&env, "accept_admin", &pending_admin, None, ());
 
         env.storage().instance().set(&KEY_ADMIN, &pending_admin);
         env.storage().instance().remove(&KEY_PENDING_ADMIN);
 
         let timestamp = env.ledger().timestamp();
         AdminTransferAccepted {
+            schema_version: EVENT_SCHEMA_VERSION,
             admin: pending_admin,
             timestamp,
         }
@@ -414,6 +1229,7 @@ impl AidEscrow {
     pub fn cancel_admin_transfer(env: Env) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
+        audit::record(&env, "cancel_admin_transfer", &admin, None, ());
 
         if !env.storage().instance().has(&KEY_PENDING_ADMIN) {
             return Err(Error::NoPendingTransfer);
@@ -422,7 +1238,12 @@ impl AidEscrow {
         env.storage().instance().remove(&KEY_PENDING_ADMIN);
 
         let timestamp = env.ledger().timestamp();
-        AdminTransferCancelled { admin, timestamp }.publish(&env);
+        AdminTransferCancelled {
+            schema_version: EVENT_SCHEMA_VERSION,
+            admin,
+            timestamp,
+        }
+        .publish(&env);
 
         Ok(())
     }
@@ -433,6 +1254,17 @@ impl AidEscrow {
         env.storage().instance().get(&KEY_VERSION).unwrap_or(0)
     }
 
+    /// Returns the head of the audit hash chain: the number of recorded changes
+    /// and the running `sha256(prev_hash || xdr(entry))` commitment.
+    pub fn get_audit_head(env: Env) -> AuditHead {
+        audit::get_head(&env)
+    }
+
+    /// Returns the `schema_version` carried by every event this contract emits.
+    pub fn get_event_schema_version(_env: Env) -> u32 {
+        EVENT_SCHEMA_VERSION
+    }
+
     /// Returns the semantic version of the contract package.
     pub fn contract_version(env: Env) -> String {
         String::from_str(&env, env!("CARGO_PKG_VERSION"))
@@ -448,13 +1280,14 @@ impl AidEscrow {
     pub fn migrate(env: Env, new_version: u32) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
+        audit::record(&env, "migrate", &admin, None, new_version);
 
         let current_version = Self::get_version(env.clone());
 
         // Perform version-specific migrations
         match (current_version, new_version) {
             (1, 2) => {
-                // Future: Add migration logic for v1 -> v2
+                delegate::migrate_legacy(&env);
             }
             _ => {
                 // No-op for now, but structured for future use
@@ -462,9 +1295,47 @@ impl AidEscrow {
         }
 
         env.storage().instance().set(&KEY_VERSION, &new_version);
+
+        ContractMigrated {
+            schema_version: EVENT_SCHEMA_VERSION,
+            from_version: current_version,
+            to_version: new_version,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
         Ok(())
     }
 
+    /// Admin-only. Moves the delegate history that versions before 2 kept in one
+    /// global list to per-package keys, after `migrate` to version 2.
+    ///
+    /// Moves at most `limit` (capped at 50) records per call, starting at
+    /// `cursor`; call again with the returned `next_cursor` until `done` is true.
+    /// Changes made in between are appended after a package's moved records.
+    ///
+    /// # Errors
+    /// - `Error::NotAuthorized` - caller is not the admin
+    /// - `Error::InvalidState` - `cursor` is not where the previous call stopped
+    pub fn migrate_delegate_history(
+        env: Env,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<crate::delegate::HistoryMigration, Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(
+            &env,
+            "migrate_delegate_history",
+            &admin,
+            None,
+            (cursor, limit),
+        );
+
+        delegate::migrate_legacy_history(&env, cursor, limit)
+    }
+
     /// Admin-only. Grants distributor privileges to `addr`.
     /// Distributors can create packages but cannot pause, config, or disburse.
     ///
@@ -473,17 +1344,26 @@ impl AidEscrow {
     pub fn add_distributor(env: Env, addr: Address) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
+        audit::record(&env, "add_distributor", &admin, None, addr.clone());
 
         let mut distributors: Map<Address, bool> = env
             .storage()
             .instance()
             .get(&KEY_DISTRIBUTORS)
             .unwrap_or(Map::new(&env));
-        distributors.set(addr, true);
+        distributors.set(addr.clone(), true);
         env.storage()
             .instance()
             .set(&KEY_DISTRIBUTORS, &distributors);
 
+        DistributorAdded {
+            schema_version: EVENT_SCHEMA_VERSION,
+            distributor: addr,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
         Ok(())
     }
 
@@ -494,20 +1374,82 @@ impl AidEscrow {
     pub fn remove_distributor(env: Env, addr: Address) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
+        audit::record(&env, "remove_distributor", &admin, None, addr.clone());
 
         let mut distributors: Map<Address, bool> = env
             .storage()
             .instance()
             .get(&KEY_DISTRIBUTORS)
             .unwrap_or(Map::new(&env));
-        distributors.remove(addr);
+        distributors.remove(addr.clone());
         env.storage()
             .instance()
             .set(&KEY_DISTRIBUTORS, &distributors);
 
+        DistributorRemoved {
+            schema_version: EVENT_SCHEMA_VERSION,
+            distributor: addr,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin-only. Grants `role` to `account`.
+    /// Emits a `RoleGranted` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    pub fn grant_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(&env, "grant_role", &admin, None, (role, account.clone()));
+
+        roles::grant(&env, role, &account);
+
+        RoleGranted {
+            schema_version: EVENT_SCHEMA_VERSION,
+            role,
+            account,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin-only. Revokes `role` from `account`.
+    /// Emits a `RoleRevoked` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    pub fn revoke_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(&env, "revoke_role", &admin, None, (role, account.clone()));
+
+        roles::revoke(&env, role, &account);
+
+        RoleRevoked {
+            schema_version: EVENT_SCHEMA_VERSION,
+            role,
+            account,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
         Ok(())
     }
 
+    /// Returns `true` if `account` holds `role`.
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        roles::has_role(&env, role, &account)
+    }
+
     /// Admin-only. Updates the global contract configuration.
     ///
     /// # Arguments
@@ -519,6 +1461,7 @@ impl AidEscrow {
     pub fn set_config(env: Env, config: Config) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
+        audit::record(&env, "set_config", &admin, None, config.clone());
 
         if config.min_amount <= 0 {
             return Err(Error::InvalidAmount);
@@ -530,6 +1473,17 @@ impl AidEscrow {
         }
 
         env.storage().instance().set(&KEY_CONFIG, &config);
+
+        ConfigUpdated {
+            schema_version: EVENT_SCHEMA_VERSION,
+            min_amount: config.min_amount,
+            max_expires_in: config.max_expires_in,
+            allowed_tokens: config.allowed_tokens,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
         Ok(())
     }
 
@@ -542,8 +1496,14 @@ impl AidEscrow {
     pub fn pause(env: Env) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
+        audit::record(&env, "pause", &admin, None, ());
         env.storage().instance().set(&KEY_PAUSED, &true);
-        ContractPausedEvent { admin }.publish(&env);
+        ContractPausedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -555,8 +1515,14 @@ impl AidEscrow {
     pub fn unpause(env: Env) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
+        audit::record(&env, "unpause", &admin, None, ());
         env.storage().instance().set(&KEY_PAUSED, &false);
-        ContractUnpausedEvent { admin }.publish(&env);
+        ContractUnpausedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -565,11 +1531,18 @@ impl AidEscrow {
     pub fn pause_action(env: Env, action: Symbol) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
+        audit::record(&env, "pause_action", &admin, None, action.clone());
 
         let key = Self::get_pause_key(action.clone())?;
         env.storage().instance().set(&key, &true);
 
-        ActionPausedEvent { admin, action }.publish(&env);
+        ActionPausedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            admin,
+            action,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -578,11 +1551,18 @@ impl AidEscrow {
     pub fn unpause_action(env: Env, action: Symbol) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
+        audit::record(&env, "unpause_action", &admin, None, action.clone());
 
         let key = Self::get_pause_key(action.clone())?;
         env.storage().instance().set(&key, &false);
 
-        ActionUnpausedEvent { admin, action }.publish(&env);
+        ActionUnpausedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            admin,
+            action,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -641,6 +1621,13 @@ impl AidEscrow {
 
         // 4. Authorization
         from.require_auth();
+        audit::record(
+            &env,
+            "fund",
+            &from,
+            None,
+            (token.clone(), from.clone(), amount),
+        );
 
         // 5. Perform Transfer
         Self::transfer_token(
@@ -654,6 +1641,7 @@ impl AidEscrow {
         // 6. Events
         let timestamp = env.ledger().timestamp();
         EscrowFunded {
+            schema_version: EVENT_SCHEMA_VERSION,
             from,
             token,
             amount,
@@ -675,7 +1663,8 @@ impl AidEscrow {
     /// * `amount` - Amount to escrow
     /// * `token` - Token contract address
     /// * `expires_at` - Expiration timestamp (0 for no expiration)
-    /// * `metadata` - Arbitrary key-value metadata for the package
+    /// * `metadata` - Arbitrary key-value metadata for the package; legacy term
+    ///   keys (`claim_starts_at`, `merkle_root`, ...) are still honoured
     #[allow(clippy::too_many_arguments)]
     pub fn create_package(
         env: Env,
@@ -686,6 +1675,40 @@ impl AidEscrow {
         token: Address,
         expires_at: u64,
         metadata: Map<Symbol, String>,
+    ) -> Result<u64, Error> {
+        Self::create_package_with_terms(
+            env,
+            operator,
+            id,
+            recipient,
+            amount,
+            token,
+            expires_at,
+            PackageTerms::default(),
+            metadata,
+        )
+    }
+
+    /// Creates a package with typed `terms` alongside free-form `metadata`.
+    ///
+    /// Same as `create_package`; a term may also be given by its legacy
+    /// metadata key, but not both ways with different values.
+    ///
+    /// # Errors
+    /// - `Error::MalformedTerm` - A legacy term key cannot be parsed
+    /// - `Error::ConflictingTerms` - Typed and metadata terms disagree
+    /// - `Error::InvalidClaimWindow` - `claim_starts_at` is before now or after `expires_at`
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_package_with_terms(
+        env: Env,
+        operator: Address,
+        id: u64,
+        recipient: Address,
+        amount: i128,
+        token: Address,
+        expires_at: u64,
+        terms: PackageTerms,
+        metadata: Map<Symbol, String>,
     ) -> Result<u64, Error> {
         Self::check_action_paused(&env, symbol_short!("create"))?;
         Self::require_admin_or_distributor(&env, &operator)?;
@@ -726,6 +1749,12 @@ impl AidEscrow {
         if env.storage().persistent().has(&key) {
             return Err(Error::PackageIdExists);
         }
+        Self::check_not_denied(&env, &recipient);
+
+        let created_at = env.ledger().timestamp();
+        let terms = terms::merge(&env, terms, &metadata)?;
+        let claim_starts_at = terms::claim_start(&terms, created_at, expires_at)?;
+        Self::check_metadata(&env, &metadata, &terms.campaign_id)?;
 
         // --- SOLVENCY CHECK ---
         let contract_balance = Self::token_balance(&env, &token, &env.current_contract_address())?;
@@ -737,8 +1766,12 @@ impl AidEscrow {
             .unwrap_or(Map::new(&env));
 
         let current_locked = locked_map.get(token.clone()).unwrap_or(0);
+        let held_fees = Self::held_fees(&env, &token);
 
-        if contract_balance < current_locked + amount {
+        // A creation-time protocol fee is charged on top of the package amount
+        let creation_fee = Self::creation_fee(&env, &token, &terms.campaign_id, amount);
+
+        if contract_balance < current_locked + held_fees + amount + creation_fee {
             return Err(Error::InsufficientFunds);
         }
 
@@ -746,13 +1779,6 @@ impl AidEscrow {
         locked_map.set(token.clone(), current_locked + amount);
         env.storage().instance().set(&KEY_TOTAL_LOCKED, &locked_map);
 
-        let created_at = env.ledger().timestamp();
-        let claim_starts_at = Self::resolve_claim_starts_at(&env, &metadata, created_at)?;
-
-        if claim_starts_at < created_at || (expires_at > 0 && claim_starts_at > expires_at) {
-            return Err(Error::InvalidState);
-        }
-
         let package = Package {
             id,
             recipient: recipient.clone(),
@@ -766,6 +1792,10 @@ impl AidEscrow {
         };
 
         env.storage().persistent().set(&key, &package);
+        terms::save(&env, id, &terms);
+        Self::record_creator(&env, id, &operator, &Self::get_admin(env.clone())?);
+        Self::enrol(&env, id, &recipient, &terms.campaign_id, amount);
+        Self::apply_protocol_fee(&env, id, &token, amount, &terms.campaign_id, created_at);
 
         let counter: u64 = env.storage().instance().get(&KEY_PKG_COUNTER).unwrap_or(0);
         if id >= counter {
@@ -778,13 +1808,15 @@ impl AidEscrow {
         env.storage().instance().set(&KEY_PKG_IDX, &(idx + 1));
 
         PackageCreated {
+            schema_version: EVENT_SCHEMA_VERSION,
             package_id: id,
             recipient: recipient.clone(),
             amount,
-            actor: operator,
+            actor: operator.clone(),
             timestamp: created_at,
         }
         .publish(&env);
+        lifecycle::created(&env, &package, &operator);
 
         Ok(id)
     }
@@ -808,6 +1840,50 @@ impl AidEscrow {
         token: Address,
         expires_in: u64,
         metadatas: Vec<Map<Symbol, String>>,
+    ) -> Result<Vec<u64>, Error> {
+        Self::batch_create(
+            env, operator, recipients, amounts, token, expires_in, None, metadatas,
+        )
+    }
+
+    /// Batch variant of `create_package_with_terms`: `terms` holds one entry
+    /// per recipient and must match the other lists in length.
+    #[allow(clippy::too_many_arguments)]
+    pub fn batch_create_packages_with_terms(
+        env: Env,
+        operator: Address,
+        recipients: Vec<Address>,
+        amounts: Vec<i128>,
+        token: Address,
+        expires_in: u64,
+        terms: Vec<PackageTerms>,
+        metadatas: Vec<Map<Symbol, String>>,
+    ) -> Result<Vec<u64>, Error> {
+        if terms.len() != recipients.len() {
+            return Err(Error::MismatchedArrays);
+        }
+        Self::batch_create(
+            env,
+            operator,
+            recipients,
+            amounts,
+            token,
+            expires_in,
+            Some(terms),
+            metadatas,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn batch_create(
+        env: Env,
+        operator: Address,
+        recipients: Vec<Address>,
+        amounts: Vec<i128>,
+        token: Address,
+        expires_in: u64,
+        terms: Option<Vec<PackageTerms>>,
+        metadatas: Vec<Map<Symbol, String>>,
     ) -> Result<Vec<u64>, Error> {
         Self::check_action_paused(&env, symbol_short!("create"))?;
         Self::require_admin_or_distributor(&env, &operator)?;
@@ -836,6 +1912,7 @@ impl AidEscrow {
             .get(&KEY_TOTAL_LOCKED)
             .unwrap_or(Map::new(&env));
         let mut current_locked = locked_map.get(token.clone()).unwrap_or(0);
+        let mut held_fees = Self::held_fees(&env, &token);
 
         // Read the current package counter
         let mut counter: u64 = env.storage().instance().get(&KEY_PKG_COUNTER).unwrap_or(0);
@@ -845,6 +1922,7 @@ impl AidEscrow {
         let created_at = env.ledger().timestamp();
         let expires_at = created_at + expires_in;
 
+        let admin = Self::get_admin(env.clone())?;
         let mut created_ids: Vec<u64> = Vec::new(&env);
         let mut total_amount: i128 = 0;
 
@@ -852,11 +1930,14 @@ impl AidEscrow {
             let recipient = recipients.get(i).unwrap();
             let amount = amounts.get(i).unwrap();
             let metadata = metadatas.get(i).unwrap();
-            let claim_starts_at = Self::resolve_claim_starts_at(&env, &metadata, created_at)?;
-
-            if claim_starts_at > expires_at {
-                return Err(Error::InvalidState);
-            }
+            Self::check_not_denied(&env, &recipient);
+            let typed = match &terms {
+                Some(terms) => terms.get(i).unwrap(),
+                None => PackageTerms::default(),
+            };
+            let package_terms = terms::merge(&env, typed, &metadata)?;
+            let claim_starts_at = terms::claim_start(&package_terms, created_at, expires_at)?;
+            Self::check_metadata(&env, &metadata, &package_terms.campaign_id)?;
 
             // Validate amount
             if amount <= 0 {
@@ -867,8 +1948,9 @@ impl AidEscrow {
                 return Err(Error::InvalidAmount);
             }
 
-            // Check solvency
-            if contract_balance < current_locked + amount {
+            // Check solvency, including any creation-time protocol fee
+            let creation_fee = Self::creation_fee(&env, &token, &package_terms.campaign_id, amount);
+            if contract_balance < current_locked + held_fees + amount + creation_fee {
                 return Err(Error::InsufficientFunds);
             }
 
@@ -892,6 +1974,18 @@ impl AidEscrow {
             };
 
             env.storage().persistent().set(&key, &package);
+            terms::save(&env, id, &package_terms);
+            Self::record_creator(&env, id, &operator, &admin);
+            Self::enrol(&env, id, &recipient, &package_terms.campaign_id, amount);
+            Self::apply_protocol_fee(
+                &env,
+                id,
+                &token,
+                amount,
+                &package_terms.campaign_id,
+                created_at,
+            );
+            held_fees += creation_fee;
 
             // Track package index for aggregation
             let idx_key = (symbol_short!("pidx"), idx);
@@ -903,6 +1997,7 @@ impl AidEscrow {
             total_amount += amount;
 
             PackageCreated {
+                schema_version: EVENT_SCHEMA_VERSION,
                 package_id: id,
                 recipient: recipient.clone(),
                 amount,
@@ -910,6 +2005,7 @@ impl AidEscrow {
                 timestamp: created_at,
             }
             .publish(&env);
+            lifecycle::created(&env, &package, &operator);
 
             created_ids.push_back(id);
         }
@@ -922,6 +2018,7 @@ impl AidEscrow {
 
         // Emit batch event
         BatchCreatedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
             ids: created_ids.clone(),
             admin: operator,
             total_amount,
@@ -935,44 +2032,18 @@ impl AidEscrow {
 
     /// Recipient claims the package.
     pub fn claim(env: Env, id: u64) -> Result<(), Error> {
-        Self::check_action_paused(&env, symbol_short!("claim"))?;
         let key = (symbol_short!("pkg"), id);
-        let mut package: Package = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(Error::PackageNotFound)?;
-
-        if package.status != PackageStatus::Created {
-            return Err(Error::PackageNotActive);
-        }
-
-        let now = env.ledger().timestamp();
-        if now < package.claim_starts_at {
-            return Err(Error::ClaimTooEarly);
-        }
-
-        if package.expires_at > 0 && now > package.expires_at {
-            return Err(Error::PackageExpired);
-        }
-
-        // Packages configured with a Merkle allowlist must be claimed through
-        // claim_with_proof so eligibility can be verified.
-        if Self::merkle_root_from_metadata(&env, &package.metadata).is_some() {
-            return Err(Error::InvalidProof);
-        }
+        let (mut package, now) = Self::load_claimable(&env, id, ClaimRoute::Address)?;
 
         package.recipient.require_auth();
-        let payout_recipient = package.recipient.clone();
         let claimant = package.recipient.clone();
 
-        Self::finalize_claim(
+        Self::settle(
             &env,
             &key,
             &mut package,
             id,
-            &payout_recipient,
-            &claimant,
+            Settlement::Claim(claimant),
             now,
         )
     }
@@ -991,133 +2062,40 @@ impl AidEscrow {
         claimant: Address,
         proof: Vec<String>,
     ) -> Result<(), Error> {
-        Self::check_action_paused(&env, symbol_short!("claim"))?;
         let key = (symbol_short!("pkg"), id);
-        let mut package: Package = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(Error::PackageNotFound)?;
-
-        if package.status != PackageStatus::Created {
-            return Err(Error::PackageNotActive);
-        }
-
-        let now = env.ledger().timestamp();
-        if now < package.claim_starts_at {
-            return Err(Error::ClaimTooEarly);
-        }
-
-        if package.expires_at > 0 && now > package.expires_at {
-            return Err(Error::PackageExpired);
-        }
+        let (mut package, now) = Self::load_claimable(&env, id, ClaimRoute::Proof)?;
 
         claimant.require_auth();
 
-        match Self::merkle_root_from_metadata(&env, &package.metadata) {
+        match Self::merkle_root(&env, &package) {
             Some(root) => {
                 if !Self::verify_merkle_proof_for_claimant(&env, &claimant, &proof, root) {
                     return Err(Error::InvalidProof);
                 }
-                Self::finalize_claim(&env, &key, &mut package, id, &claimant, &claimant, now)
+                Self::settle(
+                    &env,
+                    &key,
+                    &mut package,
+                    id,
+                    Settlement::Claim(claimant.clone()),
+                    now,
+                )
             }
             None => {
-                if claimant != package.recipient {
-                    // Check if claimant is the registered delegate
-                    let delegate = crate::delegate::get_delegate(&env, id);
-                    if delegate.is_none() || delegate.unwrap() != claimant {
-                        return Err(Error::NotAuthorized);
-                    }
-                }
-                Self::finalize_claim(&env, &key, &mut package, id, &claimant, &claimant, now)
+                // Claimant must be the recipient or a registered delegate
+                delegate::check_claimer(&env, id, &package.recipient, &claimant)?;
+                Self::settle(
+                    &env,
+                    &key,
+                    &mut package,
+                    id,
+                    Settlement::Claim(claimant.clone()),
+                    now,
+                )
             }
         }
     }
 
-    /// Claim a package through a relayer who pays the transaction costs.
-    ///
-    /// The `claimant` must be the package recipient or an authorized delegate.
-    /// Both the `claimant` and the `relayer` must have signed the transaction.
-    /// The relayer's address is recorded in the event for off-chain identification.
-    ///
-    /// Merkle-allowlist packages cannot be claimed through this path; use
-    /// `claim_with_proof` instead.
-    pub fn claim_with_relayer(
-        env: Env,
-        id: u64,
-        claimant: Address,
-        relayer: Address,
-    ) -> Result<(), Error> {
-        Self::check_action_paused(&env, symbol_short!("claim"))?;
-        let key = (symbol_short!("pkg"), id);
-        let mut package: Package = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(Error::PackageNotFound)?;
-
-        if package.status != PackageStatus::Created {
-            return Err(Error::PackageNotActive);
-        }
-
-        let now = env.ledger().timestamp();
-        if now < package.claim_starts_at {
-            return Err(Error::ClaimTooEarly);
-        }
-
-        if package.expires_at > 0 && now > package.expires_at {
-            return Err(Error::PackageExpired);
-        }
-
-        if Self::merkle_root_from_metadata(&env, &package.metadata).is_some() {
-            return Err(Error::InvalidProof);
-        }
-
-        if !delegate::is_authorised_claimer(&env, id, &package.recipient, &claimant) {
-            return Err(Error::NotAuthorized);
-        }
-
-        claimant.require_auth();
-        relayer.require_auth();
-
-        delegate::clear_delegate(&env, id);
-
-        Self::transfer_token(
-            &env,
-            &package.token,
-            &env.current_contract_address(),
-            &claimant,
-            &package.amount,
-        )?;
-
-        package.status = PackageStatus::Claimed;
-        env.storage().persistent().set(&key, &package);
-
-        Self::decrement_locked(&env, &package.token, package.amount);
-
-        let mut claimed_map: Map<Address, i128> = env
-            .storage()
-            .instance()
-            .get(&KEY_TOTAL_CLAIMED)
-            .unwrap_or(Map::new(&env));
-        let current_total = claimed_map.get(package.token.clone()).unwrap_or(0);
-        claimed_map.set(package.token.clone(), current_total + package.amount);
-        env.storage()
-            .instance()
-            .set(&KEY_TOTAL_CLAIMED, &claimed_map);
-
-        PackageClaimedByRelayer {
-            package_id: id,
-            recipient: claimant.clone(),
-            relayer,
-            amount: package.amount,
-            timestamp: now,
-        }
-        .publish(&env);
-
-        Ok(())
-    }
-
     // --- Admin Actions ---
 
     /// Admin manually triggers disbursement (overrides recipient claim need, strictly checks status).
@@ -1136,36 +2114,20 @@ impl AidEscrow {
             return Err(Error::PackageNotActive);
         }
 
-        // Transfer before accounting updates so reverted token transfers cannot
-        // leave the escrow state inconsistent.
-        Self::transfer_token(
-            &env,
-            &package.token,
-            &env.current_contract_address(),
-            &package.recipient,
-            &package.amount,
-        )?;
-
-        // State Transition
-        package.status = PackageStatus::Claimed;
-        env.storage().persistent().set(&key, &package);
-
-        // Update Locked
-        Self::decrement_locked(&env, &package.token, package.amount);
-
-        let timestamp = env.ledger().timestamp();
-        let receipt_hash = Self::receipt_hash_from_metadata(&env, &package.metadata);
-        PackageDisbursed {
-            package_id: id,
-            recipient: package.recipient.clone(),
-            amount: package.amount,
-            actor: admin.clone(),
-            timestamp,
-            receipt_hash,
+        // Hash-locked packages have no payout address until the secret is revealed.
+        if Self::is_hash_locked(&env, id) {
+            return Err(Error::HashLockedPackage);
         }
-        .publish(&env);
 
-        Ok(())
+        let now = env.ledger().timestamp();
+        Self::settle(
+            &env,
+            &key,
+            &mut package,
+            id,
+            Settlement::Disburse(admin),
+            now,
+        )
     }
 
     /// Admin revokes a package (Cancels it). Funds are effectively unlocked but remain in contract pool.
@@ -1185,14 +2147,23 @@ impl AidEscrow {
         }
 
         // State Transition
-        package.status = PackageStatus::Cancelled;
+        lifecycle::transition(
+            &env,
+            &mut package,
+            PackageStatus::Cancelled,
+            symbol_short!("revoked"),
+            &admin,
+        )?;
         env.storage().persistent().set(&key, &package);
 
         // Unlock funds (return to pool)
-        Self::decrement_locked(&env, &package.token, package.amount);
+        let outstanding = package.amount - Self::redeemed(&env, id);
+        Self::decrement_locked(&env, &package.token, outstanding);
+        Self::release_enrolment(&env, &package, outstanding);
 
         let timestamp = env.ledger().timestamp();
         PackageRevoked {
+            schema_version: EVENT_SCHEMA_VERSION,
             package_id: id,
             recipient: package.recipient.clone(),
             amount: package.amount,
@@ -1224,7 +2195,13 @@ impl AidEscrow {
         if package.status == PackageStatus::Created {
             // Check if actually expired
             if package.expires_at > 0 && env.ledger().timestamp() > package.expires_at {
-                package.status = PackageStatus::Expired;
+                lifecycle::transition(
+                    &env,
+                    &mut package,
+                    PackageStatus::Expired,
+                    symbol_short!("expired"),
+                    &admin,
+                )?;
             } else {
                 return Err(Error::InvalidState);
             }
@@ -1237,25 +2214,35 @@ impl AidEscrow {
         // If Cancelled, funds were already unlocked in `revoke`.
         // Expired packages are unlocked only after a successful refund transfer.
 
-        // Transfer Contract -> Admin
+        // Transfer Contract -> Admin. Amounts already redeemed at merchants
+        // have left the escrow.
+        let outstanding = package.amount - Self::redeemed(&env, id);
         Self::transfer_token(
             &env,
             &package.token,
             &env.current_contract_address(),
             &admin,
-            &package.amount,
+            &outstanding,
         )?;
 
         if should_unlock_locked {
-            Self::decrement_locked(&env, &package.token, package.amount);
+            Self::decrement_locked(&env, &package.token, outstanding);
+            Self::release_enrolment(&env, &package, outstanding);
         }
 
         // State Transition
-        package.status = PackageStatus::Refunded;
+        lifecycle::transition(
+            &env,
+            &mut package,
+            PackageStatus::Refunded,
+            symbol_short!("refunded"),
+            &admin,
+        )?;
         env.storage().persistent().set(&key, &package);
 
         let timestamp = env.ledger().timestamp();
         PackageRefunded {
+            schema_version: EVENT_SCHEMA_VERSION,
             package_id: id,
             recipient: package.recipient.clone(),
             amount: package.amount,
@@ -1293,14 +2280,23 @@ impl AidEscrow {
         }
 
         // 4. Update status to Cancelled and persist
-        package.status = PackageStatus::Cancelled;
+        lifecycle::transition(
+            &env,
+            &mut package,
+            PackageStatus::Cancelled,
+            symbol_short!("cancelled"),
+            &admin,
+        )?;
         env.storage().persistent().set(&key, &package);
 
         // 5. Unlock funds (Decrement the global locked amount so funds return to the pool)
-        Self::decrement_locked(&env, &package.token, package.amount);
+        let outstanding = package.amount - Self::redeemed(&env, package_id);
+        Self::decrement_locked(&env, &package.token, outstanding);
+        Self::release_enrolment(&env, &package, outstanding);
 
         let timestamp = env.ledger().timestamp();
-        PackageRevoked {
+        PackageCancelled {
+            schema_version: EVENT_SCHEMA_VERSION,
             package_id,
             recipient: package.recipient.clone(),
             amount: package.amount,
@@ -1335,6 +2331,13 @@ impl AidEscrow {
     pub fn extend_expiry(env: Env, id: u64, new_expires_at: u64) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
+        audit::record(
+            &env,
+            "extend_expiry",
+            &admin,
+            Some(id),
+            (id, new_expires_at),
+        );
         let config = Self::get_config(env.clone());
 
         let key = (symbol_short!("pkg"), id);
@@ -1372,6 +2375,7 @@ impl AidEscrow {
         env.storage().persistent().set(&key, &package);
 
         ExtendedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
             package_id: id,
             admin,
             old_expires_at,
@@ -1395,38 +2399,37 @@ impl AidEscrow {
         // 1. Only the admin can withdraw surplus
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
+        audit::record(
+            &env,
+            "withdraw_surplus",
+            &admin,
+            None,
+            (to.clone(), amount, token.clone()),
+        );
 
-        // 2. Validate amount
+        // 2. Validate amount and destination
         if amount <= 0 {
             return Err(Error::InvalidAmount);
         }
+        Self::check_not_denied(&env, &to);
 
-        // 3. Get contract's current balance for the token
+        // 3. Calculate available surplus (balance minus locked) and validate
         Self::validate_token(&env, &token)?;
-        let contract_balance = Self::token_balance(&env, &token, &env.current_contract_address())?;
-
-        // 4. Get total locked amount for the token
-        let locked_map: Map<Address, i128> = env
-            .storage()
-            .instance()
-            .get(&KEY_TOTAL_LOCKED)
-            .unwrap_or(Map::new(&env));
-        let total_locked = locked_map.get(token.clone()).unwrap_or(0);
-
-        // 5. Calculate available surplus and validate
-        let available_surplus = contract_balance - total_locked;
-        if amount > available_surplus {
+        if amount > Self::available_surplus(&env, &token)? {
             return Err(Error::InsufficientSurplus);
         }
 
-        // 6. Transfer funds from contract to recipient
+        // 4. Transfer funds from contract to recipient
         Self::transfer_token(&env, &token, &env.current_contract_address(), &to, &amount)?;
 
-        // 7. Emit event
+        // 5. Emit event
         SurplusWithdrawnEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
             to: to.clone(),
             token: token.clone(),
             amount,
+            admin,
+            timestamp: env.ledger().timestamp(),
         }
         .publish(&env);
 
@@ -1435,6 +2438,31 @@ impl AidEscrow {
 
     // --- Helpers ---
 
+    /// Contract balance of `token` not locked by any package nor held as
+    /// protocol fees awaiting collection.
+    fn available_surplus(env: &Env, token: &Address) -> Result<i128, Error> {
+        let contract_balance = Self::token_balance(env, token, &env.current_contract_address())?;
+        let locked_map: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&KEY_TOTAL_LOCKED)
+            .unwrap_or(Map::new(env));
+        let held_fees = Self::held_fees(env, token);
+        Ok(contract_balance - locked_map.get(token.clone()).unwrap_or(0) - held_fees)
+    }
+
+    /// Rejects the payout of a package whose release condition has not been
+    /// attested yet.
+    fn check_release_condition(env: &Env, package_terms: &PackageTerms) {
+        if let (Some(attestor), Some(condition_id)) =
+            (&package_terms.attestor, &package_terms.condition_id)
+        {
+            if !attestor::is_attested(env, attestor, condition_id) {
+                panic_with_error!(env, PolicyError::ConditionNotAttested);
+            }
+        }
+    }
+
     fn check_action_paused(env: &Env, action: Symbol) -> Result<(), Error> {
         if env.storage().instance().get(&KEY_PAUSED).unwrap_or(false) {
             return Err(Error::ContractPaused);
@@ -1518,129 +2546,274 @@ impl AidEscrow {
         }
     }
 
-    fn resolve_claim_starts_at(
-        env: &Env,
-        metadata: &Map<Symbol, String>,
-        created_at: u64,
-    ) -> Result<u64, Error> {
-        let key = Symbol::new(env, "claim_starts_at");
-        match metadata.get(key) {
-            Some(raw) => Self::parse_u64(raw).ok_or(Error::InvalidState),
-            None => Ok(created_at),
+    /// Loads package `id` for a payout through `route`: claims must not be
+    /// paused, and the package must be `Created`, inside its claim window and
+    /// payable through `route`. Returns the package and the current time.
+    fn load_claimable(env: &Env, id: u64, route: ClaimRoute) -> Result<(Package, u64), Error> {
+        Self::check_action_paused(env, symbol_short!("claim"))?;
+        let package: Package = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("pkg"), id))
+            .ok_or(Error::PackageNotFound)?;
+
+        if package.status != PackageStatus::Created {
+            return Err(Error::PackageNotActive);
+        }
+
+        let now = env.ledger().timestamp();
+        if now < package.claim_starts_at {
+            return Err(Error::ClaimTooEarly);
         }
-    }
 
-    fn parse_u64(value: String) -> Option<u64> {
-        let len = value.len() as usize;
-        if len == 0 || len > 20 {
-            return None;
+        if package.expires_at > 0 && now > package.expires_at {
+            return Err(Error::PackageExpired);
         }
 
-        let mut bytes = [0u8; 20];
-        value.copy_into_slice(&mut bytes[..len]);
+        if route != ClaimRoute::Own && Self::is_hash_locked(env, id) {
+            return Err(Error::HashLockedPackage);
+        }
 
-        let mut out: u64 = 0;
-        for b in bytes[..len].iter() {
-            if !b.is_ascii_digit() {
-                return None;
-            }
-            out = out.checked_mul(10)?.checked_add((b - b'0') as u64)?;
+        // Packages configured with a Merkle allowlist must be claimed through
+        // claim_with_proof so eligibility can be verified.
+        if route == ClaimRoute::Address && Self::merkle_root(env, &package).is_some() {
+            return Err(Error::InvalidProof);
         }
 
-        Some(out)
+        Ok((package, now))
     }
 
-    fn finalize_claim(
+    /// Pays out a package and moves it to `Claimed`. Every payout path goes
+    /// through here so balances, `get_total_claimed`, delegate cleanup and
+    /// events stay consistent however the package is paid.
+    fn settle(
         env: &Env,
         key: &(Symbol, u64),
         package: &mut Package,
         package_id: u64,
-        payout_recipient: &Address,
-        claimant: &Address,
+        settlement: Settlement,
         now: u64,
     ) -> Result<(), Error> {
-        Self::transfer_token(
-            env,
-            &package.token,
-            &env.current_contract_address(),
-            payout_recipient,
-            &package.amount,
-        )?;
-
-        // State Transition
-        package.status = PackageStatus::Claimed;
-        env.storage().persistent().set(key, package);
-
-        // Update Global Locked (Bookkeeping)
-        Self::decrement_locked(env, &package.token, package.amount);
+        let claimant = match &settlement {
+            Settlement::Claim(claimant) => claimant.clone(),
+            #[cfg(feature = "relayers")]
+            Settlement::Relayed { claimant, .. } => claimant.clone(),
+            Settlement::Disburse(_) => package.recipient.clone(),
+        };
+        let package_terms = terms::get(env, package);
+        if package_terms.restricted {
+            return Err(Error::RestrictedPackage);
+        }
+        Self::check_release_condition(env, &package_terms);
+        Self::check_eligible(env, package, &package_terms);
+        Self::check_not_denied(env, &package.recipient);
+        Self::check_not_denied(env, &claimant);
+        #[cfg(feature = "relayers")]
+        if let Settlement::Relayed { relayer, .. } = &settlement {
+            Self::check_not_denied(env, relayer);
+        }
+        let (reason, actor) = match &settlement {
+            Settlement::Claim(_) => (symbol_short!("claimed"), claimant.clone()),
+            #[cfg(feature = "relayers")]
+            Settlement::Relayed { .. } => (symbol_short!("relayed"), claimant.clone()),
+            Settlement::Disburse(admin) => (symbol_short!("disbursed"), admin.clone()),
+        };
+        // A capped claim pays less than the package amount; the withheld rest
+        // is unlocked with it and returns to the pool.
+        let paid = Self::apply_benefit_cap(env, package, package_id, now);
+        lifecycle::transition(env, package, PackageStatus::Claimed, reason, &actor)?;
+        Self::record_claimant(env, package_id, &claimant, &package.recipient);
+        let protocol_fee = Self::claim_fee(env, package_id, paid);
+        let payable = paid - protocol_fee;
+        #[cfg(feature = "relayers")]
+        let (relayer_fee, fee_source) = match &settlement {
+            Settlement::Relayed { relayer, .. } => {
+                Self::relayer_fee(env, package, relayer, payable)?
+            }
+            _ => (0, RelayerFeeSource::Payout),
+        };
+        #[cfg(feature = "relayers")]
+        let net_amount = if fee_source == RelayerFeeSource::Payout {
+            payable - relayer_fee
+        } else {
+            payable
+        };
+        #[cfg(not(feature = "relayers"))]
+        let net_amount = payable;
 
-        let mut claimed_map: Map<Address, i128> = env
-            .storage()
-            .instance()
-            .get(&KEY_TOTAL_CLAIMED)
-            .unwrap_or(Map::new(env));
-        let current_total = claimed_map.get(package.token.clone()).unwrap_or(0);
-        claimed_map.set(package.token.clone(), current_total + package.amount);
-        env.storage()
-            .instance()
-            .set(&KEY_TOTAL_CLAIMED, &claimed_map);
+        // Transfer before accounting updates so reverted token transfers cannot
+        // leave the escrow state inconsistent.
+        let split = Self::pay_claim(env, package, package_id, &claimant, net_amount)?;
+        #[cfg(feature = "relayers")]
+        if let Settlement::Relayed { relayer, .. } = &settlement {
+            if relayer_fee > 0 {
+                Self::transfer_token(
+                    env,
+                    &package.token,
+                    &env.current_contract_address(),
+                    relayer,
+                    &relayer_fee,
+                )?;
+            }
+            relayer::record_claim(env, relayer, &package.token, relayer_fee);
+        }
 
-        // Check if claimant is a delegate (not the recipient)
-        let is_delegate = claimant != &package.recipient;
+        Self::accrue_claim_fee(env, package_id, &package.token, protocol_fee, now);
 
-        let receipt_hash = Self::receipt_hash_from_metadata(env, &package.metadata);
+        env.storage().persistent().set(key, package);
 
-        PackageClaimed {
-            package_id,
-            recipient: payout_recipient.clone(),
-            amount: package.amount,
-            actor: payout_recipient.clone(),
-            timestamp: now,
-            receipt_hash,
+        // Update Global Locked and Claimed (Bookkeeping)
+        Self::decrement_locked(env, &package.token, package.amount);
+        Self::increment_claimed(env, &package.token, payable);
+        Self::record_benefit(env, &package.recipient, &package.token, paid);
+
+        let receipt_hash = package_terms
+            .receipt_hash
+            .unwrap_or(String::from_str(env, ""));
+        match &settlement {
+            Settlement::Claim(_) => {
+                // For delegate claims the event names the package recipient; the
+                // delegate is the actor and the split shows where the funds went.
+                let event_recipient = if split.policy.is_some() {
+                    package.recipient.clone()
+                } else {
+                    claimant.clone()
+                };
+                PackageClaimed {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    package_id,
+                    recipient: event_recipient,
+                    amount: paid,
+                    actor: claimant.clone(),
+                    timestamp: now,
+                    receipt_hash,
+                    paid_to_recipient: split.to_recipient,
+                    paid_to_delegate: split.to_delegate,
+                }
+                .publish(env);
+            }
+            #[cfg(feature = "relayers")]
+            Settlement::Relayed { relayer, .. } => {
+                PackageClaimedByRelayer {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    package_id,
+                    recipient: claimant.clone(),
+                    relayer: relayer.clone(),
+                    amount: paid,
+                    timestamp: now,
+                    relayer_fee,
+                    fee_source,
+                    net_amount,
+                    receipt_hash,
+                }
+                .publish(env);
+            }
+            Settlement::Disburse(admin) => {
+                PackageDisbursed {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    package_id,
+                    recipient: package.recipient.clone(),
+                    amount: paid,
+                    actor: admin.clone(),
+                    timestamp: now,
+                    receipt_hash,
+                }
+                .publish(env);
+            }
         }
-        .publish(env);
 
-        // A claim finalizes the package; clear any registered delegate so it
-        // cannot be reused, regardless of whether the recipient or a delegate claimed.
-        crate::delegate::clear_delegate(env, package_id);
+        // A payout finalizes the package; clear any registered delegate so it
+        // cannot be reused, regardless of who claimed or how it was paid.
+        let cleared = crate::delegate::clear_delegate(env, package_id);
+        let via_package_delegate = cleared.iter().any(|entry| entry.delegate == claimant);
 
         // If claimed by delegate, emit DelegateClaimed event
-        if is_delegate {
-            // Emit DelegateClaimed event
+        if claimant != package.recipient {
             DelegateClaimed {
+                schema_version: EVENT_SCHEMA_VERSION,
                 package_id,
                 recipient: package.recipient.clone(),
                 delegate: claimant.clone(),
                 amount: package.amount,
                 actor: claimant.clone(),
                 timestamp: now,
+                payout_policy: split.policy.unwrap_or(PayoutPolicy::ToDelegate),
+                paid_to_recipient: split.to_recipient,
+                paid_to_delegate: split.to_delegate,
             }
             .publish(env);
 
-            // Emit DelegateRevoked with claimant as actor (system-initiated on claim)
-            DelegateRevoked {
-                package_id,
-                recipient: package.recipient.clone(),
-                delegate: claimant.clone(),
-                actor: claimant.clone(), // The delegate who claimed acts as the actor for revocation
-                timestamp: now,
+            // Standing delegations outlive the package; nothing is revoked
+            if via_package_delegate
+                || !Self::record_standing_claim(env, package, package_id, &claimant, now)
+            {
+                // Emit DelegateRevoked with claimant as actor (system-initiated on claim)
+                DelegateRevoked {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    package_id,
+                    recipient: package.recipient.clone(),
+                    delegate: claimant.clone(),
+                    actor: claimant.clone(), // The delegate who claimed acts as the actor for revocation
+                    timestamp: now,
+                }
+                .publish(env);
             }
-            .publish(env);
         }
 
         Ok(())
     }
 
-    fn receipt_hash_from_metadata(env: &Env, metadata: &Map<Symbol, String>) -> String {
-        let key = Symbol::new(env, "receipt_hash");
-        metadata.get(key).unwrap_or(String::from_str(env, ""))
+    fn increment_claimed(env: &Env, token: &Address, amount: i128) {
+        let mut claimed_map: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&KEY_TOTAL_CLAIMED)
+            .unwrap_or(Map::new(env));
+        let current_total = claimed_map.get(token.clone()).unwrap_or(0);
+        claimed_map.set(token.clone(), current_total + amount);
+        env.storage()
+            .instance()
+            .set(&KEY_TOTAL_CLAIMED, &claimed_map);
+    }
+
+    /// Transfers `amount` of a claimed package's funds according to the payout
+    /// policy. Claims not made by a delegate pay `claimant` in full.
+    fn pay_claim(
+        env: &Env,
+        package: &Package,
+        package_id: u64,
+        claimant: &Address,
+        amount: i128,
+    ) -> Result<PayoutSplit, Error> {
+        let split = payout::split(amount, payout::resolve(env, package_id, package, claimant));
+        let contract = env.current_contract_address();
+
+        if split.policy.is_none() {
+            if amount > 0 {
+                Self::transfer_token(env, &package.token, &contract, claimant, &amount)?;
+            }
+            return Ok(split);
+        }
+
+        if split.to_recipient > 0 {
+            Self::transfer_token(
+                env,
+                &package.token,
+                &contract,
+                &package.recipient,
+                &split.to_recipient,
+            )?;
+        }
+        if split.to_delegate > 0 {
+            Self::transfer_token(env, &package.token, &contract, claimant, &split.to_delegate)?;
+        }
+        Ok(split)
     }
 
-    fn merkle_root_from_metadata(env: &Env, metadata: &Map<Symbol, String>) -> Option<[u8; 32]> {
-        let root_key = Symbol::new(env, META_MERKLE_ROOT_KEY);
-        metadata
-            .get(root_key)
-            .and_then(|hex| Self::parse_hex_32(&hex))
+    fn merkle_root(env: &Env, package: &Package) -> Option<[u8; 32]> {
+        terms::get(env, package)
+            .merkle_root
+            .map(|root| root.to_array())
     }
 
     fn verify_merkle_proof_for_claimant(
@@ -1657,7 +2830,7 @@ impl AidEscrow {
                 None => return false,
             };
 
-            let sibling = match Self::parse_hex_32(&sibling_hex) {
+            let sibling = match terms::parse_hex_32(&sibling_hex) {
                 Some(v) => v,
                 None => return false,
             };
@@ -1704,36 +2877,6 @@ impl AidEscrow {
         value.to_array()
     }
 
-    fn parse_hex_32(value: &String) -> Option<[u8; 32]> {
-        let len = value.len() as usize;
-        if len != 64 {
-            return None;
-        }
-
-        let mut raw = [0u8; 64];
-        value.copy_into_slice(&mut raw);
-
-        let mut out = [0u8; 32];
-        let mut i = 0usize;
-        while i < 32 {
-            let hi = Self::hex_nibble(raw[i * 2])?;
-            let lo = Self::hex_nibble(raw[i * 2 + 1])?;
-            out[i] = (hi << 4) | lo;
-            i += 1;
-        }
-
-        Some(out)
-    }
-
-    fn hex_nibble(b: u8) -> Option<u8> {
-        match b {
-            b'0'..=b'9' => Some(b - b'0'),
-            b'a'..=b'f' => Some(10 + (b - b'a')),
-            b'A'..=b'F' => Some(10 + (b - b'A')),
-            _ => None,
-        }
-    }
-
     /// Returns the total amount currently locked for a specific token.
     pub fn get_total_locked(env: Env, token: Address) -> i128 {
         let locked_map: Map<Address, i128> = env
@@ -1745,6 +2888,10 @@ impl AidEscrow {
     }
 
     /// Returns the cumulative amount ever claimed for a specific token.
+    ///
+    /// Counts what packages paid out, net of claim-time protocol fees (those
+    /// are reported by `get_fee_totals`). Relayer fees taken from a payout are
+    /// included, since they are paid out of the package.
     pub fn get_total_claimed(env: Env, token: Address) -> i128 {
         let claimed_map: Map<Address, i128> = env
             .storage()
@@ -1757,9 +2904,17 @@ impl AidEscrow {
     fn require_admin_or_distributor(env: &Env, operator: &Address) -> Result<(), Error> {
         operator.require_auth();
 
+        if Self::is_admin_or_distributor(env, operator)? {
+            Ok(())
+        } else {
+            Err(Error::NotAuthorized)
+        }
+    }
+
+    fn is_admin_or_distributor(env: &Env, operator: &Address) -> Result<bool, Error> {
         let admin = Self::get_admin(env.clone())?;
         if *operator == admin {
-            return Ok(());
+            return Ok(true);
         }
 
         let distributors: Map<Address, bool> = env
@@ -1767,13 +2922,24 @@ impl AidEscrow {
             .instance()
             .get(&KEY_DISTRIBUTORS)
             .unwrap_or(Map::new(env));
-        if distributors.get(operator.clone()).unwrap_or(false) {
+        Ok(distributors.get(operator.clone()).unwrap_or(false))
+    }
+
+    fn require_delegate_manager(env: &Env, operator: &Address) -> Result<(), Error> {
+        operator.require_auth();
+
+        if Self::is_delegate_manager(env, operator)? {
             Ok(())
         } else {
             Err(Error::NotAuthorized)
         }
     }
 
+    fn is_delegate_manager(env: &Env, operator: &Address) -> Result<bool, Error> {
+        let admin = Self::get_admin(env.clone())?;
+        Ok(*operator == admin || roles::has_role(env, Role::DelegateManager, operator))
+    }
+
     /// Retrieves the full details of a package by its ID.
     ///
     /// # Errors
@@ -1786,6 +2952,13 @@ impl AidEscrow {
             .ok_or(Error::PackageNotFound)
     }
 
+    /// Returns the typed terms of a package. For packages created with legacy
+    /// metadata keys the terms are read from that metadata.
+    pub fn get_package_terms(env: Env, id: u64) -> Result<PackageTerms, Error> {
+        let package = Self::get_package(env.clone(), id)?;
+        Ok(terms::get(&env, &package))
+    }
+
     /// Returns only the status of a package.
     /// Cheaper alternative to get_package for polling frontends.
     pub fn view_package_status(env: Env, id: u64) -> Result<PackageStatus, Error> {
@@ -1800,9 +2973,13 @@ impl AidEscrow {
     /// Iterates across all created packages and computes:
     /// - `total_committed`: sum of amounts for packages still in `Created` status,
     /// - `total_claimed`: sum of amounts for packages in `Claimed` status,
+    ///   including any claim-time protocol fee (unlike `get_total_claimed`),
     /// - `total_expired_cancelled`: sum of amounts for packages in `Expired`,
     ///    `Cancelled`, or `Refunded` status.
     ///
+    /// Amounts redeemed at merchants count as claimed even while the rest of
+    /// a restricted package is still committed or has been returned.
+    ///
     /// This is a read-only view intended for dashboards and analytics.
     pub fn get_aggregates(env: Env, token: Address) -> Aggregates {
         let count: u64 = env.storage().instance().get(&KEY_PKG_IDX).unwrap_or(0);
@@ -1819,15 +2996,19 @@ impl AidEscrow {
                     if package.token == token {
                         match package.status {
                             PackageStatus::Created => {
-                                total_committed += package.amount;
+                                let redeemed = Self::redeemed(&env, pkg_id);
+                                total_committed += package.amount - redeemed;
+                                total_claimed += redeemed;
                             }
                             PackageStatus::Claimed => {
-                                total_claimed += package.amount;
+                                total_claimed += Self::paid(&env, pkg_id, &package);
                             }
                             PackageStatus::Expired
                             | PackageStatus::Cancelled
                             | PackageStatus::Refunded => {
-                                total_expired_cancelled += package.amount;
+                                let redeemed = Self::redeemed(&env, pkg_id);
+                                total_expired_cancelled += package.amount - redeemed;
+                                total_claimed += redeemed;
                             }
                         }
                     }
@@ -1842,20 +3023,19 @@ impl AidEscrow {
         }
     }
 
-    /// Returns the number of stored packages associated with a `campaign_ref` metadata value.
+    /// Returns the number of stored packages in campaign `campaign_ref` (see `PackageTerms::campaign_id`).
     ///
     /// This read-only helper scans all package IDs from `0..package_counter`, treating the
     /// counter as an upper bound over assigned IDs and skipping gaps. It never mutates
     /// storage and is safe to use for dashboard metrics.
     pub fn get_campaign_package_count(env: Env, campaign_ref: String) -> u64 {
         let count: u64 = env.storage().instance().get(&KEY_PKG_COUNTER).unwrap_or(0);
-        let campaign_key = Symbol::new(&env, "campaign_ref");
         let mut matches = 0;
 
         for id in 0..count {
             let key = (symbol_short!("pkg"), id);
             if let Some(package) = env.storage().persistent().get::<_, Package>(&key) {
-                if package.metadata.get(campaign_key.clone()).as_ref() == Some(&campaign_ref) {
+                if terms::campaign(&env, &package).as_ref() == Some(&campaign_ref) {
                     matches += 1;
                 }
             }
@@ -1864,20 +3044,19 @@ impl AidEscrow {
         matches
     }
 
-    /// Returns the number of claimed packages associated with a `campaign_ref` metadata value.
+    /// Returns the number of claimed packages in campaign `campaign_ref` (see `PackageTerms::campaign_id`).
     ///
     /// This helper is intentionally read-only and deterministic: it performs a full scan
     /// over persisted package records and counts only packages whose status is `Claimed`.
     pub fn get_campaign_claim_count(env: Env, campaign_ref: String) -> u64 {
         let count: u64 = env.storage().instance().get(&KEY_PKG_COUNTER).unwrap_or(0);
-        let campaign_key = Symbol::new(&env, "campaign_ref");
         let mut matches = 0;
 
         for id in 0..count {
             let key = (symbol_short!("pkg"), id);
             if let Some(package) = env.storage().persistent().get::<_, Package>(&key) {
                 if package.status == PackageStatus::Claimed
-                    && package.metadata.get(campaign_key.clone()).as_ref() == Some(&campaign_ref)
+                    && terms::campaign(&env, &package).as_ref() == Some(&campaign_ref)
                 {
                     matches += 1;
                 }
@@ -1948,16 +3127,17 @@ impl AidEscrow {
 
     // --- Delegate Operations ---
 
-    /// Sets a delegate for a package. Only the admin can call this.
+    /// Sets a delegate for a package. Only the admin or a delegate manager can call this.
     /// The delegate can claim the package on behalf of the recipient.
     /// Emits a `DelegateAdded` event.
     ///
     /// # Arguments
-    /// * `admin` - Admin address (must be authenticated)
+    /// * `admin` - Admin or delegate-manager address (must be authenticated)
     /// * `package_id` - Package ID to set delegate for
     /// * `delegate` - Delegate address
     ///
     /// # Errors
+    /// - `Error::NotAuthorized` - Caller is neither the admin nor a delegate manager
     /// - `Error::PackageNotFound` - Package doesn't exist
     /// - `Error::PackageNotActive` - Package already claimed
     /// - `Error::InvalidState` - Delegate cannot be set to recipient address
@@ -1967,7 +3147,14 @@ impl AidEscrow {
         package_id: u64,
         delegate: Address,
     ) -> Result<(), Error> {
-        admin.require_auth();
+        Self::require_delegate_manager(&env, &admin)?;
+        audit::record(
+            &env,
+            "set_delegate",
+            &admin,
+            Some(package_id),
+            (admin.clone(), package_id, delegate.clone()),
+        );
 
         // Validate package state
         let key = (symbol_short!("pkg"), package_id);
@@ -1991,20 +3178,14 @@ impl AidEscrow {
 
         // Emit event
         let timestamp = env.ledger().timestamp();
-        // Get expiry if any
-        let expiry_map: Map<u64, u64> = env
-            .storage()
-            .persistent()
-            .get(&crate::delegate::KEY_DELEGATE_EXPIRY)
-            .unwrap_or(Map::new(&env));
-        let expires_at = expiry_map.get(package_id).unwrap_or(0);
 
         DelegateAdded {
+            schema_version: EVENT_SCHEMA_VERSION,
             package_id,
             recipient: package.recipient.clone(),
             delegate: delegate.clone(),
             actor: admin.clone(),
-            expires_at,
+            expires_at: 0,
             timestamp,
         }
         .publish(&env);
@@ -2013,16 +3194,17 @@ impl AidEscrow {
     }
 
     /// Sets a delegate for a package with an expiration time.
-    /// Only the admin can call this.
+    /// Only the admin or a delegate manager can call this.
     /// Emits a `DelegateAdded` event.
     ///
     /// # Arguments
-    /// * `admin` - Admin address (must be authenticated)
+    /// * `admin` - Admin or delegate-manager address (must be authenticated)
     /// * `package_id` - Package ID to set delegate for
     /// * `delegate` - Delegate address
     /// * `expires_at` - Expiration timestamp (0 = no expiration)
     ///
     /// # Errors
+    /// - `Error::NotAuthorized` - Caller is neither the admin nor a delegate manager
     /// - `Error::PackageNotFound` - Package doesn't exist
     /// - `Error::PackageNotActive` - Package already claimed
     /// - `Error::InvalidState` - Invalid delegate address or expiration
@@ -2033,7 +3215,14 @@ impl AidEscrow {
         delegate: Address,
         expires_at: u64,
     ) -> Result<(), Error> {
-        admin.require_auth();
+        Self::require_delegate_manager(&env, &admin)?;
+        audit::record(
+            &env,
+            "set_delegate_with_expiry",
+            &admin,
+            Some(package_id),
+            (admin.clone(), package_id, delegate.clone(), expires_at),
+        );
 
         // Validate expiration time
         let now = env.ledger().timestamp();
@@ -2065,6 +3254,7 @@ impl AidEscrow {
         let timestamp = env.ledger().timestamp();
 
         DelegateAdded {
+            schema_version: EVENT_SCHEMA_VERSION,
             package_id,
             recipient: package.recipient.clone(),
             delegate: delegate.clone(),
@@ -2077,17 +3267,25 @@ impl AidEscrow {
         Ok(())
     }
 
-    /// Removes the delegate for a package. Called after a successful claim
-    /// to prevent any further reassignment. Emits a `DelegateRevoked` event.
+    /// Removes every delegate for a package. Called after a successful claim
+    /// to prevent any further reassignment. Emits a `DelegateRevoked` event per delegate.
     ///
     /// # Arguments
-    /// * `admin` - Admin address (must be authenticated)
+    /// * `caller` - Admin, delegate manager, or the package recipient (must be authenticated)
     /// * `package_id` - Package ID to remove delegate for
     ///
     /// # Errors
     /// - `Error::PackageNotFound` - Package doesn't exist
-    pub fn revoke_delegate(env: Env, admin: Address, package_id: u64) -> Result<(), Error> {
-        admin.require_auth();
+    /// - `Error::NotAuthorized` - Caller may not manage this package's delegate
+    pub fn revoke_delegate(env: Env, caller: Address, package_id: u64) -> Result<(), Error> {
+        caller.require_auth();
+        audit::record(
+            &env,
+            "revoke_delegate",
+            &caller,
+            Some(package_id),
+            (caller.clone(), package_id),
+        );
 
         // Check package exists
         let key = (symbol_short!("pkg"), package_id);
@@ -2097,21 +3295,22 @@ impl AidEscrow {
             .get(&key)
             .ok_or(Error::PackageNotFound)?;
 
-        // Get the current delegate before removing
-        let current_delegate = crate::delegate::get_delegate(&env, package_id);
+        if caller != package.recipient && !Self::is_delegate_manager(&env, &caller)? {
+            return Err(Error::NotAuthorized);
+        }
 
         // Use the delegate module function
-        crate::delegate::clear_delegate(&env, package_id);
-
-        // Emit event if there was a delegate to revoke
-        if let Some(delegate) = current_delegate {
-            let timestamp = env.ledger().timestamp();
+        let removed = crate::delegate::clear_delegate(&env, package_id);
 
+        // Emit one event per revoked delegate
+        let timestamp = env.ledger().timestamp();
+        for entry in removed.iter() {
             DelegateRevoked {
+                schema_version: EVENT_SCHEMA_VERSION,
                 package_id,
                 recipient: package.recipient.clone(),
-                delegate: delegate.clone(),
-                actor: admin.clone(),
+                delegate: entry.delegate,
+                actor: caller.clone(),
                 timestamp,
             }
             .publish(&env);
@@ -2125,17 +3324,30 @@ impl AidEscrow {
         crate::delegate::get_delegate(&env, package_id)
     }
 
-    /// Gets delegate information including expiration.
-    pub fn get_delegate_info(env: Env, package_id: u64) -> Option<(Address, Option<u64>)> {
+    /// Gets every active delegate for a package, oldest first.
+    pub fn get_delegates(env: Env, package_id: u64) -> Vec<Address> {
+        crate::delegate::get_delegates(&env, package_id)
+    }
+
+    /// Gets the full delegate set of a package, including each entry's expiration.
+    pub fn get_delegate_info(env: Env, package_id: u64) -> Vec<crate::delegate::DelegateEntry> {
         crate::delegate::get_delegate_info(&env, package_id)
     }
 
-    /// Gets the delegate history for a package.
+    /// Gets up to `limit` delegate history records for a package, oldest
+    /// first, starting at record `cursor`. `limit` is capped at 50.
     pub fn get_delegate_history(
         env: Env,
         package_id: u64,
+        cursor: u32,
+        limit: u32,
     ) -> Vec<crate::delegate::DelegateHistory> {
-        crate::delegate::get_delegate_history(&env, package_id)
+        crate::delegate::get_delegate_history(&env, package_id, cursor, limit)
+    }
+
+    /// Gets the number of delegate history records stored for a package.
+    pub fn get_delegate_history_len(env: Env, package_id: u64) -> u32 {
+        crate::delegate::get_delegate_history_len(&env, package_id)
     }
 
     // --- Token Allowlist Management ---
@@ -2154,6 +3366,7 @@ impl AidEscrow {
     pub fn add_allowed_token(env: Env, token: Address) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
+        audit::record(&env, "add_allowed_token", &admin, None, token.clone());
 
         // Validate the token contract
         Self::validate_token(&env, &token)?;
@@ -2173,6 +3386,7 @@ impl AidEscrow {
         // Emit event
         let timestamp = env.ledger().timestamp();
         TokenAdded {
+            schema_version: EVENT_SCHEMA_VERSION,
             admin,
             token,
             timestamp,
@@ -2194,6 +3408,7 @@ impl AidEscrow {
     pub fn remove_allowed_token(env: Env, token: Address) -> Result<(), Error> {
         let admin = Self::get_admin(env.clone())?;
         admin.require_auth();
+        audit::record(&env, "remove_allowed_token", &admin, None, token.clone());
 
         // Read current config
         let mut config = Self::get_config(env.clone());
@@ -2221,6 +3436,7 @@ impl AidEscrow {
         // Emit event
         let timestamp = env.ledger().timestamp();
         TokenRemoved {
+            schema_version: EVENT_SCHEMA_VERSION,
             admin,
             token,
             timestamp,
@@ -2232,14 +3448,2883 @@ impl AidEscrow {
 
     /// Cleanup expired delegates to reclaim storage.
     /// Called periodically or as part of maintenance operations.
-    pub fn cleanup_expired_delegates(env: Env, admin: Address) -> Result<u32, Error> {
-        admin.require_auth();
-        crate::delegate::cleanup_expired_delegates(&env, &admin)
+    ///
+    /// Visits at most `limit` (capped at 50) packages that have had delegates,
+    /// starting at `cursor`; call again with the returned `next_cursor` until
+    /// `done` is true. Each pruned entry is recorded in the package's delegate
+    /// history with reason `expired` and emits a `DelegateRemoved` event.
+    pub fn cleanup_expired_delegates(
+        env: Env,
+        admin: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<crate::delegate::DelegateCleanup, Error> {
+        Self::require_delegate_manager(&env, &admin)?;
+        audit::record(
+            &env,
+            "cleanup_expired_delegates",
+            &admin,
+            None,
+            (admin.clone(), cursor, limit),
+        );
+        crate::delegate::cleanup_expired_delegates(&env, &admin, cursor, limit)
     }
 }
 
 // --- Tests ---
 
+#[cfg(feature = "recovery")]
+#[contractimpl]
+impl AidEscrow {
+    fn require_recovery_operator(env: &Env, operator: &Address) -> Result<(), Error> {
+        operator.require_auth();
+
+        let admin = Self::get_admin(env.clone())?;
+        if *operator == admin || roles::has_role(env, Role::RecoveryAgent, operator) {
+            Ok(())
+        } else {
+            Err(Error::NotAuthorized)
+        }
+    }
+
+    /// Moves a single package from the request's old recipient to its new one.
+    /// Returns `false` (and changes nothing) if the package is not eligible.
+    fn rotate_recipient(
+        env: &Env,
+        request: &RecoveryRequest,
+        package_id: u64,
+        operator: &Address,
+        now: u64,
+    ) -> bool {
+        let key = (symbol_short!("pkg"), package_id);
+        let mut package: Package = match env.storage().persistent().get(&key) {
+            Some(p) => p,
+            None => return false,
+        };
+
+        if package.status != PackageStatus::Created
+            || package.recipient != request.old_recipient
+            || Self::is_hash_locked(env, package_id)
+        {
+            return false;
+        }
+
+        package.recipient = request.new_recipient.clone();
+        env.storage().persistent().set(&key, &package);
+        let campaign = terms::campaign(env, &package);
+        Self::move_enrolment(
+            env,
+            &package,
+            (&campaign, &request.old_recipient),
+            (&campaign, &request.new_recipient),
+        );
+
+        if delegate::get_delegate_info(env, package_id)
+            .iter()
+            .any(|entry| entry.delegate == request.new_recipient)
+        {
+            let _ = delegate::remove_delegate(env, operator, package_id, &request.new_recipient);
+        }
+
+        recovery::record_change(
+            env,
+            &RecipientChange {
+                package_id,
+                old_recipient: request.old_recipient.clone(),
+                new_recipient: request.new_recipient.clone(),
+                recovery_id: request.id,
+                changed_by: operator.clone(),
+                changed_at: now,
+            },
+        );
+
+        PackageRecipientChanged {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id,
+            old_recipient: request.old_recipient.clone(),
+            new_recipient: request.new_recipient.clone(),
+            recovery_id: request.id,
+            actor: operator.clone(),
+            timestamp: now,
+        }
+        .publish(env);
+
+        true
+    }
+
+    /// Admin-only. Sets the waiting period (seconds) between proposing and executing
+    /// a recipient recovery. Defaults to 3 days. Emits a `RecoveryDelaySet` event.
+    ///
+    /// # Errors
+    /// - `Error::InvalidState` - `seconds` is below the 1 day minimum
+    pub fn set_recovery_delay(env: Env, seconds: u64) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(&env, "set_recovery_delay", &admin, None, seconds);
+
+        if seconds < recovery::MIN_RECOVERY_DELAY {
+            return Err(Error::InvalidState);
+        }
+        let old = recovery::get_delay(&env);
+        recovery::set_delay(&env, seconds);
+
+        RecoveryDelaySet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            old,
+            new: seconds,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the recipient recovery waiting period in seconds.
+    pub fn get_recovery_delay(env: Env) -> u64 {
+        recovery::get_delay(&env)
+    }
+
+    /// Admin or recovery-agent only. Proposes moving `old_recipient`'s packages to
+    /// `new_recipient`, either a single package or all open packages.
+    /// The proposal can be executed once the recovery delay has elapsed, unless
+    /// `old_recipient` objects first. Emits a `RecoveryProposed` event.
+    ///
+    /// # Errors
+    /// - `Error::NotAuthorized` - `operator` is neither admin nor a recovery agent
+    /// - `Error::InvalidState` - `new_recipient == old_recipient`, or the scoped package
+    ///   is not an open, address-based package of `old_recipient`
+    /// - `PolicyError::AddressDenied` - `new_recipient` is on the denylist
+    pub fn propose_recovery(
+        env: Env,
+        operator: Address,
+        old_recipient: Address,
+        new_recipient: Address,
+        scope: RecoveryScope,
+    ) -> Result<u64, Error> {
+        Self::require_recovery_operator(&env, &operator)?;
+        audit::record(
+            &env,
+            "propose_recovery",
+            &operator,
+            None,
+            (
+                operator.clone(),
+                old_recipient.clone(),
+                new_recipient.clone(),
+                scope.clone(),
+            ),
+        );
+
+        if old_recipient == new_recipient {
+            return Err(Error::InvalidState);
+        }
+        Self::check_not_denied(&env, &new_recipient);
+
+        if let RecoveryScope::Package(package_id) = scope {
+            let package = Self::get_package(env.clone(), package_id)?;
+            if package.status != PackageStatus::Created
+                || package.recipient != old_recipient
+                || Self::is_hash_locked(&env, package_id)
+            {
+                return Err(Error::InvalidState);
+            }
+        }
+
+        let now = env.ledger().timestamp();
+        let request = RecoveryRequest {
+            id: recovery::next_id(&env),
+            old_recipient: old_recipient.clone(),
+            new_recipient: new_recipient.clone(),
+            scope: scope.clone(),
+            proposer: operator.clone(),
+            proposed_at: now,
+            executable_at: now.saturating_add(recovery::get_delay(&env)),
+            status: RecoveryStatus::Pending,
+            cursor: 0,
+            rotated: 0,
+        };
+        recovery::save(&env, &request);
+
+        RecoveryProposed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            recovery_id: request.id,
+            old_recipient,
+            new_recipient,
+            scope,
+            actor: operator,
+            executable_at: request.executable_at,
+            timestamp: now,
+        }
+        .publish(&env);
+
+        Ok(request.id)
+    }
+
+    /// Original-recipient only. Objects to a pending recovery, permanently blocking it.
+    /// Emits a `RecoveryObjected` event.
+    ///
+    /// # Errors
+    /// - `Error::RecoveryNotFound` - No such recovery request
+    /// - `Error::InvalidState` - Request is no longer pending
+    pub fn object_recovery(env: Env, recovery_id: u64) -> Result<(), Error> {
+        let mut request = recovery::load(&env, recovery_id)?;
+        if request.status != RecoveryStatus::Pending {
+            return Err(Error::InvalidState);
+        }
+
+        request.old_recipient.require_auth();
+        audit::record(
+            &env,
+            "object_recovery",
+            &request.old_recipient,
+            None,
+            recovery_id,
+        );
+
+        request.status = RecoveryStatus::Objected;
+        recovery::save(&env, &request);
+
+        RecoveryObjected {
+            schema_version: EVENT_SCHEMA_VERSION,
+            recovery_id,
+            old_recipient: request.old_recipient,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin or recovery-agent only. Cancels a pending recovery.
+    /// Emits a `RecoveryCancelled` event.
+    pub fn cancel_recovery(env: Env, operator: Address, recovery_id: u64) -> Result<(), Error> {
+        Self::require_recovery_operator(&env, &operator)?;
+        audit::record(
+            &env,
+            "cancel_recovery",
+            &operator,
+            None,
+            (operator.clone(), recovery_id),
+        );
+
+        let mut request = recovery::load(&env, recovery_id)?;
+        if request.status != RecoveryStatus::Pending {
+            return Err(Error::InvalidState);
+        }
+
+        request.status = RecoveryStatus::Cancelled;
+        recovery::save(&env, &request);
+
+        RecoveryCancelled {
+            schema_version: EVENT_SCHEMA_VERSION,
+            recovery_id,
+            actor: operator,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin or recovery-agent only. Executes a pending recovery after its waiting period.
+    ///
+    /// Rewrites `Package.recipient` for every package in scope that is still `Created`
+    /// and owned by `old_recipient`, records a `RecipientChange` per package and emits
+    /// `PackageRecipientChanged` for each, followed by `RecoveryExecuted` once done.
+    /// A delegate equal to the new recipient is cleared, since it would be redundant.
+    ///
+    /// An `AllOpen` recovery visits at most `limit` (capped at 50) package ids per
+    /// call; call again until `done` is true. After the first call the request is
+    /// `Executing` and can no longer be objected to or cancelled.
+    ///
+    /// # Errors
+    /// - `Error::RecoveryNotFound` - No such recovery request
+    /// - `Error::InvalidState` - Request is neither pending nor executing
+    /// - `Error::RecoveryNotReady` - Waiting period has not elapsed
+    /// - `PolicyError::EnrolmentLimitReached` - The new recipient would exceed a campaign's enrolment limits
+    /// - `PolicyError::RecipientNotRegistered` / `PolicyError::DuplicateIdentity` - The beneficiary
+    ///   registry rejects the new recipient
+    /// - `PolicyError::AddressDenied` - The new recipient is on the denylist
+    pub fn execute_recovery(
+        env: Env,
+        operator: Address,
+        recovery_id: u64,
+        limit: u32,
+    ) -> Result<RecoveryProgress, Error> {
+        Self::require_recovery_operator(&env, &operator)?;
+        audit::record(
+            &env,
+            "execute_recovery",
+            &operator,
+            None,
+            (operator.clone(), recovery_id, limit),
+        );
+
+        let mut request = recovery::load(&env, recovery_id)?;
+        let now = env.ledger().timestamp();
+        match request.status {
+            RecoveryStatus::Pending if now < request.executable_at => {
+                return Err(Error::RecoveryNotReady);
+            }
+            RecoveryStatus::Pending | RecoveryStatus::Executing => {}
+            _ => return Err(Error::InvalidState),
+        }
+        Self::check_not_denied(&env, &request.new_recipient);
+
+        let mut rotated = 0u32;
+        let done = match request.scope {
+            RecoveryScope::Package(package_id) => {
+                if Self::rotate_recipient(&env, &request, package_id, &operator, now) {
+                    rotated += 1;
+                }
+                true
+            }
+            RecoveryScope::AllOpen => {
+                let count: u64 = env.storage().instance().get(&KEY_PKG_COUNTER).unwrap_or(0);
+                let end = request
+                    .cursor
+                    .saturating_add(limit.min(recovery::MAX_RECOVERY_PAGE) as u64)
+                    .min(count);
+                for package_id in request.cursor..end {
+                    if Self::rotate_recipient(&env, &request, package_id, &operator, now) {
+                        rotated += 1;
+                    }
+                }
+                request.cursor = end;
+                request.cursor >= count
+            }
+        };
+
+        request.rotated += rotated;
+        request.status = if done {
+            RecoveryStatus::Executed
+        } else {
+            RecoveryStatus::Executing
+        };
+        recovery::save(&env, &request);
+
+        if done {
+            RecoveryExecuted {
+                schema_version: EVENT_SCHEMA_VERSION,
+                recovery_id,
+                old_recipient: request.old_recipient,
+                new_recipient: request.new_recipient,
+                packages_rotated: request.rotated,
+                actor: operator,
+                timestamp: now,
+            }
+            .publish(&env);
+        }
+
+        Ok(RecoveryProgress {
+            rotated,
+            next_cursor: request.cursor,
+            done,
+        })
+    }
+
+    /// Returns a recovery request by id.
+    ///
+    /// # Errors
+    /// Returns `Error::RecoveryNotFound` if no request exists with the given id.
+    pub fn get_recovery(env: Env, recovery_id: u64) -> Result<RecoveryRequest, Error> {
+        recovery::load(&env, recovery_id)
+    }
+
+    /// Returns the recipient-change history of a package, oldest first.
+    pub fn get_recipient_history(env: Env, package_id: u64) -> Vec<RecipientChange> {
+        recovery::get_history(&env, package_id)
+    }
+}
+
+#[cfg(feature = "merchants")]
+#[contractimpl]
+impl AidEscrow {
+    /// Recipient pays `amount` of a restricted package to a registered merchant.
+    ///
+    /// Funds go straight from the escrow to `merchant`, less any claim-time
+    /// protocol fee. Partial redemptions leave the package `Created` with the
+    /// rest still redeemable; the redemption that empties it moves it to
+    /// `Claimed`. Emits `PackageRedeemed`.
+    ///
+    /// # Errors
+    /// - `Error::PackageNotActive` - Package is not `Created`
+    /// - `Error::ClaimTooEarly` / `Error::PackageExpired` - Outside the claim window
+    /// - `Error::InvalidState` - Package is not a restricted-spend voucher
+    /// - `PolicyError::ConditionNotAttested` - The package's release condition is not attested yet
+    /// - `PolicyError::NotEligible` - The campaign issuer does not confirm the recipient
+    /// - `Error::MerchantUnavailable` - Merchant is not registered or is suspended
+    /// - `PolicyError::CategoryNotAllowed` - None of the merchant's categories is allowed by the package
+    /// - `Error::InvalidAmount` - `amount` is not positive or exceeds what remains
+    /// - `PolicyError::BenefitCapExceeded` - `amount` would take the recipient over the token's benefit cap
+    pub fn redeem(env: Env, id: u64, merchant: Address, amount: i128) -> Result<(), Error> {
+        let key = (symbol_short!("pkg"), id);
+        let (mut package, now) = Self::load_claimable(&env, id, ClaimRoute::Own)?;
+
+        let package_terms = terms::get(&env, &package);
+        if !package_terms.restricted {
+            return Err(Error::InvalidState);
+        }
+        Self::check_release_condition(&env, &package_terms);
+        Self::check_eligible(&env, &package, &package_terms);
+        Self::check_not_denied(&env, &package.recipient);
+        Self::check_not_denied(&env, &merchant);
+
+        package.recipient.require_auth();
+        audit::record(
+            &env,
+            "redeem",
+            &package.recipient,
+            Some(id),
+            (id, merchant.clone(), amount),
+        );
+
+        let info = merchant::get(&env, &merchant).ok_or(Error::MerchantUnavailable)?;
+        if info.suspended {
+            return Err(Error::MerchantUnavailable);
+        }
+        let category = merchant::spend_category(&info, &package_terms.allowed_categories);
+        if category.is_none() && package_terms.allowed_categories.is_some() {
+            panic_with_error!(&env, PolicyError::CategoryNotAllowed);
+        }
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let remaining = package.amount - merchant::get_redeemed(&env, id) - amount;
+        if remaining < 0 {
+            return Err(Error::InvalidAmount);
+        }
+        Self::check_benefit_room(&env, &package, amount);
+
+        let protocol_fee = Self::claim_fee(&env, id, amount);
+        let net_amount = amount - protocol_fee;
+        Self::transfer_token(
+            &env,
+            &package.token,
+            &env.current_contract_address(),
+            &merchant,
+            &net_amount,
+        )?;
+
+        Self::accrue_claim_fee(&env, id, &package.token, protocol_fee, now);
+
+        merchant::add_redeemed(&env, id, amount);
+        merchant::add_settled(&env, &merchant, &package.token, net_amount);
+        if let (Some(campaign), Some(category)) = (&package_terms.campaign_id, &category) {
+            merchant::add_category_spend(&env, campaign, &package.token, category, amount);
+        }
+        Self::decrement_locked(&env, &package.token, amount);
+        Self::increment_claimed(&env, &package.token, net_amount);
+        Self::record_benefit(&env, &package.recipient, &package.token, amount);
+
+        if remaining == 0 {
+            let recipient = package.recipient.clone();
+            lifecycle::transition(
+                &env,
+                &mut package,
+                PackageStatus::Claimed,
+                symbol_short!("redeemed"),
+                &recipient,
+            )?;
+            env.storage().persistent().set(&key, &package);
+        }
+
+        PackageRedeemed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id: id,
+            recipient: package.recipient.clone(),
+            merchant,
+            amount,
+            net_amount,
+            remaining,
+            category,
+            timestamp: now,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the amount redeemed at merchants from a package so far.
+    pub fn get_redeemed(env: Env, id: u64) -> i128 {
+        merchant::get_redeemed(&env, id)
+    }
+
+    /// Admin-only. Registers `merchant` to accept redemptions of restricted
+    /// packages, tagged with the categories it sells.
+    /// Emits a `MerchantAdded` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    /// Returns `Error::InvalidState` if the merchant is already registered.
+    pub fn add_merchant(env: Env, merchant: Address, categories: Vec<Symbol>) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(
+            &env,
+            "add_merchant",
+            &admin,
+            None,
+            (merchant.clone(), categories.clone()),
+        );
+
+        if merchant::get(&env, &merchant).is_some() {
+            return Err(Error::InvalidState);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        merchant::save(
+            &env,
+            &merchant,
+            &MerchantInfo {
+                suspended: false,
+                categories: categories.clone(),
+                registered_at: timestamp,
+            },
+        );
+
+        MerchantAdded {
+            schema_version: EVENT_SCHEMA_VERSION,
+            merchant,
+            categories,
+            admin,
+            timestamp,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin-only. Suspends or reinstates a registered merchant. A suspended
+    /// merchant cannot accept redemptions; its settlement totals are kept.
+    /// Emits a `MerchantUpdated` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    /// Returns `Error::MerchantUnavailable` if the merchant is not registered.
+    pub fn set_merchant_suspended(
+        env: Env,
+        merchant: Address,
+        suspended: bool,
+    ) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(
+            &env,
+            "set_merchant_suspended",
+            &admin,
+            None,
+            (merchant.clone(), suspended),
+        );
+
+        let mut info = merchant::get(&env, &merchant).ok_or(Error::MerchantUnavailable)?;
+        info.suspended = suspended;
+        Self::save_merchant(&env, merchant, info, admin);
+        Ok(())
+    }
+
+    /// Admin-only. Replaces the category tags of a registered merchant.
+    /// Emits a `MerchantUpdated` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    /// Returns `Error::MerchantUnavailable` if the merchant is not registered.
+    pub fn set_merchant_categories(
+        env: Env,
+        merchant: Address,
+        categories: Vec<Symbol>,
+    ) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(
+            &env,
+            "set_merchant_categories",
+            &admin,
+            None,
+            (merchant.clone(), categories.clone()),
+        );
+
+        let mut info = merchant::get(&env, &merchant).ok_or(Error::MerchantUnavailable)?;
+        info.categories = categories;
+        Self::save_merchant(&env, merchant, info, admin);
+        Ok(())
+    }
+
+    fn save_merchant(env: &Env, merchant: Address, info: MerchantInfo, admin: Address) {
+        merchant::save(env, &merchant, &info);
+        MerchantUpdated {
+            schema_version: EVENT_SCHEMA_VERSION,
+            merchant,
+            suspended: info.suspended,
+            categories: info.categories,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(env);
+    }
+
+    /// Returns the registry entry (status and categories) of `merchant`, if registered.
+    pub fn get_merchant(env: Env, merchant: Address) -> Option<MerchantInfo> {
+        merchant::get(&env, &merchant)
+    }
+
+    /// Returns the total `merchant` has been paid in `token` through redemptions.
+    pub fn get_merchant_settled(env: Env, merchant: Address, token: Address) -> i128 {
+        merchant::get_settled(&env, &merchant, &token)
+    }
+
+    /// Returns the amounts of `token` redeemed per spending category by
+    /// packages in campaign `campaign_ref`, before protocol fees.
+    pub fn get_campaign_category_spend(
+        env: Env,
+        campaign_ref: String,
+        token: Address,
+    ) -> Map<Symbol, i128> {
+        merchant::get_category_spend(&env, &campaign_ref, &token)
+    }
+
+    /// Amount of package `id` redeemed at merchants so far.
+    fn redeemed(env: &Env, id: u64) -> i128 {
+        merchant::get_redeemed(env, id)
+    }
+}
+
+/// Without merchant redemptions, the core paths call these no-ops instead.
+#[cfg(not(feature = "merchants"))]
+impl AidEscrow {
+    fn redeemed(_env: &Env, _id: u64) -> i128 {
+        0
+    }
+}
+
+#[cfg(feature = "fees")]
+#[contractimpl]
+impl AidEscrow {
+    /// Admin-only. Sets the treasury address that receives collected protocol fees.
+    /// Emits a `TreasurySet` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    pub fn set_treasury(env: Env, treasury: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(&env, "set_treasury", &admin, None, treasury.clone());
+
+        fee::set_treasury(&env, &treasury);
+
+        TreasurySet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            treasury,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the treasury address, if set.
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        fee::get_treasury(&env)
+    }
+
+    /// Admin-only. Sets the protocol fee for a token or campaign, or removes it
+    /// when `fee` is `None`. Applies to packages created afterwards; a campaign
+    /// fee wins over the token fee. Emits a `ProtocolFeeSet` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    /// Returns `Error::InvalidProtocolFee` if the rate exceeds 10 000 bps.
+    pub fn set_protocol_fee(
+        env: Env,
+        scope: FeeScope,
+        fee: Option<ProtocolFee>,
+    ) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(
+            &env,
+            "set_protocol_fee",
+            &admin,
+            None,
+            (scope.clone(), fee.clone()),
+        );
+
+        if let Some(fee) = &fee {
+            fee::validate(fee)?;
+        }
+        fee::set(&env, &scope, &fee);
+
+        ProtocolFeeSet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            scope,
+            fee,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the protocol fee configured for `scope`, if any.
+    pub fn get_protocol_fee(env: Env, scope: FeeScope) -> Option<ProtocolFee> {
+        fee::get(&env, &scope)
+    }
+
+    /// Returns the protocol fees accrued and collected for `token`.
+    pub fn get_fee_totals(env: Env, token: Address) -> FeeTotals {
+        fee::get_totals(&env, &token)
+    }
+
+    /// Sweeps every held protocol fee for `token` to the treasury and returns
+    /// the amount. Callable by anyone, since funds can only go to the treasury.
+    /// Emits a `FeesCollected` event.
+    ///
+    /// # Errors
+    /// Returns `Error::TreasuryNotSet` if no treasury has been configured.
+    pub fn collect_fees(env: Env, token: Address) -> Result<i128, Error> {
+        let treasury = fee::get_treasury(&env).ok_or(Error::TreasuryNotSet)?;
+        audit::record(&env, "collect_fees", &treasury, None, token.clone());
+
+        let amount = fee::take_held(&env, &token);
+        if amount > 0 {
+            Self::transfer_token(
+                &env,
+                &token,
+                &env.current_contract_address(),
+                &treasury,
+                &amount,
+            )?;
+        }
+
+        FeesCollected {
+            schema_version: EVENT_SCHEMA_VERSION,
+            token,
+            treasury,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Protocol fees held in `token` for collection; they back no package and
+    /// cannot be withdrawn as surplus.
+    fn held_fees(env: &Env, token: &Address) -> i128 {
+        fee::get_totals(env, token).held()
+    }
+
+    /// Creation-time protocol fee due on top of a new package of `amount`.
+    fn creation_fee(env: &Env, token: &Address, campaign: &Option<String>, amount: i128) -> i128 {
+        match fee::resolve(env, token, campaign) {
+            Some(f) if f.timing == FeeTiming::AtCreation => fee::compute(amount, f.bps),
+            _ => 0,
+        }
+    }
+
+    /// Charges a creation-time protocol fee, or snapshots the claim-time rate,
+    /// for a newly created package. The solvency check must already include
+    /// the creation-time fee.
+    fn apply_protocol_fee(
+        env: &Env,
+        package_id: u64,
+        token: &Address,
+        amount: i128,
+        campaign: &Option<String>,
+        now: u64,
+    ) {
+        let Some(protocol_fee) = fee::resolve(env, token, campaign) else {
+            return;
+        };
+        match protocol_fee.timing {
+            FeeTiming::AtClaim => fee::set_claim_bps(env, package_id, protocol_fee.bps),
+            FeeTiming::AtCreation => {
+                let charged = fee::compute(amount, protocol_fee.bps);
+                if charged > 0 {
+                    fee::accrue(env, token, charged);
+                    ProtocolFeeCharged {
+                        schema_version: EVENT_SCHEMA_VERSION,
+                        package_id,
+                        token: token.clone(),
+                        amount: charged,
+                        timing: FeeTiming::AtCreation,
+                        timestamp: now,
+                    }
+                    .publish(env);
+                }
+            }
+        }
+    }
+
+    /// Claim-time protocol fee on `amount` paid out of `package_id`.
+    fn claim_fee(env: &Env, package_id: u64, amount: i128) -> i128 {
+        fee::compute(amount, fee::get_claim_bps(env, package_id))
+    }
+
+    /// Holds a claim-time protocol fee for collection.
+    fn accrue_claim_fee(env: &Env, package_id: u64, token: &Address, amount: i128, now: u64) {
+        if amount > 0 {
+            fee::accrue(env, token, amount);
+            ProtocolFeeCharged {
+                schema_version: EVENT_SCHEMA_VERSION,
+                package_id,
+                token: token.clone(),
+                amount,
+                timing: FeeTiming::AtClaim,
+                timestamp: now,
+            }
+            .publish(env);
+        }
+    }
+}
+
+/// Without protocol fees, the core paths call these no-ops instead.
+#[cfg(not(feature = "fees"))]
+impl AidEscrow {
+    fn held_fees(_env: &Env, _token: &Address) -> i128 {
+        0
+    }
+
+    fn creation_fee(
+        _env: &Env,
+        _token: &Address,
+        _campaign: &Option<String>,
+        _amount: i128,
+    ) -> i128 {
+        0
+    }
+
+    fn apply_protocol_fee(
+        _env: &Env,
+        _package_id: u64,
+        _token: &Address,
+        _amount: i128,
+        _campaign: &Option<String>,
+        _now: u64,
+    ) {
+    }
+
+    fn claim_fee(_env: &Env, _package_id: u64, _amount: i128) -> i128 {
+        0
+    }
+
+    fn accrue_claim_fee(_env: &Env, _package_id: u64, _token: &Address, _amount: i128, _now: u64) {}
+}
+
+#[cfg(feature = "benefit-caps")]
+#[contractimpl]
+impl AidEscrow {
+    /// Returns what `recipient` has been paid in `token`: total, number of
+    /// payouts, last payout time, and the amount within the current benefit
+    /// cap window. Read from stored counters; payouts made before the
+    /// counters existed are not included.
+    pub fn get_recipient_totals(env: Env, recipient: Address, token: Address) -> RecipientTotals {
+        benefit::get_totals(&env, &recipient, &token)
+    }
+
+    /// Admin-only. Caps what one recipient may be paid in `token` over a
+    /// rolling window, or removes the cap when `cap` is `None`.
+    /// Emits a `BenefitCapSet` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    /// Returns `Error::InvalidAmount` if the cap amount or window is not positive.
+    pub fn set_benefit_cap(env: Env, token: Address, cap: Option<BenefitCap>) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(
+            &env,
+            "set_benefit_cap",
+            &admin,
+            None,
+            (token.clone(), cap.clone()),
+        );
+
+        if cap.as_ref().is_some_and(|c| c.amount <= 0 || c.window == 0) {
+            return Err(Error::InvalidAmount);
+        }
+        benefit::set_cap(&env, &token, &cap);
+
+        BenefitCapSet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            token,
+            cap,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the benefit cap for `token`, if any.
+    pub fn get_benefit_cap(env: Env, token: Address) -> Option<BenefitCap> {
+        benefit::get_cap(&env, &token)
+    }
+
+    /// Applies the token's benefit cap to a package being paid out. Rejects
+    /// the payout, or returns what the recipient may still be paid, which is
+    /// `package.amount` when the cap is not reached.
+    fn apply_benefit_cap(env: &Env, package: &Package, package_id: u64, now: u64) -> i128 {
+        let Some(cap) = benefit::get_cap(env, &package.token) else {
+            return package.amount;
+        };
+        let room = benefit::room(env, &cap, &package.recipient, &package.token);
+        if package.amount <= room {
+            return package.amount;
+        }
+        if cap.mode == CapMode::Reject || room <= 0 {
+            panic_with_error!(env, PolicyError::BenefitCapExceeded);
+        }
+
+        benefit::set_paid(env, package_id, room);
+        BenefitCapApplied {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id,
+            recipient: package.recipient.clone(),
+            token: package.token.clone(),
+            requested: package.amount,
+            paid: room,
+            withheld: package.amount - room,
+            timestamp: now,
+        }
+        .publish(env);
+        room
+    }
+
+    /// Rejects a redemption of `amount` that would take the recipient over
+    /// the token's benefit cap; redemptions are never shortened.
+    #[cfg(feature = "merchants")]
+    fn check_benefit_room(env: &Env, package: &Package, amount: i128) {
+        if let Some(cap) = benefit::get_cap(env, &package.token) {
+            if amount > benefit::room(env, &cap, &package.recipient, &package.token) {
+                panic_with_error!(env, PolicyError::BenefitCapExceeded);
+            }
+        }
+    }
+
+    /// Adds `amount` paid to `recipient` to their running totals.
+    fn record_benefit(env: &Env, recipient: &Address, token: &Address, amount: i128) {
+        benefit::record(env, recipient, token, amount);
+    }
+
+    /// Amount a claimed package actually paid out.
+    fn paid(env: &Env, id: u64, package: &Package) -> i128 {
+        benefit::get_paid(env, id, package)
+    }
+}
+
+/// Without benefit caps, the core paths call these no-ops instead.
+#[cfg(not(feature = "benefit-caps"))]
+impl AidEscrow {
+    fn apply_benefit_cap(_env: &Env, package: &Package, _package_id: u64, _now: u64) -> i128 {
+        package.amount
+    }
+
+    #[cfg(feature = "merchants")]
+    fn check_benefit_room(_env: &Env, _package: &Package, _amount: i128) {}
+
+    fn record_benefit(_env: &Env, _recipient: &Address, _token: &Address, _amount: i128) {}
+
+    fn paid(_env: &Env, _id: u64, package: &Package) -> i128 {
+        package.amount
+    }
+}
+
+#[cfg(feature = "denylist")]
+#[contractimpl]
+impl AidEscrow {
+    fn require_compliance_operator(env: &Env, operator: &Address) -> Result<(), Error> {
+        operator.require_auth();
+
+        let admin = Self::get_admin(env.clone())?;
+        if *operator == admin || roles::has_role(env, Role::Compliance, operator) {
+            Ok(())
+        } else {
+            Err(Error::NotAuthorized)
+        }
+    }
+
+    /// Admin or `Compliance` role. Denies `address` with a `reason` code, or
+    /// updates the reason if it is already denied. The address's packages are
+    /// left as they are; they cannot be claimed while it is denied.
+    /// Emits a `DenylistEntryAdded` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if `operator` is neither the admin nor a compliance officer.
+    pub fn add_to_denylist(
+        env: Env,
+        operator: Address,
+        address: Address,
+        reason: u32,
+    ) -> Result<(), Error> {
+        Self::require_compliance_operator(&env, &operator)?;
+        audit::record(
+            &env,
+            "add_to_denylist",
+            &operator,
+            None,
+            (operator.clone(), address.clone(), reason),
+        );
+
+        let entry = denylist::add(&env, &address, reason, &operator);
+
+        DenylistEntryAdded {
+            schema_version: EVENT_SCHEMA_VERSION,
+            address,
+            reason,
+            operator,
+            timestamp: entry.added_at,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin or `Compliance` role. Removes `address` from the denylist.
+    /// Emits a `DenylistEntryRemoved` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if `operator` is neither the admin nor a compliance officer.
+    /// Returns `Error::InvalidState` if `address` is not denied.
+    pub fn remove_from_denylist(
+        env: Env,
+        operator: Address,
+        address: Address,
+    ) -> Result<(), Error> {
+        Self::require_compliance_operator(&env, &operator)?;
+        audit::record(
+            &env,
+            "remove_from_denylist",
+            &operator,
+            None,
+            (operator.clone(), address.clone()),
+        );
+
+        let entry = denylist::remove(&env, &address).ok_or(Error::InvalidState)?;
+
+        DenylistEntryRemoved {
+            schema_version: EVENT_SCHEMA_VERSION,
+            address,
+            reason: entry.reason,
+            operator,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns `true` if `address` is denylisted.
+    pub fn is_denied(env: Env, address: Address) -> bool {
+        denylist::is_denied(&env, &address)
+    }
+
+    /// Returns the denylist entry of `address`, if any.
+    pub fn get_denylist_entry(env: Env, address: Address) -> Option<DenyEntry> {
+        denylist::get(&env, &address)
+    }
+
+    /// Gets up to `limit` denylist entries starting at position `cursor`.
+    /// `limit` is capped at 50; see `get_denylist_count` for the total.
+    pub fn get_denylist(env: Env, cursor: u32, limit: u32) -> Vec<DenyEntry> {
+        denylist::page(&env, cursor, limit)
+    }
+
+    /// Returns the number of denylisted addresses.
+    pub fn get_denylist_count(env: Env) -> u32 {
+        denylist::count(&env)
+    }
+
+    /// Rejects creation, payout or withdrawal involving a denylisted address.
+    fn check_not_denied(env: &Env, address: &Address) {
+        if denylist::is_denied(env, address) {
+            panic_with_error!(env, PolicyError::AddressDenied);
+        }
+    }
+}
+
+/// Without the denylist, the core paths call these no-ops instead.
+#[cfg(not(feature = "denylist"))]
+impl AidEscrow {
+    fn check_not_denied(_env: &Env, _address: &Address) {}
+}
+
+#[cfg(feature = "registry")]
+#[contractimpl]
+impl AidEscrow {
+    /// Admin-only. Sets the issuer contract asked `is_eligible(recipient,
+    /// campaign) -> bool` before packages in `campaign_ref` are paid out, or
+    /// removes it when `issuer` is `None`.
+    /// Emits an `EligibilityIssuerSet` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    pub fn set_eligibility_issuer(
+        env: Env,
+        campaign_ref: String,
+        issuer: Option<Address>,
+    ) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(
+            &env,
+            "set_eligibility_issuer",
+            &admin,
+            None,
+            (campaign_ref.clone(), issuer.clone()),
+        );
+
+        eligibility::set_issuer(&env, &campaign_ref, &issuer);
+
+        EligibilityIssuerSet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            campaign: campaign_ref,
+            issuer,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the eligibility issuer of `campaign_ref`, if any.
+    pub fn get_eligibility_issuer(env: Env, campaign_ref: String) -> Option<Address> {
+        eligibility::get_issuer(&env, &campaign_ref)
+    }
+
+    /// Asks the campaign issuer whether the recipient of package `id` is still
+    /// eligible, as a payout would. Returns `true` when the package's campaign
+    /// has no issuer. Emits `EligibilityCheckFailed` when the check fails,
+    /// which a rejected payout cannot do.
+    ///
+    /// # Errors
+    /// Returns `Error::PackageNotFound` if the package does not exist.
+    pub fn check_eligibility(env: Env, id: u64) -> Result<bool, Error> {
+        let package = Self::get_package(env.clone(), id)?;
+        let Some(campaign) = terms::campaign(&env, &package) else {
+            return Ok(true);
+        };
+        let Some(issuer) = eligibility::get_issuer(&env, &campaign) else {
+            return Ok(true);
+        };
+        if eligibility::is_eligible(&env, &issuer, &package.recipient, &campaign) {
+            return Ok(true);
+        }
+
+        EligibilityCheckFailed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id: id,
+            recipient: package.recipient,
+            campaign,
+            issuer,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(false)
+    }
+
+    /// Admin-only. Caps the packages and total amount each recipient may be
+    /// allocated in `campaign_ref`, or removes the cap when `policy` is `None`.
+    /// Existing enrolments are kept and count against the new cap.
+    /// Emits an `EnrolmentPolicySet` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    /// Returns `Error::InvalidAmount` if `max_amount` is negative.
+    pub fn set_enrolment_policy(
+        env: Env,
+        campaign_ref: String,
+        policy: Option<EnrolmentPolicy>,
+    ) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(
+            &env,
+            "set_enrolment_policy",
+            &admin,
+            None,
+            (campaign_ref.clone(), policy.clone()),
+        );
+
+        if policy.as_ref().is_some_and(|p| p.max_amount < 0) {
+            return Err(Error::InvalidAmount);
+        }
+        enrolment::set_policy(&env, &campaign_ref, &policy);
+
+        EnrolmentPolicySet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            campaign: campaign_ref,
+            policy,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the enrolment limits of `campaign_ref`, if any.
+    pub fn get_enrolment_policy(env: Env, campaign_ref: String) -> Option<EnrolmentPolicy> {
+        enrolment::get_policy(&env, &campaign_ref)
+    }
+
+    /// Returns the packages and amount `recipient` is allocated in `campaign_ref`.
+    pub fn get_enrolment(env: Env, campaign_ref: String, recipient: Address) -> Enrolment {
+        enrolment::get(&env, &campaign_ref, &recipient)
+    }
+
+    /// Admin-only. Links the beneficiary registry consulted when packages are
+    /// created, or unlinks it when `policy` is `None`.
+    /// Emits a `BeneficiaryRegistrySet` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    pub fn set_beneficiary_registry(
+        env: Env,
+        policy: Option<BeneficiaryPolicy>,
+    ) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(
+            &env,
+            "set_beneficiary_registry",
+            &admin,
+            None,
+            policy.clone(),
+        );
+
+        beneficiary::set_policy(&env, &policy);
+
+        BeneficiaryRegistrySet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            policy,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the linked beneficiary registry policy, if any.
+    pub fn get_beneficiary_registry(env: Env) -> Option<BeneficiaryPolicy> {
+        beneficiary::get_policy(&env)
+    }
+
+    /// Returns the package the beneficiary with `identity_hash` was last
+    /// enrolled in within campaign `campaign_ref`, if any.
+    pub fn get_identity_package(
+        env: Env,
+        campaign_ref: String,
+        identity_hash: BytesN<32>,
+    ) -> Option<u64> {
+        beneficiary::get_campaign_package(&env, &campaign_ref, &identity_hash)
+    }
+
+    /// Counts a new package of `amount` for `recipient` against the beneficiary
+    /// registry and the campaign's enrolment limits.
+    fn enrol(
+        env: &Env,
+        package_id: u64,
+        recipient: &Address,
+        campaign: &Option<String>,
+        amount: i128,
+    ) {
+        if let Some(policy) = beneficiary::get_policy(env) {
+            beneficiary::enroll(env, &policy, package_id, recipient, campaign);
+        }
+        if let Some(campaign) = campaign {
+            enrolment::enroll(env, campaign, recipient, amount);
+        }
+    }
+
+    /// Rejects the payout of a package whose recipient the campaign's
+    /// eligibility issuer does not confirm.
+    fn check_eligible(env: &Env, package: &Package, package_terms: &PackageTerms) {
+        let Some(campaign) = &package_terms.campaign_id else {
+            return;
+        };
+        if let Some(issuer) = eligibility::get_issuer(env, campaign) {
+            if !eligibility::is_eligible(env, &issuer, &package.recipient, campaign) {
+                panic_with_error!(env, PolicyError::NotEligible);
+            }
+        }
+    }
+
+    /// Gives back the campaign enrolment of a package that is being cancelled
+    /// or refunded with `outstanding` still unpaid.
+    fn release_enrolment(env: &Env, package: &Package, outstanding: i128) {
+        if let Some(campaign) = terms::campaign(env, package) {
+            enrolment::release(
+                env,
+                &campaign,
+                &package.recipient,
+                outstanding,
+                outstanding == package.amount,
+            );
+        }
+    }
+
+    /// Moves the campaign enrolment and the beneficiary identity slot of a
+    /// `Created` package whose campaign or recipient changed from `from` to
+    /// `to`, checking the limits of `to`.
+    #[cfg(any(feature = "amendments", feature = "recovery"))]
+    fn move_enrolment(
+        env: &Env,
+        package: &Package,
+        from: (&Option<String>, &Address),
+        to: (&Option<String>, &Address),
+    ) {
+        if from == to {
+            return;
+        }
+        if let (Some(campaign), recipient) = from {
+            enrolment::release(env, campaign, recipient, package.amount, true);
+        }
+        if let (Some(campaign), recipient) = to {
+            enrolment::enroll(env, campaign, recipient, package.amount);
+        }
+        if let Some(policy) = beneficiary::get_policy(env) {
+            if from.1 == to.1 {
+                beneficiary::reassign(env, &policy, package.id, to.1, from.0, to.0);
+            } else {
+                beneficiary::reassign_recipient(env, &policy, package.id, from.1, to.1, to.0);
+            }
+        }
+    }
+}
+
+/// Without the registry checks, the core paths call these no-ops instead.
+#[cfg(not(feature = "registry"))]
+impl AidEscrow {
+    fn check_eligible(_env: &Env, _package: &Package, _package_terms: &PackageTerms) {}
+
+    fn enrol(
+        _env: &Env,
+        _package_id: u64,
+        _recipient: &Address,
+        _campaign: &Option<String>,
+        _amount: i128,
+    ) {
+    }
+
+    fn release_enrolment(_env: &Env, _package: &Package, _outstanding: i128) {}
+
+    #[cfg(any(feature = "amendments", feature = "recovery"))]
+    fn move_enrolment(
+        _env: &Env,
+        _package: &Package,
+        _from: (&Option<String>, &Address),
+        _to: (&Option<String>, &Address),
+    ) {
+    }
+}
+
+#[cfg(feature = "vouchers")]
+#[contractimpl]
+impl AidEscrow {
+    /// Admin/distributor-only. Registers (or rotates) the ed25519 public key used to
+    /// verify claim vouchers signed by `distributor`.
+    /// Emits a `DistributorKeySet` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if `distributor` is neither the admin nor a distributor.
+    pub fn set_distributor_key(
+        env: Env,
+        distributor: Address,
+        public_key: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_admin_or_distributor(&env, &distributor)?;
+        audit::record(
+            &env,
+            "set_distributor_key",
+            &distributor,
+            None,
+            (distributor.clone(), public_key.clone()),
+        );
+
+        voucher::set_signing_key(&env, &distributor, &public_key);
+
+        DistributorKeySet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            distributor,
+            public_key,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the voucher signing key registered by `distributor`, if any.
+    pub fn get_distributor_key(env: Env, distributor: Address) -> Option<BytesN<32>> {
+        voucher::get_signing_key(&env, &distributor)
+    }
+
+    /// Claim a package by redeeming a distributor-signed voucher.
+    ///
+    /// The voucher must be signed with the ed25519 key the distributor registered
+    /// via `set_distributor_key`, over the bytes returned by `voucher_payload`.
+    /// The `claimant` must be the package recipient or an authorized delegate and
+    /// must sign the transaction. Each `(distributor, nonce)` pair is single-use.
+    ///
+    /// Merkle-allowlist packages cannot be claimed through this path; use
+    /// `claim_with_proof` instead. An invalid signature aborts the invocation.
+    ///
+    /// # Errors
+    /// - `Error::VoucherExpired` - `voucher.expires_at` is in the past
+    /// - `Error::NotAuthorized` - signer is no longer admin/distributor, or claimant is not authorized
+    /// - `Error::SigningKeyNotRegistered` - signer has no registered public key
+    /// - `Error::VoucherAlreadyUsed` - nonce already redeemed for this distributor
+    pub fn claim_with_voucher(
+        env: Env,
+        voucher: ClaimVoucher,
+        signature: BytesN<64>,
+    ) -> Result<(), Error> {
+        let id = voucher.package_id;
+        let key = (symbol_short!("pkg"), id);
+        let (mut package, now) = Self::load_claimable(&env, id, ClaimRoute::Address)?;
+
+        if now > voucher.expires_at {
+            return Err(Error::VoucherExpired);
+        }
+
+        if !Self::is_admin_or_distributor(&env, &voucher.distributor)? {
+            return Err(Error::NotAuthorized);
+        }
+
+        let public_key = voucher::get_signing_key(&env, &voucher.distributor)
+            .ok_or(Error::SigningKeyNotRegistered)?;
+
+        if voucher::is_nonce_used(&env, &voucher.distributor, voucher.nonce) {
+            return Err(Error::VoucherAlreadyUsed);
+        }
+
+        let claimant = voucher.claimant.clone();
+        delegate::check_claimer(&env, id, &package.recipient, &claimant)?;
+
+        claimant.require_auth();
+        voucher::verify(&env, &voucher, &public_key, &signature);
+        voucher::mark_nonce_used(&env, &voucher.distributor, voucher.nonce);
+
+        VoucherRedeemed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id: id,
+            distributor: voucher.distributor.clone(),
+            claimant: claimant.clone(),
+            nonce: voucher.nonce,
+            timestamp: now,
+        }
+        .publish(&env);
+
+        Self::settle(
+            &env,
+            &key,
+            &mut package,
+            id,
+            Settlement::Claim(claimant.clone()),
+            now,
+        )
+    }
+
+    /// Returns the exact bytes a distributor must sign to issue `voucher`.
+    pub fn voucher_payload(env: Env, voucher: ClaimVoucher) -> Bytes {
+        voucher::payload(&env, &voucher)
+    }
+
+    /// Returns `true` if `nonce` has already been redeemed for `distributor`.
+    pub fn is_voucher_nonce_used(env: Env, distributor: Address, nonce: u64) -> bool {
+        voucher::is_nonce_used(&env, &distributor, nonce)
+    }
+}
+
+#[cfg(feature = "hash-locks")]
+#[contractimpl]
+impl AidEscrow {
+    /// Creates a package that is claimable by whoever knows the preimage of `claim_hash`.
+    ///
+    /// Behaves like `create_package`, but instead of a recipient address the package
+    /// stores `claim_hash = sha256(secret)`. Until claimed, `Package.recipient` holds the
+    /// escrow contract address as a placeholder; it is replaced by the payout address
+    /// when the package is claimed via `claim_with_secret`.
+    ///
+    /// Emits `PackageCreated` and `HashLockedPackageCreated`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_hashlocked_package(
+        env: Env,
+        operator: Address,
+        id: u64,
+        claim_hash: BytesN<32>,
+        amount: i128,
+        token: Address,
+        expires_at: u64,
+        metadata: Map<Symbol, String>,
+    ) -> Result<u64, Error> {
+        let placeholder = env.current_contract_address();
+        let id = Self::create_package(
+            env.clone(),
+            operator.clone(),
+            id,
+            placeholder,
+            amount,
+            token,
+            expires_at,
+            metadata,
+        )?;
+
+        hashlock::set_claim_hash(&env, id, &claim_hash);
+
+        HashLockedPackageCreated {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id: id,
+            claim_hash,
+            actor: operator,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(id)
+    }
+
+    /// First step of a hash-locked claim: `payout_address` commits to
+    /// `sha256(secret || payout_address_xdr)` without revealing the secret.
+    ///
+    /// The commitment can be revealed with `claim_with_secret` in any later ledger.
+    /// Committing again replaces the previous commitment for the same payout address.
+    ///
+    /// # Errors
+    /// - `Error::PackageNotFound` - Package doesn't exist
+    /// - `Error::PackageNotActive` - Package is not in `Created` status
+    /// - `Error::InvalidState` - Package is not hash-locked
+    pub fn commit_secret_claim(
+        env: Env,
+        id: u64,
+        payout_address: Address,
+        commitment: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::check_action_paused(&env, symbol_short!("claim"))?;
+        let package = Self::get_package(env.clone(), id)?;
+
+        if package.status != PackageStatus::Created {
+            return Err(Error::PackageNotActive);
+        }
+
+        if !Self::is_hash_locked(&env, id) {
+            return Err(Error::InvalidState);
+        }
+
+        payout_address.require_auth();
+        audit::record(
+            &env,
+            "commit_secret_claim",
+            &payout_address,
+            Some(id),
+            (id, payout_address.clone(), commitment.clone()),
+        );
+        hashlock::set_commitment(&env, id, &payout_address, &commitment);
+
+        SecretClaimCommitted {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id: id,
+            payout_address,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Second step of a hash-locked claim: reveals `secret` and pays `payout_address`.
+    ///
+    /// `sha256(secret)` must equal the package claim hash, and `payout_address` must
+    /// have committed to this secret in an earlier ledger via `commit_secret_claim`,
+    /// so an observer cannot front-run the reveal with a different payout address.
+    /// The package recipient is set to `payout_address` on success.
+    ///
+    /// # Errors
+    /// - `Error::InvalidState` - Package is not hash-locked
+    /// - `Error::InvalidSecret` - Secret or commitment does not match
+    /// - `Error::CommitmentNotFound` - `payout_address` has not committed
+    /// - `Error::CommitmentTooRecent` - Commitment was made in the current ledger
+    /// - `PolicyError::EnrolmentLimitReached` - `payout_address` would exceed the campaign's
+    ///   enrolment limits
+    pub fn claim_with_secret(
+        env: Env,
+        id: u64,
+        secret: Bytes,
+        payout_address: Address,
+    ) -> Result<(), Error> {
+        let key = (symbol_short!("pkg"), id);
+        let (mut package, now) = Self::load_claimable(&env, id, ClaimRoute::Own)?;
+
+        let claim_hash = hashlock::get_claim_hash(&env, id).ok_or(Error::InvalidState)?;
+        if hashlock::hash_secret(&env, &secret) != claim_hash {
+            return Err(Error::InvalidSecret);
+        }
+
+        let commitment =
+            hashlock::get_commitment(&env, id, &payout_address).ok_or(Error::CommitmentNotFound)?;
+        if commitment.ledger >= env.ledger().sequence() {
+            return Err(Error::CommitmentTooRecent);
+        }
+        if hashlock::commitment_for(&env, &secret, &payout_address) != commitment.commitment {
+            return Err(Error::InvalidSecret);
+        }
+
+        payout_address.require_auth();
+        hashlock::remove_commitment(&env, id, &payout_address);
+
+        package.recipient = payout_address.clone();
+        let campaign = terms::campaign(&env, &package);
+        Self::enrol(&env, id, &payout_address, &campaign, package.amount);
+        Self::settle(
+            &env,
+            &key,
+            &mut package,
+            id,
+            Settlement::Claim(payout_address),
+            now,
+        )
+    }
+
+    /// Returns the claim hash of a hash-locked package, or `None` for regular packages.
+    pub fn get_claim_hash(env: Env, id: u64) -> Option<BytesN<32>> {
+        hashlock::get_claim_hash(&env, id)
+    }
+
+    /// Returns `true` if package `id` is claimable only with its secret.
+    fn is_hash_locked(env: &Env, id: u64) -> bool {
+        hashlock::is_hash_locked(env, id)
+    }
+}
+
+/// Without hash-locked packages, the core paths call these no-ops instead.
+#[cfg(not(feature = "hash-locks"))]
+impl AidEscrow {
+    fn is_hash_locked(_env: &Env, _id: u64) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "receipts")]
+#[contractimpl]
+impl AidEscrow {
+    /// Confirms what was received for a paid-out package. Callable once per
+    /// package by the recipient, the delegate that collected it, or a standing
+    /// delegate of the recipient. `rating` ranges from 1 to 5.
+    /// Emits a `ReceiptAcknowledged` event.
+    ///
+    /// # Errors
+    /// - `Error::InvalidState` - The package has not been claimed or disbursed
+    /// - `Error::NotAuthorized` - `caller` is not the recipient or a delegate
+    /// - `Error::InvalidRating` - `rating` is outside 1..=5
+    /// - `Error::AlreadyAcknowledged` - The package was already acknowledged
+    pub fn acknowledge_receipt(
+        env: Env,
+        caller: Address,
+        id: u64,
+        receipt_hash: String,
+        rating: u32,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let package = Self::get_package(env.clone(), id)?;
+        if package.status != PackageStatus::Claimed {
+            return Err(Error::InvalidState);
+        }
+        if caller != package.recipient
+            && receipt::get_claimant(&env, id).as_ref() != Some(&caller)
+            && !delegate::is_standing_delegate(&env, id, &caller)
+        {
+            return Err(Error::NotAuthorized);
+        }
+        audit::record(
+            &env,
+            "acknowledge_receipt",
+            &caller,
+            Some(id),
+            (caller.clone(), id, receipt_hash.clone(), rating),
+        );
+
+        if rating == 0 || rating > receipt::MAX_RATING {
+            return Err(Error::InvalidRating);
+        }
+        if receipt::get(&env, id).is_some() {
+            return Err(Error::AlreadyAcknowledged);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        receipt::save(
+            &env,
+            id,
+            &ReceiptAck {
+                by: caller.clone(),
+                receipt_hash: receipt_hash.clone(),
+                rating,
+                timestamp,
+            },
+        );
+
+        ReceiptAcknowledged {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id: id,
+            by: caller,
+            receipt_hash,
+            rating,
+            timestamp,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the receipt acknowledgement of a package, if any.
+    pub fn get_receipt_ack(env: Env, id: u64) -> Option<ReceiptAck> {
+        receipt::get(&env, id)
+    }
+
+    /// Returns receipt acknowledgement counts for campaign `campaign_ref`:
+    /// paid-out packages, how many of them were acknowledged, and the sum of
+    /// their ratings. Read-only full scan, like `get_campaign_claim_count`.
+    pub fn get_campaign_ack_stats(env: Env, campaign_ref: String) -> AckStats {
+        let count: u64 = env.storage().instance().get(&KEY_PKG_COUNTER).unwrap_or(0);
+        let mut stats = AckStats::default();
+
+        for id in 0..count {
+            let key = (symbol_short!("pkg"), id);
+            if let Some(package) = env.storage().persistent().get::<_, Package>(&key) {
+                if package.status == PackageStatus::Claimed
+                    && terms::campaign(&env, &package).as_ref() == Some(&campaign_ref)
+                {
+                    stats.claimed += 1;
+                    match receipt::get(&env, id) {
+                        Some(ack) => {
+                            stats.acknowledged += 1;
+                            stats.rating_total += ack.rating as u64;
+                        }
+                        None => stats.unacknowledged += 1,
+                    }
+                }
+            }
+        }
+
+        stats
+    }
+
+    /// Remembers who collected a package, so that a delegate may acknowledge
+    /// its receipt.
+    fn record_claimant(env: &Env, package_id: u64, claimant: &Address, recipient: &Address) {
+        receipt::set_claimant(env, package_id, claimant, recipient);
+    }
+}
+
+/// Without receipt acknowledgements, the core paths call these no-ops instead.
+#[cfg(not(feature = "receipts"))]
+impl AidEscrow {
+    fn record_claimant(_env: &Env, _package_id: u64, _claimant: &Address, _recipient: &Address) {}
+}
+
+#[cfg(feature = "metadata-rules")]
+#[contractimpl]
+impl AidEscrow {
+    /// Admin-only. Replaces the rules applied to the metadata of new packages.
+    /// Emits a `MetadataRulesSet` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    pub fn set_metadata_rules(env: Env, rules: MetadataRules) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(&env, "set_metadata_rules", &admin, None, rules.clone());
+
+        metadata::set_rules(&env, &rules);
+
+        MetadataRulesSet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            allowed_keys: rules.allowed_keys,
+            max_keys: rules.max_keys,
+            max_value_len: rules.max_value_len,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the current metadata rules.
+    pub fn get_metadata_rules(env: Env) -> MetadataRules {
+        metadata::get_rules(&env)
+    }
+
+    /// Admin-only. Sets the metadata keys every new package in `campaign` must
+    /// carry; an empty list clears the requirement.
+    /// Emits a `RequiredMetadataSet` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    pub fn set_required_metadata(
+        env: Env,
+        campaign: String,
+        keys: Vec<Symbol>,
+    ) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(
+            &env,
+            "set_required_metadata",
+            &admin,
+            None,
+            (campaign.clone(), keys.clone()),
+        );
+
+        metadata::set_required(&env, &campaign, &keys);
+
+        RequiredMetadataSet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            campaign,
+            keys,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the metadata keys required for packages in `campaign`.
+    pub fn get_required_metadata(env: Env, campaign: String) -> Vec<Symbol> {
+        metadata::get_required(&env, &campaign)
+    }
+
+    /// Checks `metadata` against the metadata rules and the keys `campaign`
+    /// requires.
+    fn check_metadata(
+        env: &Env,
+        metadata: &Map<Symbol, String>,
+        campaign: &Option<String>,
+    ) -> Result<(), Error> {
+        metadata::check(env, &metadata::get_rules(env), metadata, campaign)
+    }
+}
+
+/// Without metadata rules, the core paths call these no-ops instead.
+#[cfg(not(feature = "metadata-rules"))]
+impl AidEscrow {
+    fn check_metadata(
+        _env: &Env,
+        _metadata: &Map<Symbol, String>,
+        _campaign: &Option<String>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "amendments")]
+#[contractimpl]
+impl AidEscrow {
+    /// Amends the metadata of a package: entries in `set` are added or
+    /// overwritten, keys in `remove` are deleted. Callable by the admin or by
+    /// the distributor that created the package.
+    ///
+    /// Keys that change behaviour (`claim_starts_at`, `merkle_root`,
+    /// `campaign_ref`) can only be amended while the package is `Created`, and
+    /// update the package terms; other keys can be amended in any status. The
+    /// result is checked against the metadata rules and the claim window, and
+    /// a package moved to another campaign against that campaign's enrolment
+    /// limits and identity deduplication.
+    /// Appends to the amendment log and emits `PackageMetadataUpdated`.
+    ///
+    /// # Errors
+    /// - `Error::NotAuthorized` - `operator` is neither the admin nor the creator
+    /// - `Error::InvalidState` - Nothing to change, or a key is both set and removed
+    /// - `Error::PackageNotActive` - A behaviour key is amended after `Created`
+    /// - `Error::MalformedTerm` / `Error::InvalidClaimWindow` - Invalid new terms
+    pub fn update_package_metadata(
+        env: Env,
+        operator: Address,
+        id: u64,
+        set: Map<Symbol, String>,
+        remove: Vec<Symbol>,
+    ) -> Result<(), Error> {
+        operator.require_auth();
+        let key = (symbol_short!("pkg"), id);
+        let mut package: Package = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::PackageNotFound)?;
+
+        let is_creator = amendment::get_creator(&env, id).as_ref() == Some(&operator)
+            && Self::is_admin_or_distributor(&env, &operator)?;
+        if operator != Self::get_admin(env.clone())? && !is_creator {
+            return Err(Error::NotAuthorized);
+        }
+        audit::record(
+            &env,
+            "update_package_metadata",
+            &operator,
+            Some(id),
+            (operator.clone(), id, set.clone(), remove.clone()),
+        );
+
+        if set.is_empty() && remove.is_empty() {
+            return Err(Error::InvalidState);
+        }
+
+        let mut package_terms = terms::get(&env, &package);
+        let old_campaign = package_terms.campaign_id.clone();
+        let mut changes_behaviour = false;
+        let mut set_keys = Vec::new(&env);
+        for (k, v) in set.iter() {
+            if remove.contains(&k) {
+                return Err(Error::InvalidState);
+            }
+            changes_behaviour |= terms::is_behaviour_key(&env, &k);
+            terms::amend(&env, &mut package_terms, &k, Some(v.clone()))?;
+            package.metadata.set(k.clone(), v);
+            set_keys.push_back(k);
+        }
+        for k in remove.iter() {
+            changes_behaviour |= terms::is_behaviour_key(&env, &k);
+            terms::amend(&env, &mut package_terms, &k, None)?;
+            package.metadata.remove(k);
+        }
+
+        if changes_behaviour && package.status != PackageStatus::Created {
+            return Err(Error::PackageNotActive);
+        }
+
+        package.claim_starts_at =
+            terms::claim_start(&package_terms, package.created_at, package.expires_at)?;
+        Self::check_metadata(&env, &package.metadata, &package_terms.campaign_id)?;
+
+        env.storage().persistent().set(&key, &package);
+        terms::replace(&env, id, &package_terms);
+        Self::move_enrolment(
+            &env,
+            &package,
+            (&old_campaign, &package.recipient),
+            (&package_terms.campaign_id, &package.recipient),
+        );
+
+        let now = env.ledger().timestamp();
+        let index = amendment::append(
+            &env,
+            id,
+            MetadataAmendment {
+                actor: operator.clone(),
+                timestamp: now,
+                set,
+                removed: remove.clone(),
+            },
+        );
+
+        PackageMetadataUpdated {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id: id,
+            actor: operator,
+            set_keys,
+            removed_keys: remove,
+            amendment: index,
+            timestamp: now,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the metadata amendment log of a package, oldest first.
+    pub fn get_metadata_amendments(env: Env, id: u64) -> Vec<MetadataAmendment> {
+        amendment::get_log(&env, id)
+    }
+
+    /// Remembers the distributor that created a package, who may amend its
+    /// metadata later.
+    fn record_creator(env: &Env, package_id: u64, creator: &Address, admin: &Address) {
+        amendment::set_creator(env, package_id, creator, admin);
+    }
+}
+
+/// Without metadata amendments, the core paths call these no-ops instead.
+#[cfg(not(feature = "amendments"))]
+impl AidEscrow {
+    fn record_creator(_env: &Env, _package_id: u64, _creator: &Address, _admin: &Address) {}
+}
+
+#[cfg(feature = "multi-delegates")]
+#[contractimpl]
+impl AidEscrow {
+    /// Adds `delegate` to the package's delegate set, keeping existing delegates.
+    /// Re-adding a current delegate only updates its expiration.
+    /// Only the admin or a delegate manager can call this.
+    /// Emits a `DelegateAdded` event.
+    ///
+    /// # Arguments
+    /// * `operator` - Admin or delegate-manager address (must be authenticated)
+    /// * `package_id` - Package ID to add the delegate to
+    /// * `delegate` - Delegate address
+    /// * `expires_at` - Expiration timestamp (0 = no expiration)
+    ///
+    /// # Errors
+    /// - `Error::NotAuthorized` - Caller is neither the admin nor a delegate manager
+    /// - `Error::PackageNotFound` - Package doesn't exist
+    /// - `Error::PackageNotActive` - Package already claimed
+    /// - `Error::InvalidState` - Invalid delegate address or expiration
+    /// - `Error::DelegateLimitReached` - Package already has the maximum number of delegates
+    pub fn add_delegate(
+        env: Env,
+        operator: Address,
+        package_id: u64,
+        delegate: Address,
+        expires_at: u64,
+    ) -> Result<(), Error> {
+        Self::require_delegate_manager(&env, &operator)?;
+        audit::record(
+            &env,
+            "add_delegate",
+            &operator,
+            Some(package_id),
+            (operator.clone(), package_id, delegate.clone(), expires_at),
+        );
+
+        delegate::add_delegate(
+            &env,
+            &operator,
+            package_id,
+            &delegate,
+            expires_at,
+            Symbol::new(&env, "delegate_added"),
+        )?;
+
+        let package = Self::get_package(env.clone(), package_id)?;
+        DelegateAdded {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id,
+            recipient: package.recipient,
+            delegate,
+            actor: operator,
+            expires_at,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Removes a single delegate from the package's delegate set.
+    /// Emits a `DelegateRevoked` event.
+    ///
+    /// # Arguments
+    /// * `caller` - Admin, delegate manager, or the package recipient (must be authenticated)
+    /// * `package_id` - Package ID to remove the delegate from
+    /// * `delegate` - Delegate to remove
+    ///
+    /// # Errors
+    /// - `Error::PackageNotFound` - Package doesn't exist
+    /// - `Error::NotAuthorized` - Caller may not manage this package's delegates
+    /// - `Error::DelegateNotFound` - `delegate` is not in the set
+    pub fn remove_delegate(
+        env: Env,
+        caller: Address,
+        package_id: u64,
+        delegate: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        audit::record(
+            &env,
+            "remove_delegate",
+            &caller,
+            Some(package_id),
+            (caller.clone(), package_id, delegate.clone()),
+        );
+
+        let package = Self::get_package(env.clone(), package_id)?;
+        if caller != package.recipient && !Self::is_delegate_manager(&env, &caller)? {
+            return Err(Error::NotAuthorized);
+        }
+
+        delegate::remove_delegate(&env, &caller, package_id, &delegate)?;
+
+        DelegateRevoked {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id,
+            recipient: package.recipient,
+            delegate,
+            actor: caller,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Requires `threshold` distinct delegates to co-sign delegate-initiated
+    /// claims of the package via `claim_with_delegates` (0 or 1 = any single
+    /// delegate may claim). Recipient claims are unaffected.
+    /// Only the admin or a delegate manager can call this.
+    /// Emits a `DelegateThresholdSet` event.
+    ///
+    /// # Errors
+    /// - `Error::NotAuthorized` - Caller is neither the admin nor a delegate manager
+    /// - `Error::PackageNotFound` - Package doesn't exist
+    /// - `Error::PackageNotActive` - Package already claimed
+    /// - `Error::InvalidState` - Threshold exceeds the per-package delegate limit
+    pub fn set_delegate_threshold(
+        env: Env,
+        operator: Address,
+        package_id: u64,
+        threshold: u32,
+    ) -> Result<(), Error> {
+        Self::require_delegate_manager(&env, &operator)?;
+        audit::record(
+            &env,
+            "set_delegate_threshold",
+            &operator,
+            Some(package_id),
+            (operator.clone(), package_id, threshold),
+        );
+
+        delegate::set_threshold(&env, package_id, threshold)?;
+
+        DelegateThresholdSet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id,
+            threshold: delegate::get_threshold(&env, package_id),
+            actor: operator,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Gets the number of delegates that must co-sign a delegate-initiated claim (at least 1).
+    pub fn get_delegate_threshold(env: Env, package_id: u64) -> u32 {
+        crate::delegate::get_threshold(&env, package_id)
+    }
+
+    /// Claim a package jointly as several of its delegates.
+    ///
+    /// Every address in `signers` must be a distinct, active delegate of the
+    /// package and must sign the transaction; at least `get_delegate_threshold`
+    /// signers are required. The payout goes to `signers[0]`.
+    ///
+    /// Merkle-allowlist packages cannot be claimed through this path; use
+    /// `claim_with_proof` instead.
+    ///
+    /// # Errors
+    /// - `Error::NotAuthorized` - empty, duplicate or non-delegate signer
+    /// - `Error::DelegateThresholdNotMet` - fewer signers than the package threshold
+    pub fn claim_with_delegates(env: Env, id: u64, signers: Vec<Address>) -> Result<(), Error> {
+        let key = (symbol_short!("pkg"), id);
+        let (mut package, now) = Self::load_claimable(&env, id, ClaimRoute::Address)?;
+
+        delegate::check_cosigners(&env, id, &signers)?;
+        for signer in signers.iter() {
+            signer.require_auth();
+        }
+
+        DelegateClaimCosigned {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id: id,
+            recipient: package.recipient.clone(),
+            signers: signers.clone(),
+            threshold: delegate::get_threshold(&env, id),
+            timestamp: now,
+        }
+        .publish(&env);
+
+        let collector = signers.get(0).unwrap();
+        Self::settle(
+            &env,
+            &key,
+            &mut package,
+            id,
+            Settlement::Claim(collector.clone()),
+            now,
+        )
+    }
+}
+
+#[cfg(feature = "standing-delegates")]
+#[contractimpl]
+impl AidEscrow {
+    /// Grants `delegate` a standing delegation covering every current and
+    /// future package of `recipient` within `scope` (all packages, one token,
+    /// or one campaign). Re-granting replaces the delegate's scope and expiry.
+    /// Package-level delegates take precedence; packages that require co-signed
+    /// delegate claims do not accept a standing delegate alone.
+    /// Emits a `StandingDelegateGranted` event.
+    ///
+    /// # Arguments
+    /// * `caller` - The recipient, the admin or a delegate manager (must be authenticated)
+    /// * `expires_at` - Expiration timestamp (0 = no expiration)
+    ///
+    /// # Errors
+    /// - `Error::NotAuthorized` - Caller may not manage `recipient`'s delegations
+    /// - `Error::InvalidState` - Delegate is the recipient, or expiration is in the past
+    /// - `Error::DelegateLimitReached` - Recipient already has the maximum number of standing delegates
+    pub fn grant_standing_delegate(
+        env: Env,
+        caller: Address,
+        recipient: Address,
+        delegate: Address,
+        scope: DelegationScope,
+        expires_at: u64,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        audit::record(
+            &env,
+            "grant_standing_delegate",
+            &caller,
+            None,
+            (
+                caller.clone(),
+                recipient.clone(),
+                delegate.clone(),
+                scope.clone(),
+                expires_at,
+            ),
+        );
+        if caller != recipient && !Self::is_delegate_manager(&env, &caller)? {
+            return Err(Error::NotAuthorized);
+        }
+
+        standing::grant(
+            &env,
+            &caller,
+            &recipient,
+            &delegate,
+            scope.clone(),
+            expires_at,
+        )?;
+
+        StandingDelegateGranted {
+            schema_version: EVENT_SCHEMA_VERSION,
+            recipient,
+            delegate,
+            scope,
+            expires_at,
+            actor: caller,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Revokes the standing delegation of `delegate` for `recipient`.
+    /// The delegate may also renounce its own delegation.
+    /// Emits a `StandingDelegateRevoked` event.
+    ///
+    /// # Errors
+    /// - `Error::NotAuthorized` - Caller is not the recipient, the delegate, the admin or a delegate manager
+    /// - `Error::DelegateNotFound` - No such standing delegation
+    pub fn revoke_standing_delegate(
+        env: Env,
+        caller: Address,
+        recipient: Address,
+        delegate: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        audit::record(
+            &env,
+            "revoke_standing_delegate",
+            &caller,
+            None,
+            (caller.clone(), recipient.clone(), delegate.clone()),
+        );
+        if caller != recipient && caller != delegate && !Self::is_delegate_manager(&env, &caller)? {
+            return Err(Error::NotAuthorized);
+        }
+
+        standing::revoke(&env, &recipient, &delegate)?;
+
+        StandingDelegateRevoked {
+            schema_version: EVENT_SCHEMA_VERSION,
+            recipient,
+            delegate,
+            actor: caller,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Gets every standing delegation of `recipient`, including expired ones.
+    pub fn get_standing_delegates(env: Env, recipient: Address) -> Vec<StandingDelegation> {
+        standing::get_all(&env, &recipient)
+    }
+
+    /// Emits `StandingDelegationUsed` and returns `true` if `claimant` claimed
+    /// `package` under a standing delegation of its recipient.
+    fn record_standing_claim(
+        env: &Env,
+        package: &Package,
+        package_id: u64,
+        claimant: &Address,
+        now: u64,
+    ) -> bool {
+        if standing::find_for_package(env, package, claimant).is_none() {
+            return false;
+        }
+        StandingDelegationUsed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id,
+            recipient: package.recipient.clone(),
+            delegate: claimant.clone(),
+            timestamp: now,
+        }
+        .publish(env);
+        true
+    }
+}
+
+/// Without standing delegations, the core paths call these no-ops instead.
+#[cfg(not(feature = "standing-delegates"))]
+impl AidEscrow {
+    fn record_standing_claim(
+        _env: &Env,
+        _package: &Package,
+        _package_id: u64,
+        _claimant: &Address,
+        _now: u64,
+    ) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "payout-policies")]
+#[contractimpl]
+impl AidEscrow {
+    /// Sets how delegate-initiated claims of a package are paid out:
+    /// `ToDelegate` (default), `ToRecipient`, or `Split(bps)` with `bps`
+    /// going to the delegate. Policies set on a delegation take precedence.
+    /// Only the admin or a delegate manager can call this.
+    /// Emits a `PayoutPolicySet` event.
+    ///
+    /// # Errors
+    /// - `Error::NotAuthorized` - Caller is neither the admin nor a delegate manager
+    /// - `Error::PackageNotFound` - Package doesn't exist
+    /// - `Error::PackageNotActive` - Package is no longer `Created`
+    /// - `Error::InvalidPayoutPolicy` - Split share above 10 000 bps
+    pub fn set_payout_policy(
+        env: Env,
+        operator: Address,
+        package_id: u64,
+        policy: PayoutPolicy,
+    ) -> Result<(), Error> {
+        Self::require_delegate_manager(&env, &operator)?;
+        audit::record(
+            &env,
+            "set_payout_policy",
+            &operator,
+            Some(package_id),
+            (operator.clone(), package_id, policy.clone()),
+        );
+        payout::validate(&policy)?;
+
+        let package = Self::get_package(env.clone(), package_id)?;
+        if package.status != PackageStatus::Created {
+            return Err(Error::PackageNotActive);
+        }
+        payout::set_package_policy(&env, package_id, &policy);
+
+        PayoutPolicySet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id,
+            delegate: None,
+            policy: Some(policy),
+            actor: operator,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Gets the package-level payout policy (`ToDelegate` when unset).
+    pub fn get_payout_policy(env: Env, package_id: u64) -> PayoutPolicy {
+        payout::get_package_policy(&env, package_id)
+    }
+
+    /// Gets the payout policy set on one package delegate, if any.
+    pub fn get_delegate_payout_policy(
+        env: Env,
+        package_id: u64,
+        delegate: Address,
+    ) -> Option<PayoutPolicy> {
+        delegate::get_entry_payout(&env, package_id, &delegate)
+    }
+
+    /// Sets or clears (`None`) the payout policy of one package delegate,
+    /// overriding the package-level policy for that delegate's claims.
+    /// Only the admin or a delegate manager can call this.
+    /// Emits a `PayoutPolicySet` event.
+    ///
+    /// # Errors
+    /// - `Error::NotAuthorized` - Caller is neither the admin nor a delegate manager
+    /// - `Error::PackageNotFound` / `Error::PackageNotActive` - Package missing or claimed
+    /// - `Error::DelegateNotFound` - `delegate` is not in the package's delegate set
+    /// - `Error::InvalidPayoutPolicy` - Split share above 10 000 bps
+    pub fn set_delegate_payout_policy(
+        env: Env,
+        operator: Address,
+        package_id: u64,
+        delegate: Address,
+        policy: Option<PayoutPolicy>,
+    ) -> Result<(), Error> {
+        Self::require_delegate_manager(&env, &operator)?;
+        audit::record(
+            &env,
+            "set_delegate_payout_policy",
+            &operator,
+            Some(package_id),
+            (
+                operator.clone(),
+                package_id,
+                delegate.clone(),
+                policy.clone(),
+            ),
+        );
+        if let Some(policy) = &policy {
+            payout::validate(policy)?;
+        }
+
+        delegate::set_entry_payout(&env, package_id, &delegate, policy.clone())?;
+
+        PayoutPolicySet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id,
+            delegate: Some(delegate),
+            policy,
+            actor: operator,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "nominations")]
+#[contractimpl]
+impl AidEscrow {
+    /// Recipient-only. Nominates `delegate` to claim `package_id` on the recipient's behalf.
+    /// The nomination only becomes active once the nominee calls `accept_delegation`;
+    /// a new nomination replaces any pending one. Emits a `DelegateNominated` event.
+    ///
+    /// # Arguments
+    /// * `package_id` - Package ID owned by the calling recipient
+    /// * `delegate` - Nominee address
+    /// * `expires_at` - Expiration of the delegation once accepted (0 = no expiration)
+    ///
+    /// # Errors
+    /// - `Error::PackageNotFound` - Package doesn't exist
+    /// - `Error::PackageNotActive` - Package already claimed
+    /// - `Error::HashLockedPackage` - Package has no recipient address yet
+    /// - `Error::InvalidState` - Nominee is the recipient, or expiration is in the past
+    pub fn nominate_delegate(
+        env: Env,
+        package_id: u64,
+        delegate: Address,
+        expires_at: u64,
+    ) -> Result<(), Error> {
+        let package = Self::get_package(env.clone(), package_id)?;
+        if Self::is_hash_locked(&env, package_id) {
+            return Err(Error::HashLockedPackage);
+        }
+
+        package.recipient.require_auth();
+        audit::record(
+            &env,
+            "nominate_delegate",
+            &package.recipient,
+            Some(package_id),
+            (package_id, delegate.clone(), expires_at),
+        );
+        delegate::nominate(&env, &package.recipient, package_id, &delegate, expires_at)?;
+
+        DelegateNominated {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id,
+            recipient: package.recipient,
+            delegate,
+            expires_at,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Nominee-only. Accepts a pending nomination, making `delegate` the active delegate.
+    /// Emits a `DelegateAdded` event with the delegate as actor.
+    ///
+    /// # Errors
+    /// - `Error::NoPendingNomination` - No nomination for `delegate` is pending
+    /// - `Error::PackageNotActive` - Package already claimed
+    /// - `Error::InvalidState` - The nominated delegation has already expired
+    pub fn accept_delegation(env: Env, package_id: u64, delegate: Address) -> Result<(), Error> {
+        let package = Self::get_package(env.clone(), package_id)?;
+
+        delegate.require_auth();
+        audit::record(
+            &env,
+            "accept_delegation",
+            &delegate,
+            Some(package_id),
+            (package_id, delegate.clone()),
+        );
+        let nomination = delegate::accept_nomination(&env, package_id, &delegate)?;
+
+        DelegateAdded {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id,
+            recipient: package.recipient,
+            delegate: delegate.clone(),
+            actor: delegate,
+            expires_at: nomination.expires_at,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Nominee-only. Declines a pending nomination.
+    /// Emits a `DelegateNominationDiscarded` event.
+    ///
+    /// # Errors
+    /// - `Error::NoPendingNomination` - No nomination for `delegate` is pending
+    pub fn reject_delegation(env: Env, package_id: u64, delegate: Address) -> Result<(), Error> {
+        let package = Self::get_package(env.clone(), package_id)?;
+        match delegate::get_nomination(&env, package_id) {
+            Some(n) if n.delegate == delegate => {}
+            _ => return Err(Error::NoPendingNomination),
+        }
+
+        delegate.require_auth();
+        audit::record(
+            &env,
+            "reject_delegation",
+            &delegate,
+            Some(package_id),
+            (package_id, delegate.clone()),
+        );
+        let reason = Symbol::new(&env, "nomination_rejected");
+        delegate::discard_nomination(&env, package_id, &delegate, reason.clone())?;
+
+        DelegateNominationDiscarded {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id,
+            recipient: package.recipient,
+            delegate: delegate.clone(),
+            actor: delegate,
+            reason,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Recipient-only. Withdraws a pending nomination before it is accepted.
+    /// Emits a `DelegateNominationDiscarded` event.
+    ///
+    /// # Errors
+    /// - `Error::NoPendingNomination` - No nomination is pending
+    pub fn cancel_delegate_nomination(env: Env, package_id: u64) -> Result<(), Error> {
+        let package = Self::get_package(env.clone(), package_id)?;
+
+        package.recipient.require_auth();
+        audit::record(
+            &env,
+            "cancel_delegate_nomination",
+            &package.recipient,
+            Some(package_id),
+            package_id,
+        );
+        let reason = Symbol::new(&env, "nomination_cancelled");
+        let nomination =
+            delegate::discard_nomination(&env, package_id, &package.recipient, reason.clone())?;
+
+        DelegateNominationDiscarded {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id,
+            recipient: package.recipient.clone(),
+            delegate: nomination.delegate,
+            actor: package.recipient,
+            reason,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the pending (not yet accepted) delegate nomination for a package.
+    pub fn get_delegate_nomination(
+        env: Env,
+        package_id: u64,
+    ) -> Option<crate::delegate::DelegateNomination> {
+        crate::delegate::get_nomination(&env, package_id)
+    }
+}
+
+#[cfg(all(feature = "standing-delegates", feature = "payout-policies"))]
+#[contractimpl]
+impl AidEscrow {
+    /// Sets or clears (`None`) the payout policy of a standing delegation,
+    /// overriding package-level policies for claims made under it.
+    /// Emits a `PayoutPolicySet` event with `package_id` 0.
+    ///
+    /// # Errors
+    /// - `Error::NotAuthorized` - Caller is not the recipient, the admin or a delegate manager
+    /// - `Error::DelegateNotFound` - No such standing delegation
+    /// - `Error::InvalidPayoutPolicy` - Split share above 10 000 bps
+    pub fn set_standing_payout_policy(
+        env: Env,
+        caller: Address,
+        recipient: Address,
+        delegate: Address,
+        policy: Option<PayoutPolicy>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        audit::record(
+            &env,
+            "set_standing_payout_policy",
+            &caller,
+            None,
+            (
+                caller.clone(),
+                recipient.clone(),
+                delegate.clone(),
+                policy.clone(),
+            ),
+        );
+        if caller != recipient && !Self::is_delegate_manager(&env, &caller)? {
+            return Err(Error::NotAuthorized);
+        }
+        if let Some(policy) = &policy {
+            payout::validate(policy)?;
+        }
+
+        standing::set_payout(&env, &recipient, &delegate, policy.clone())?;
+
+        PayoutPolicySet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            package_id: 0,
+            delegate: Some(delegate),
+            policy,
+            actor: caller,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "relayers")]
+#[contractimpl]
+impl AidEscrow {
+    /// Claim a package through a relayer who pays the transaction costs.
+    ///
+    /// The `claimant` must be the package recipient or an authorized delegate.
+    /// Both the `claimant` and the `relayer` must have signed the transaction.
+    /// The relayer's address is recorded in the event for off-chain identification.
+    ///
+    /// Registered relayers that are disabled are rejected. Registered, enabled
+    /// relayers are paid the fee configured for the package token (see
+    /// `set_relayer_fee`), either out of the payout or from the pool surplus;
+    /// a surplus-sourced fee is limited to the surplus available.
+    ///
+    /// Merkle-allowlist packages cannot be claimed through this path; use
+    /// `claim_with_proof` instead.
+    pub fn claim_with_relayer(
+        env: Env,
+        id: u64,
+        claimant: Address,
+        relayer: Address,
+    ) -> Result<(), Error> {
+        let key = (symbol_short!("pkg"), id);
+        let (mut package, now) = Self::load_claimable(&env, id, ClaimRoute::Address)?;
+
+        delegate::check_claimer(&env, id, &package.recipient, &claimant)?;
+
+        if relayer::get(&env, &relayer).is_some_and(|info| !info.enabled) {
+            return Err(Error::RelayerDisabled);
+        }
+
+        claimant.require_auth();
+        relayer.require_auth();
+
+        Self::settle(
+            &env,
+            &key,
+            &mut package,
+            id,
+            Settlement::Relayed { claimant, relayer },
+            now,
+        )
+    }
+
+    /// Admin-only. Registers `relayer` as an enabled relayer eligible for fees.
+    /// Emits a `RelayerRegistered` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    /// Returns `Error::InvalidState` if the relayer is already registered.
+    pub fn register_relayer(env: Env, relayer: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(&env, "register_relayer", &admin, None, relayer.clone());
+
+        if relayer::get(&env, &relayer).is_some() {
+            return Err(Error::InvalidState);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        relayer::save(
+            &env,
+            &relayer,
+            &RelayerInfo {
+                enabled: true,
+                registered_at: timestamp,
+                claims_relayed: 0,
+            },
+        );
+
+        RelayerRegistered {
+            schema_version: EVENT_SCHEMA_VERSION,
+            relayer,
+            admin,
+            timestamp,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin-only. Enables or disables a registered relayer. A disabled relayer
+    /// cannot submit `claim_with_relayer`; its stats are kept.
+    /// Emits a `RelayerStatusChanged` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    /// Returns `Error::RelayerNotRegistered` if the relayer is not registered.
+    pub fn set_relayer_enabled(env: Env, relayer: Address, enabled: bool) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(
+            &env,
+            "set_relayer_enabled",
+            &admin,
+            None,
+            (relayer.clone(), enabled),
+        );
+
+        let mut info = relayer::get(&env, &relayer).ok_or(Error::RelayerNotRegistered)?;
+        info.enabled = enabled;
+        relayer::save(&env, &relayer, &info);
+
+        RelayerStatusChanged {
+            schema_version: EVENT_SCHEMA_VERSION,
+            relayer,
+            enabled,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the registry entry (status and claim count) of `relayer`, if registered.
+    pub fn get_relayer(env: Env, relayer: Address) -> Option<RelayerInfo> {
+        relayer::get(&env, &relayer)
+    }
+
+    /// Returns the total fees `relayer` has been paid in `token`.
+    pub fn get_relayer_fees_earned(env: Env, relayer: Address, token: Address) -> i128 {
+        relayer::get_earned(&env, &relayer, &token)
+    }
+
+    /// Admin-only. Sets the fee paid to registered relayers for claims of
+    /// packages in `token`, or removes it when `fee` is `None`.
+    /// Emits a `RelayerFeeSet` event.
+    ///
+    /// # Errors
+    /// Returns `Error::NotAuthorized` if caller is not the admin.
+    /// Returns `Error::InvalidRelayerFee` for a negative amount or cap, or a share above 10 000 bps.
+    pub fn set_relayer_fee(env: Env, token: Address, fee: Option<RelayerFee>) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        audit::record(
+            &env,
+            "set_relayer_fee",
+            &admin,
+            None,
+            (token.clone(), fee.clone()),
+        );
+
+        match &fee {
+            Some(fee) => {
+                relayer::validate_fee(fee)?;
+                relayer::set_fee(&env, &token, fee);
+            }
+            None => relayer::remove_fee(&env, &token),
+        }
+
+        RelayerFeeSet {
+            schema_version: EVENT_SCHEMA_VERSION,
+            token,
+            fee,
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Returns the relayer fee configured for `token`, if any.
+    pub fn get_relayer_fee(env: Env, token: Address) -> Option<RelayerFee> {
+        relayer::get_fee(&env, &token)
+    }
+
+    /// Fee owed to `relayer` for relaying a claim of `package` paying out
+    /// `payable`, and where it is paid from. Unregistered or disabled relayers
+    /// are owed nothing; a surplus-sourced fee is limited to the surplus available.
+    fn relayer_fee(
+        env: &Env,
+        package: &Package,
+        relayer: &Address,
+        payable: i128,
+    ) -> Result<(i128, RelayerFeeSource), Error> {
+        let enabled = relayer::get(env, relayer).is_some_and(|info| info.enabled);
+        let fee = match relayer::get_fee(env, &package.token) {
+            Some(fee) if enabled => fee,
+            _ => return Ok((0, RelayerFeeSource::Payout)),
+        };
+
+        let mut value = relayer::compute(&fee, payable);
+        if fee.source == RelayerFeeSource::Surplus {
+            value = value.min(Self::available_surplus(env, &package.token)?.max(0));
+        }
+        Ok((value, fee.source))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,118 @@
+//! Package status transitions.
+//!
+//! Every status change goes through `transition`, which only permits the
+//! moves listed in `TRANSITIONS`:
+//!
+//! | From        | To          | Via                                                   |
+//! | ----------- | ----------- | ----------------------------------------------------- |
+//! | `Created`   | `Claimed`   | every claim path, `claim_with_relayer`, `disburse`    |
+//! | `Created`   | `Cancelled` | `revoke`, `cancel_package`                            |
+//! | `Created`   | `Expired`   | `refund` of a package past its expiry                 |
+//! | `Expired`   | `Refunded`  | `refund`                                              |
+//! | `Cancelled` | `Refunded`  | `refund`                                              |
+//!
+//! `Claimed` and `Refunded` are terminal.  Entry points still perform their
+//! own status checks first so they keep returning their documented errors;
+//! the table is the single source of truth for what is reachable.
+//!
+//! Each transition, and each creation (`from` = `None`), emits one
+//! `PackageStatusChanged` event naming the reason, so indexers can rebuild
+//! every package's status from that stream alone, and appends the change to
+//! the audit hash chain, bound to the package as it now stands.
+
+use soroban_sdk::{Address, Env, Symbol};
+
+use crate::{Error, Package, PackageStatus, PackageStatusChanged, EVENT_SCHEMA_VERSION};
+
+/// Allowed `(from, to)` status transitions.
+pub const TRANSITIONS: [(PackageStatus, PackageStatus); 5] = [
+    (PackageStatus::Created, PackageStatus::Claimed),
+    (PackageStatus::Created, PackageStatus::Cancelled),
+    (PackageStatus::Created, PackageStatus::Expired),
+    (PackageStatus::Expired, PackageStatus::Refunded),
+    (PackageStatus::Cancelled, PackageStatus::Refunded),
+];
+
+/// Returns `true` if a package may move from `from` to `to`.
+pub fn is_allowed(from: PackageStatus, to: PackageStatus) -> bool {
+    TRANSITIONS.iter().any(|&(f, t)| f == from && t == to)
+}
+
+/// Moves `package` to `to` and emits `PackageStatusChanged`, returning the
+/// previous status.
+///
+/// # Errors
+/// - `Error::InvalidState` - The transition is not in `TRANSITIONS`
+pub fn transition(
+    env: &Env,
+    package: &mut Package,
+    to: PackageStatus,
+    reason: Symbol,
+    actor: &Address,
+) -> Result<PackageStatus, Error> {
+    let from = package.status;
+    if !is_allowed(from, to) {
+        return Err(Error::InvalidState);
+    }
+    package.status = to;
+    publish(env, package, Some(from), reason, actor);
+    Ok(from)
+}
+
+/// Emits `PackageStatusChanged` for a newly created package.
+pub fn created(env: &Env, package: &Package, actor: &Address) {
+    publish(env, package, None, Symbol::new(env, "created"), actor);
+}
+
+fn publish(
+    env: &Env,
+    package: &Package,
+    from: Option<PackageStatus>,
+    reason: Symbol,
+    actor: &Address,
+) {
+    crate::audit::record_symbol(
+        env,
+        reason.clone(),
+        actor,
+        Some(package.id),
+        package.clone(),
+    );
+
+    PackageStatusChanged {
+        package_id: package.id,
+        from,
+        to: package.status,
+        reason,
+        actor: actor.clone(),
+        timestamp: env.ledger().timestamp(),
+        schema_version: EVENT_SCHEMA_VERSION,
+    }
+    .publish(env);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terminal_states_have_no_exits() {
+        let all = [
+            PackageStatus::Created,
+            PackageStatus::Claimed,
+            PackageStatus::Expired,
+            PackageStatus::Cancelled,
+            PackageStatus::Refunded,
+        ];
+        for to in all {
+            assert!(!is_allowed(PackageStatus::Claimed, to));
+            assert!(!is_allowed(PackageStatus::Refunded, to));
+        }
+        assert!(!is_allowed(PackageStatus::Expired, PackageStatus::Claimed));
+        assert!(!is_allowed(
+            PackageStatus::Cancelled,
+            PackageStatus::Created
+        ));
+        assert!(is_allowed(PackageStatus::Created, PackageStatus::Claimed));
+    }
+}
@@ -0,0 +1,111 @@
+//! Merchant registry and restricted-spend redemptions.
+//!
+//! Packages created with `PackageTerms::restricted` cannot be paid out as
+//! cash.  Instead the recipient authorizes `redeem`, which pays part or all of
+//! the package straight from the escrow to a registered, unsuspended merchant.
+//! The package stays `Created` until it is fully redeemed; revoking, cancelling
+//! or refunding it only touches the outstanding part.
+//!
+//! A package may also limit the spending categories it can be redeemed for.
+//! Each redemption is counted under one category: the first of the
+//! merchant's tags the package allows, so a merchant's primary category should
+//! be tagged first.  Spend is aggregated per campaign, token and category.
+//!
+//! Storage: `("mrch", merchant)` -> `MerchantInfo`, `("mrchtot", merchant,
+//! token)` -> total settled to the merchant, `("redeemed", id)` -> amount
+//! redeemed from the package so far, `("catspend", campaign, token)` ->
+//! `Map<Symbol, i128>` of spend per category.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, String, Symbol, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MerchantInfo {
+    pub suspended: bool,
+    /// Category tags, e.g. `food`, `pharmacy`.
+    pub categories: Vec<Symbol>,
+    pub registered_at: u64,
+}
+
+/// Returns the registry entry for `merchant`, if registered.
+pub fn get(env: &Env, merchant: &Address) -> Option<MerchantInfo> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("mrch"), merchant.clone()))
+}
+
+/// Stores the registry entry for `merchant`.
+pub fn save(env: &Env, merchant: &Address, info: &MerchantInfo) {
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("mrch"), merchant.clone()), info);
+}
+
+/// Returns the total `merchant` has been paid in `token` through redemptions.
+pub fn get_settled(env: &Env, merchant: &Address, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("mrchtot"), merchant.clone(), token.clone()))
+        .unwrap_or(0)
+}
+
+/// Adds a payment to the settlement total of `merchant` in `token`.
+pub fn add_settled(env: &Env, merchant: &Address, token: &Address, amount: i128) {
+    let total = get_settled(env, merchant, token) + amount;
+    env.storage().persistent().set(
+        &(symbol_short!("mrchtot"), merchant.clone(), token.clone()),
+        &total,
+    );
+}
+
+/// Returns the amount redeemed from a package so far.
+pub fn get_redeemed(env: &Env, package_id: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("redeemed"), package_id))
+        .unwrap_or(0)
+}
+
+/// Adds a redemption to the running total of a package.
+pub fn add_redeemed(env: &Env, package_id: u64, amount: i128) {
+    let total = get_redeemed(env, package_id) + amount;
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("redeemed"), package_id), &total);
+}
+
+/// Returns the category a redemption at `info` is counted under: the first
+/// merchant tag in `allowed` (any tag when `allowed` is `None`).  `None` when
+/// the merchant has no acceptable tag.
+pub fn spend_category(info: &MerchantInfo, allowed: &Option<Vec<Symbol>>) -> Option<Symbol> {
+    info.categories
+        .iter()
+        .find(|tag| allowed.as_ref().is_none_or(|allowed| allowed.contains(tag)))
+}
+
+/// Returns the spend per category of `campaign` in `token`.
+pub fn get_category_spend(env: &Env, campaign: &String, token: &Address) -> Map<Symbol, i128> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("catspend"), campaign.clone(), token.clone()))
+        .unwrap_or(Map::new(env))
+}
+
+/// Adds a redemption to the spend of `category` in `campaign`.
+pub fn add_category_spend(
+    env: &Env,
+    campaign: &String,
+    token: &Address,
+    category: &Symbol,
+    amount: i128,
+) {
+    let mut spend = get_category_spend(env, campaign, token);
+    spend.set(
+        category.clone(),
+        spend.get(category.clone()).unwrap_or(0) + amount,
+    );
+    env.storage().persistent().set(
+        &(symbol_short!("catspend"), campaign.clone(), token.clone()),
+        &spend,
+    );
+}
@@ -0,0 +1,107 @@
+//! Metadata schema rules.
+//!
+//! The admin can restrict the free-form metadata attached to packages: an
+//! allowed key set, a maximum number of keys, a maximum value length, and per
+//! campaign a set of keys every package in that campaign must carry.  Rules
+//! are checked when packages are created, single or batch; packages that
+//! already exist are not revalidated.  Typed `PackageTerms` are not metadata
+//! and are not subject to these rules, but legacy term keys are.
+//!
+//! Storage: instance `"metarules"` -> `MetadataRules`, `("metareq", campaign)`
+//! -> `Vec<Symbol>` of required keys.
+
+use soroban_sdk::{contracttype, symbol_short, Env, Map, String, Symbol, Vec};
+
+use crate::Error;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetadataRules {
+    /// Keys packages may use (empty = any key).
+    pub allowed_keys: Vec<Symbol>,
+    /// Maximum number of entries per package (0 = unlimited).
+    pub max_keys: u32,
+    /// Maximum value length in bytes (0 = unlimited).
+    pub max_value_len: u32,
+}
+
+impl MetadataRules {
+    /// Rules that accept any metadata.
+    pub fn unrestricted(env: &Env) -> Self {
+        Self {
+            allowed_keys: Vec::new(env),
+            max_keys: 0,
+            max_value_len: 0,
+        }
+    }
+}
+
+/// Returns the current rules (unrestricted if never set).
+pub fn get_rules(env: &Env) -> MetadataRules {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("metarules"))
+        .unwrap_or(MetadataRules::unrestricted(env))
+}
+
+/// Replaces the rules.
+pub fn set_rules(env: &Env, rules: &MetadataRules) {
+    env.storage()
+        .instance()
+        .set(&symbol_short!("metarules"), rules);
+}
+
+/// Returns the keys required for packages in `campaign` (empty if none).
+pub fn get_required(env: &Env, campaign: &String) -> Vec<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("metareq"), campaign.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Sets the keys required for packages in `campaign`; an empty list clears it.
+pub fn set_required(env: &Env, campaign: &String, keys: &Vec<Symbol>) {
+    let key = (symbol_short!("metareq"), campaign.clone());
+    if keys.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, keys);
+    }
+}
+
+/// Checks `metadata` of a new package in `campaign` against the rules.
+///
+/// # Errors
+/// - `Error::MetadataTooManyKeys` - More entries than `max_keys`
+/// - `Error::MetadataKeyNotAllowed` - A key outside `allowed_keys`
+/// - `Error::MetadataValueTooLong` - A value longer than `max_value_len`
+/// - `Error::MissingRequiredMetadata` - A key required by the campaign is absent
+pub fn check(
+    env: &Env,
+    rules: &MetadataRules,
+    metadata: &Map<Symbol, String>,
+    campaign: &Option<String>,
+) -> Result<(), Error> {
+    if rules.max_keys > 0 && metadata.len() > rules.max_keys {
+        return Err(Error::MetadataTooManyKeys);
+    }
+
+    for (key, value) in metadata.iter() {
+        if !rules.allowed_keys.is_empty() && !rules.allowed_keys.contains(&key) {
+            return Err(Error::MetadataKeyNotAllowed);
+        }
+        if rules.max_value_len > 0 && value.len() > rules.max_value_len {
+            return Err(Error::MetadataValueTooLong);
+        }
+    }
+
+    if let Some(campaign) = campaign {
+        for key in get_required(env, campaign).iter() {
+            if !metadata.contains_key(key) {
+                return Err(Error::MissingRequiredMetadata);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+//! Payout policies for delegate-initiated claims.
+//!
+//! When a delegate claims, the policy decides where the funds land: with
+//! the delegate (the historical behaviour), with the package recipient, or
+//! split between the two.  A policy attached to the delegation itself (a
+//! package delegate under `("dpay", package_id, delegate)` or a standing
+//! delegation under `("sdpay", recipient, delegate)`) wins over the package
+//! policy stored under `("payout", package_id)`, which wins over the default
+//! `ToDelegate`.  Claims by the recipient, or by a Merkle-allowlisted
+//! claimant that is not a delegate, are always paid in full to the claimant.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env};
+
+#[cfg(feature = "payout-policies")]
+use crate::Error;
+use crate::Package;
+
+/// Basis-point denominator for `PayoutPolicy::Split`.
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PayoutPolicy {
+    /// The claiming delegate receives the full amount.
+    ToDelegate,
+    /// The package recipient receives the full amount; the delegate only triggers the claim.
+    ToRecipient,
+    /// The delegate receives this many basis points; the recipient receives the rest.
+    Split(u32),
+}
+
+/// Amounts paid out by one claim.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PayoutSplit {
+    pub to_recipient: i128,
+    pub to_delegate: i128,
+    /// `None` when the claimant was not acting as a delegate.
+    pub policy: Option<PayoutPolicy>,
+}
+
+/// Rejects split shares above 100%.
+#[cfg(feature = "payout-policies")]
+pub fn validate(policy: &PayoutPolicy) -> Result<(), Error> {
+    match policy {
+        PayoutPolicy::Split(bps) if *bps > BPS_DENOMINATOR => Err(Error::InvalidPayoutPolicy),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the package-level policy, or `ToDelegate` if none is set.
+pub fn get_package_policy(env: &Env, package_id: u64) -> PayoutPolicy {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("payout"), package_id))
+        .unwrap_or(PayoutPolicy::ToDelegate)
+}
+
+/// Sets the package-level policy.
+#[cfg(feature = "payout-policies")]
+pub fn set_package_policy(env: &Env, package_id: u64, policy: &PayoutPolicy) {
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("payout"), package_id), policy);
+}
+
+/// Returns the policy governing a claim of `package` by `claimant`, or
+/// `None` if the claimant is the recipient or not a delegate of the package.
+pub fn resolve(
+    env: &Env,
+    package_id: u64,
+    package: &Package,
+    claimant: &Address,
+) -> Option<PayoutPolicy> {
+    if claimant == &package.recipient {
+        return None;
+    }
+
+    let delegation_policy = if crate::delegate::is_active_delegate(env, package_id, claimant) {
+        crate::delegate::get_entry_payout(env, package_id, claimant)
+    } else if let Some(policy) = standing_policy(env, package, claimant) {
+        policy
+    } else {
+        return None;
+    };
+
+    Some(delegation_policy.unwrap_or_else(|| get_package_policy(env, package_id)))
+}
+
+/// Returns the policy of the standing delegation letting `claimant` act for
+/// the recipient of `package`: `None` without such a delegation, `Some(None)`
+/// when the delegation has no policy of its own.
+#[cfg(feature = "standing-delegates")]
+fn standing_policy(
+    env: &Env,
+    package: &Package,
+    claimant: &Address,
+) -> Option<Option<PayoutPolicy>> {
+    crate::standing::find_for_package(env, package, claimant)
+        .map(|_| crate::standing::get_payout(env, &package.recipient, claimant))
+}
+
+#[cfg(not(feature = "standing-delegates"))]
+fn standing_policy(
+    _env: &Env,
+    _package: &Package,
+    _claimant: &Address,
+) -> Option<Option<PayoutPolicy>> {
+    None
+}
+
+/// Splits `amount` between recipient and delegate according to `policy`.
+pub fn split(amount: i128, policy: Option<PayoutPolicy>) -> PayoutSplit {
+    let to_delegate = match &policy {
+        None | Some(PayoutPolicy::ToRecipient) => 0,
+        Some(PayoutPolicy::ToDelegate) => amount,
+        Some(PayoutPolicy::Split(bps)) => amount * (*bps as i128) / (BPS_DENOMINATOR as i128),
+    };
+    PayoutSplit {
+        to_recipient: amount - to_delegate,
+        to_delegate,
+        policy,
+    }
+}
@@ -0,0 +1,66 @@
+//! Proof-of-receipt acknowledgements.
+//!
+//! Once a package is paid out (claimed or disbursed) its recipient, the
+//! delegate that collected it, or a standing delegate of the recipient can
+//! confirm what was actually received with a receipt hash and a rating.  Each
+//! package takes one acknowledgement.
+//!
+//! Storage: `("ack", id)` -> `ReceiptAck`, `("claimby", id)` -> the address
+//! that collected the package, kept only when it is not the recipient.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String};
+
+/// Highest accepted `ReceiptAck::rating`; ratings start at 1.
+pub const MAX_RATING: u32 = 5;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReceiptAck {
+    pub by: Address,
+    pub receipt_hash: String,
+    /// 1 (worst) to `MAX_RATING` (best).
+    pub rating: u32,
+    pub timestamp: u64,
+}
+
+/// Acknowledgement counts for one campaign.
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AckStats {
+    /// Packages paid out (`Claimed`).
+    pub claimed: u64,
+    pub acknowledged: u64,
+    pub unacknowledged: u64,
+    /// Sum of ratings over acknowledged packages.
+    pub rating_total: u64,
+}
+
+/// Returns the acknowledgement of a package, if any.
+pub fn get(env: &Env, package_id: u64) -> Option<ReceiptAck> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("ack"), package_id))
+}
+
+/// Stores the acknowledgement of a package.
+pub fn save(env: &Env, package_id: u64, ack: &ReceiptAck) {
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("ack"), package_id), ack);
+}
+
+/// Records who collected a package when it was not the recipient.
+pub fn set_claimant(env: &Env, package_id: u64, claimant: &Address, recipient: &Address) {
+    if claimant != recipient {
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("claimby"), package_id), claimant);
+    }
+}
+
+/// Returns the delegate that collected a package, if it was not the recipient.
+pub fn get_claimant(env: &Env, package_id: u64) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("claimby"), package_id))
+}
@@ -0,0 +1,149 @@
+//! Recipient address rotation and lost-wallet recovery.
+//!
+//! The admin or a `RecoveryAgent` proposes moving one package, or every
+//! open package of an address, to a new recipient.  The proposal only
+//! becomes executable after the configured waiting period, during which
+//! the original recipient key can object and block it.  Every executed
+//! rotation is appended to a per-package recipient history.
+//!
+//! An `AllOpen` recovery visits package ids in pages of at most
+//! `MAX_RECOVERY_PAGE`, so executing it never scans the whole package range in
+//! one call.  The request keeps its own cursor: the first page moves it from
+//! `Pending` to `Executing`, which can no longer be objected to or cancelled,
+//! and the page that reaches the last package id marks it `Executed`.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::Error;
+
+const KEY_RECOVERY_COUNTER: Symbol = symbol_short!("rcv_cnt");
+const KEY_RECOVERY_DELAY: Symbol = symbol_short!("rcv_dly");
+
+/// Default waiting period between proposal and execution (3 days).
+pub const DEFAULT_RECOVERY_DELAY: u64 = 3 * 24 * 60 * 60;
+
+/// Shortest waiting period the admin can configure (1 day), so the original
+/// recipient always has a chance to object.
+pub const MIN_RECOVERY_DELAY: u64 = 24 * 60 * 60;
+
+/// Maximum package ids visited by one `execute_recovery` call.
+pub const MAX_RECOVERY_PAGE: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum RecoveryScope {
+    /// Rotate a single package.
+    Package(u64),
+    /// Rotate every package of `old_recipient` still in `Created` status at execution time.
+    AllOpen,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u32)]
+pub enum RecoveryStatus {
+    Pending = 0,
+    Executed = 1,
+    Objected = 2,
+    Cancelled = 3,
+    /// Some pages of an `AllOpen` recovery have been executed.
+    Executing = 4,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecoveryRequest {
+    pub id: u64,
+    pub old_recipient: Address,
+    pub new_recipient: Address,
+    pub scope: RecoveryScope,
+    pub proposer: Address,
+    pub proposed_at: u64,
+    pub executable_at: u64,
+    pub status: RecoveryStatus,
+    /// Next package id an `AllOpen` execution visits.
+    pub cursor: u64,
+    /// Packages rotated so far.
+    pub rotated: u32,
+}
+
+/// Result of one [`AidEscrow::execute_recovery`](crate::AidEscrow::execute_recovery) call.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecoveryProgress {
+    /// Packages rotated by this call.
+    pub rotated: u32,
+    /// Next package id to visit.
+    pub next_cursor: u64,
+    /// `true` once every package in scope has been visited.
+    pub done: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecipientChange {
+    pub package_id: u64,
+    pub old_recipient: Address,
+    pub new_recipient: Address,
+    pub recovery_id: u64,
+    pub changed_by: Address,
+    pub changed_at: u64,
+}
+
+/// Returns the configured waiting period in seconds.
+pub fn get_delay(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&KEY_RECOVERY_DELAY)
+        .unwrap_or(DEFAULT_RECOVERY_DELAY)
+}
+
+/// Sets the waiting period in seconds.
+pub fn set_delay(env: &Env, seconds: u64) {
+    env.storage().instance().set(&KEY_RECOVERY_DELAY, &seconds);
+}
+
+/// Allocates the next recovery request id.
+pub fn next_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&KEY_RECOVERY_COUNTER)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&KEY_RECOVERY_COUNTER, &(id + 1));
+    id
+}
+
+/// Loads a recovery request.
+pub fn load(env: &Env, id: u64) -> Result<RecoveryRequest, Error> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("rcv"), id))
+        .ok_or(Error::RecoveryNotFound)
+}
+
+/// Persists a recovery request.
+pub fn save(env: &Env, request: &RecoveryRequest) {
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("rcv"), request.id), request);
+}
+
+/// Returns the recipient-change history of a package, oldest first.
+pub fn get_history(env: &Env, package_id: u64) -> Vec<RecipientChange> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("rcphist"), package_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Appends a recipient change to the package history.
+pub fn record_change(env: &Env, change: &RecipientChange) {
+    let mut history = get_history(env, change.package_id);
+    history.push_back(change.clone());
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("rcphist"), change.package_id), &history);
+}
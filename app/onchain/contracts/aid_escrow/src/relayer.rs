@@ -0,0 +1,142 @@
+//! Relayer registry and sponsored-fee reimbursement.
+//!
+//! The admin registers relayers and can disable them; a disabled relayer
+//! cannot submit `claim_with_relayer`.  Unregistered relayers may still
+//! relay claims but are never paid a fee.  Registered, enabled relayers
+//! receive the fee configured for the package token, either deducted from
+//! the payout or paid from the pool surplus, in the same transaction as
+//! the claim.
+//!
+//! Storage: `("rly", relayer)` -> `RelayerInfo`, `("rlyfee", token)` ->
+//! `RelayerFee`, `("rlyearn", relayer, token)` -> total fees earned.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env};
+
+use crate::Error;
+
+/// Basis-point denominator for `RelayerFeeKind::Bps`.
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum RelayerFeeKind {
+    /// Fixed amount in token base units.
+    Fixed(i128),
+    /// Basis points of the package amount.
+    Bps(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u32)]
+pub enum RelayerFeeSource {
+    /// Deducted from the package payout.
+    Payout = 0,
+    /// Paid from unallocated pool funds; the payout is untouched.
+    Surplus = 1,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelayerFee {
+    pub kind: RelayerFeeKind,
+    /// Maximum fee per claim in token base units (0 = uncapped).
+    pub cap: i128,
+    pub source: RelayerFeeSource,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelayerInfo {
+    pub enabled: bool,
+    pub registered_at: u64,
+    pub claims_relayed: u64,
+}
+
+/// Returns the registry entry for `relayer`, if registered.
+pub fn get(env: &Env, relayer: &Address) -> Option<RelayerInfo> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("rly"), relayer.clone()))
+}
+
+/// Stores the registry entry for `relayer`.
+pub fn save(env: &Env, relayer: &Address, info: &RelayerInfo) {
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("rly"), relayer.clone()), info);
+}
+
+/// Rejects negative amounts and shares above 100%.
+pub fn validate_fee(fee: &RelayerFee) -> Result<(), Error> {
+    let kind_ok = match fee.kind {
+        RelayerFeeKind::Fixed(amount) => amount >= 0,
+        RelayerFeeKind::Bps(bps) => bps <= BPS_DENOMINATOR,
+    };
+    if !kind_ok || fee.cap < 0 {
+        return Err(Error::InvalidRelayerFee);
+    }
+    Ok(())
+}
+
+/// Returns the fee configured for `token`, if any.
+pub fn get_fee(env: &Env, token: &Address) -> Option<RelayerFee> {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("rlyfee"), token.clone()))
+}
+
+/// Sets the fee for `token`.
+pub fn set_fee(env: &Env, token: &Address, fee: &RelayerFee) {
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("rlyfee"), token.clone()), fee);
+}
+
+/// Removes the fee for `token`.
+pub fn remove_fee(env: &Env, token: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&(symbol_short!("rlyfee"), token.clone()));
+}
+
+/// Computes the fee owed for relaying a claim of `amount`, before any
+/// surplus limit.  Payout-sourced fees never exceed the payout itself.
+pub fn compute(fee: &RelayerFee, amount: i128) -> i128 {
+    let mut value = match fee.kind {
+        RelayerFeeKind::Fixed(fixed) => fixed,
+        RelayerFeeKind::Bps(bps) => amount * (bps as i128) / (BPS_DENOMINATOR as i128),
+    };
+    if fee.cap > 0 {
+        value = value.min(fee.cap);
+    }
+    if fee.source == RelayerFeeSource::Payout {
+        value = value.min(amount);
+    }
+    value
+}
+
+/// Returns the total fees `relayer` has earned in `token`.
+pub fn get_earned(env: &Env, relayer: &Address, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(symbol_short!("rlyearn"), relayer.clone(), token.clone()))
+        .unwrap_or(0)
+}
+
+/// Updates relayer stats after a relayed claim paying `fee` in `token`.
+pub fn record_claim(env: &Env, relayer: &Address, token: &Address, fee: i128) {
+    let Some(mut info) = get(env, relayer) else {
+        return;
+    };
+    info.claims_relayed += 1;
+    save(env, relayer, &info);
+
+    if fee > 0 {
+        let earned = get_earned(env, relayer, token) + fee;
+        env.storage().persistent().set(
+            &(symbol_short!("rlyearn"), relayer.clone(), token.clone()),
+            &earned,
+        );
+    }
+}
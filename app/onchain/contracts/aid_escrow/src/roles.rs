@@ -0,0 +1,53 @@
+//! Admin-granted operational roles.
+//!
+//! Distributors predate this module and keep their own `dstrbtrs` map; newer
+//! roles share one storage layout: one `Map<Address, bool>` per role in
+//! instance storage, keyed by `("role", Role)`.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map};
+
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u32)]
+pub enum Role {
+    /// May propose, cancel and execute recipient recovery requests.
+    RecoveryAgent = 0,
+    /// May set, replace and revoke package delegates on behalf of recipients.
+    DelegateManager = 1,
+    /// May add and remove denylisted addresses.
+    Compliance = 2,
+}
+
+fn load_members(env: &Env, role: Role) -> Map<Address, bool> {
+    env.storage()
+        .instance()
+        .get(&(symbol_short!("role"), role))
+        .unwrap_or_else(|| Map::new(env))
+}
+
+fn save_members(env: &Env, role: Role, members: &Map<Address, bool>) {
+    env.storage()
+        .instance()
+        .set(&(symbol_short!("role"), role), members);
+}
+
+/// Grants `role` to `account`.
+pub fn grant(env: &Env, role: Role, account: &Address) {
+    let mut members = load_members(env, role);
+    members.set(account.clone(), true);
+    save_members(env, role, &members);
+}
+
+/// Revokes `role` from `account`.
+pub fn revoke(env: &Env, role: Role, account: &Address) {
+    let mut members = load_members(env, role);
+    members.remove(account.clone());
+    save_members(env, role, &members);
+}
+
+/// Returns `true` if `account` currently holds `role`.
+pub fn has_role(env: &Env, role: Role, account: &Address) -> bool {
+    load_members(env, role)
+        .get(account.clone())
+        .unwrap_or(false)
+}
@@ -0,0 +1,194 @@
+//! Standing recipient-level delegations.
+//!
+//! A standing delegation is keyed by the recipient address rather than a
+//! package id, so it covers every current and future package of that
+//! recipient whose token or campaign matches the delegation scope.  Each
+//! recipient may hold up to `MAX_STANDING_DELEGATES` delegations, one per
+//! delegate address, stored under `("sdlg", recipient)`.
+//!
+//! Precedence (see `delegate::check_claimer`): the recipient always wins,
+//! then the package's own delegate set, then standing delegations.  A
+//! package that requires co-signed delegate claims does not accept a lone
+//! standing delegate either.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
+
+use crate::{payout::PayoutPolicy, terms, Error, Package};
+
+/// Maximum number of standing delegations per recipient.
+pub const MAX_STANDING_DELEGATES: u32 = 5;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DelegationScope {
+    /// Every package of the recipient.
+    All,
+    /// Packages denominated in this token.
+    Token(Address),
+    /// Packages whose `campaign_id` term equals this value.
+    Campaign(String),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct StandingDelegation {
+    pub delegate: Address,
+    pub scope: DelegationScope,
+    /// Expiration timestamp (0 = no expiration).
+    pub expires_at: u64,
+    pub granted_by: Address,
+    pub granted_at: u64,
+}
+
+fn key(recipient: &Address) -> (Symbol, Address) {
+    (symbol_short!("sdlg"), recipient.clone())
+}
+
+/// Returns every standing delegation of `recipient`, including expired ones.
+pub fn get_all(env: &Env, recipient: &Address) -> Vec<StandingDelegation> {
+    env.storage()
+        .persistent()
+        .get(&key(recipient))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn save_all(env: &Env, recipient: &Address, delegations: &Vec<StandingDelegation>) {
+    if delegations.is_empty() {
+        env.storage().persistent().remove(&key(recipient));
+    } else {
+        env.storage().persistent().set(&key(recipient), delegations);
+    }
+}
+
+fn is_expired(env: &Env, delegation: &StandingDelegation) -> bool {
+    delegation.expires_at > 0 && env.ledger().timestamp() > delegation.expires_at
+}
+
+fn scope_matches(env: &Env, scope: &DelegationScope, package: &Package) -> bool {
+    match scope {
+        DelegationScope::All => true,
+        DelegationScope::Token(token) => token == &package.token,
+        DelegationScope::Campaign(campaign) => {
+            terms::campaign(env, package).as_ref() == Some(campaign)
+        }
+    }
+}
+
+/// Grants (or replaces) the standing delegation of `delegate` for `recipient`.
+/// The caller (contract entry point) must already have authenticated `actor`.
+///
+/// # Errors
+/// - `Error::InvalidState` - Delegate is the recipient, or expiration is in the past
+/// - `Error::DelegateLimitReached` - Recipient already has `MAX_STANDING_DELEGATES` delegations
+pub fn grant(
+    env: &Env,
+    actor: &Address,
+    recipient: &Address,
+    delegate: &Address,
+    scope: DelegationScope,
+    expires_at: u64,
+) -> Result<(), Error> {
+    let now = env.ledger().timestamp();
+    if delegate == recipient || (expires_at > 0 && expires_at <= now) {
+        return Err(Error::InvalidState);
+    }
+
+    let mut delegations = get_all(env, recipient);
+    let delegation = StandingDelegation {
+        delegate: delegate.clone(),
+        scope,
+        expires_at,
+        granted_by: actor.clone(),
+        granted_at: now,
+    };
+
+    match delegations.iter().position(|d| &d.delegate == delegate) {
+        Some(index) => delegations.set(index as u32, delegation),
+        None => {
+            if delegations.len() >= MAX_STANDING_DELEGATES {
+                return Err(Error::DelegateLimitReached);
+            }
+            delegations.push_back(delegation);
+            remove_payout(env, recipient, delegate);
+        }
+    }
+    save_all(env, recipient, &delegations);
+    Ok(())
+}
+
+/// Removes the standing delegation of `delegate` for `recipient`.
+///
+/// # Errors
+/// - `Error::DelegateNotFound` - No such delegation
+pub fn revoke(
+    env: &Env,
+    recipient: &Address,
+    delegate: &Address,
+) -> Result<StandingDelegation, Error> {
+    let mut delegations = get_all(env, recipient);
+    let index = delegations
+        .iter()
+        .position(|d| &d.delegate == delegate)
+        .ok_or(Error::DelegateNotFound)? as u32;
+    let removed = delegations.get(index).unwrap();
+    delegations.remove(index);
+    save_all(env, recipient, &delegations);
+    remove_payout(env, recipient, delegate);
+    Ok(removed)
+}
+
+fn payout_key(recipient: &Address, delegate: &Address) -> (Symbol, Address, Address) {
+    (symbol_short!("sdpay"), recipient.clone(), delegate.clone())
+}
+
+/// Sets or clears the payout policy of the standing delegation of `delegate` for `recipient`.
+///
+/// # Errors
+/// - `Error::DelegateNotFound` - No such delegation
+#[cfg(feature = "payout-policies")]
+pub fn set_payout(
+    env: &Env,
+    recipient: &Address,
+    delegate: &Address,
+    payout: Option<PayoutPolicy>,
+) -> Result<(), Error> {
+    if !get_all(env, recipient)
+        .iter()
+        .any(|d| &d.delegate == delegate)
+    {
+        return Err(Error::DelegateNotFound);
+    }
+    match payout {
+        Some(policy) => env
+            .storage()
+            .persistent()
+            .set(&payout_key(recipient, delegate), &policy),
+        None => remove_payout(env, recipient, delegate),
+    }
+    Ok(())
+}
+
+/// Returns the payout policy set on a standing delegation, if any.
+pub fn get_payout(env: &Env, recipient: &Address, delegate: &Address) -> Option<PayoutPolicy> {
+    env.storage()
+        .persistent()
+        .get(&payout_key(recipient, delegate))
+}
+
+fn remove_payout(env: &Env, recipient: &Address, delegate: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&payout_key(recipient, delegate));
+}
+
+/// Returns the active standing delegation that lets `claimer` act for the
+/// recipient of `package`, if any.
+pub fn find_for_package(
+    env: &Env,
+    package: &Package,
+    claimer: &Address,
+) -> Option<StandingDelegation> {
+    get_all(env, &package.recipient).iter().find(|d| {
+        &d.delegate == claimer && !is_expired(env, d) && scope_matches(env, &d.scope, package)
+    })
+}
@@ -0,0 +1,270 @@
+//! Typed package terms.
+//!
+//! Behaviour that used to be driven by magic metadata keys is carried by
+//! `PackageTerms`, passed alongside the free-form metadata on creation.  The
+//! legacy keys are still accepted: `claim_starts_at` (decimal seconds),
+//! `merkle_root` (64 hex chars), `receipt_hash` and `campaign_ref`.  A term may
+//! be given either typed or in metadata, not both with different values.
+//!
+//! Terms are stored per package only when at least one is set.  Packages
+//! created before typed terms existed have none stored and are read back from
+//! their metadata, exactly as before.  Amending a legacy key with
+//! `update_package_metadata` updates the stored terms to match.
+//! `restricted` has no metadata form and cannot be amended.
+//!
+//! Storage: `("terms", id)` -> `PackageTerms`.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Map, String, Symbol, Vec};
+
+use crate::{Error, Package};
+
+/// Legacy metadata key for `claim_starts_at`.
+pub const META_CLAIM_STARTS_AT: &str = "claim_starts_at";
+/// Legacy metadata key for `merkle_root`.
+pub const META_MERKLE_ROOT: &str = "merkle_root";
+/// Legacy metadata key for `receipt_hash`.
+pub const META_RECEIPT_HASH: &str = "receipt_hash";
+/// Legacy metadata key for `campaign_id`.
+pub const META_CAMPAIGN: &str = "campaign_ref";
+
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PackageTerms {
+    /// Earliest claim time; defaults to the creation time.
+    pub claim_starts_at: Option<u64>,
+    /// Merkle allowlist root; claims must go through `claim_with_proof`.
+    pub merkle_root: Option<BytesN<32>>,
+    /// Off-chain receipt reference echoed in payout events.
+    pub receipt_hash: Option<String>,
+    /// Campaign the package belongs to (fees, delegation scopes, stats).
+    pub campaign_id: Option<String>,
+    /// Restricted-spend voucher: paid out only through merchant `redeem`.
+    pub restricted: bool,
+    /// Spending categories a restricted package may be redeemed for (`None` =
+    /// any).  Matched against merchant category tags.
+    pub allowed_categories: Option<Vec<Symbol>>,
+    /// Contract attesting `condition_id`; the package is released only once
+    /// the condition is attested.  Set together with `condition_id`.
+    pub attestor: Option<Address>,
+    pub condition_id: Option<Symbol>,
+}
+
+/// Combines typed terms with any legacy keys in `metadata`.
+///
+/// # Errors
+/// - `Error::MalformedTerm` - A legacy key cannot be parsed, or
+///   `allowed_categories` is empty or set on an unrestricted package, or only
+///   one of `attestor` and `condition_id` is set, or `restricted` is set in a
+///   build without the `merchants` feature, where it could never be paid out
+/// - `Error::ConflictingTerms` - A term is given both ways with different values
+pub fn merge(
+    env: &Env,
+    typed: PackageTerms,
+    metadata: &Map<Symbol, String>,
+) -> Result<PackageTerms, Error> {
+    if let Some(categories) = &typed.allowed_categories {
+        if categories.is_empty() || !typed.restricted {
+            return Err(Error::MalformedTerm);
+        }
+    }
+    if typed.restricted && cfg!(not(feature = "merchants")) {
+        return Err(Error::MalformedTerm);
+    }
+    if typed.attestor.is_some() != typed.condition_id.is_some() {
+        return Err(Error::MalformedTerm);
+    }
+    let claim_starts_at = match metadata.get(Symbol::new(env, META_CLAIM_STARTS_AT)) {
+        Some(raw) => Some(parse_u64(&raw).ok_or(Error::MalformedTerm)?),
+        None => None,
+    };
+    let merkle_root = match metadata.get(Symbol::new(env, META_MERKLE_ROOT)) {
+        Some(raw) => {
+            let root = parse_hex_32(&raw).ok_or(Error::MalformedTerm)?;
+            Some(BytesN::from_array(env, &root))
+        }
+        None => None,
+    };
+    let legacy = PackageTerms {
+        claim_starts_at,
+        merkle_root,
+        receipt_hash: metadata.get(Symbol::new(env, META_RECEIPT_HASH)),
+        campaign_id: metadata.get(Symbol::new(env, META_CAMPAIGN)),
+        restricted: false,
+        allowed_categories: None,
+        attestor: None,
+        condition_id: None,
+    };
+
+    Ok(PackageTerms {
+        claim_starts_at: pick(typed.claim_starts_at, legacy.claim_starts_at)?,
+        merkle_root: pick(typed.merkle_root, legacy.merkle_root)?,
+        receipt_hash: pick(typed.receipt_hash, legacy.receipt_hash)?,
+        campaign_id: pick(typed.campaign_id, legacy.campaign_id)?,
+        restricted: typed.restricted,
+        allowed_categories: typed.allowed_categories,
+        attestor: typed.attestor,
+        condition_id: typed.condition_id,
+    })
+}
+
+fn pick<T: PartialEq>(typed: Option<T>, legacy: Option<T>) -> Result<Option<T>, Error> {
+    match (typed, legacy) {
+        (Some(a), Some(b)) if a != b => Err(Error::ConflictingTerms),
+        (Some(a), _) => Ok(Some(a)),
+        (None, b) => Ok(b),
+    }
+}
+
+/// Returns the effective claim start, checking it lies within
+/// `[created_at, expires_at]` (`expires_at` 0 = no expiry).
+///
+/// # Errors
+/// - `Error::InvalidClaimWindow` - The start is before creation or after expiry
+pub fn claim_start(terms: &PackageTerms, created_at: u64, expires_at: u64) -> Result<u64, Error> {
+    let start = terms.claim_starts_at.unwrap_or(created_at);
+    if start < created_at || (expires_at > 0 && start > expires_at) {
+        return Err(Error::InvalidClaimWindow);
+    }
+    Ok(start)
+}
+
+/// Returns `true` for legacy keys whose change alters how a package is
+/// claimed or accounted for.
+#[cfg(feature = "amendments")]
+pub fn is_behaviour_key(env: &Env, key: &Symbol) -> bool {
+    [META_CLAIM_STARTS_AT, META_MERKLE_ROOT, META_CAMPAIGN]
+        .iter()
+        .any(|k| *key == Symbol::new(env, k))
+}
+
+/// Applies a change of the legacy metadata `key` to `terms` (`value` = `None`
+/// when the key is removed).  Keys that are not term keys are ignored.
+///
+/// # Errors
+/// - `Error::MalformedTerm` - The new value cannot be parsed
+#[cfg(feature = "amendments")]
+pub fn amend(
+    env: &Env,
+    terms: &mut PackageTerms,
+    key: &Symbol,
+    value: Option<String>,
+) -> Result<(), Error> {
+    if *key == Symbol::new(env, META_CLAIM_STARTS_AT) {
+        terms.claim_starts_at = match value {
+            Some(raw) => Some(parse_u64(&raw).ok_or(Error::MalformedTerm)?),
+            None => None,
+        };
+    } else if *key == Symbol::new(env, META_MERKLE_ROOT) {
+        terms.merkle_root = match value {
+            Some(raw) => {
+                let root = parse_hex_32(&raw).ok_or(Error::MalformedTerm)?;
+                Some(BytesN::from_array(env, &root))
+            }
+            None => None,
+        };
+    } else if *key == Symbol::new(env, META_RECEIPT_HASH) {
+        terms.receipt_hash = value;
+    } else if *key == Symbol::new(env, META_CAMPAIGN) {
+        terms.campaign_id = value;
+    }
+    Ok(())
+}
+
+/// Stores the terms of a new package, if any are set.
+pub fn save(env: &Env, package_id: u64, terms: &PackageTerms) {
+    if *terms != PackageTerms::default() {
+        replace(env, package_id, terms);
+    }
+}
+
+/// Overwrites the stored terms of a package.
+pub fn replace(env: &Env, package_id: u64, terms: &PackageTerms) {
+    env.storage()
+        .persistent()
+        .set(&(symbol_short!("terms"), package_id), terms);
+}
+
+/// Returns the terms of `package`, reading legacy metadata when none are
+/// stored.  Unparseable legacy values are ignored, as they always were.
+/// `claim_starts_at` is always the effective start held on the package.
+pub fn get(env: &Env, package: &Package) -> PackageTerms {
+    if let Some(mut terms) = env
+        .storage()
+        .persistent()
+        .get::<_, PackageTerms>(&(symbol_short!("terms"), package.id))
+    {
+        terms.claim_starts_at = Some(package.claim_starts_at);
+        return terms;
+    }
+
+    let metadata = &package.metadata;
+    PackageTerms {
+        claim_starts_at: Some(package.claim_starts_at),
+        merkle_root: metadata
+            .get(Symbol::new(env, META_MERKLE_ROOT))
+            .and_then(|raw| parse_hex_32(&raw))
+            .map(|root| BytesN::from_array(env, &root)),
+        receipt_hash: metadata.get(Symbol::new(env, META_RECEIPT_HASH)),
+        campaign_id: metadata.get(Symbol::new(env, META_CAMPAIGN)),
+        restricted: false,
+        allowed_categories: None,
+        attestor: None,
+        condition_id: None,
+    }
+}
+
+/// Returns the campaign `package` belongs to, if any.
+pub fn campaign(env: &Env, package: &Package) -> Option<String> {
+    get(env, package).campaign_id
+}
+
+fn parse_u64(value: &String) -> Option<u64> {
+    let len = value.len() as usize;
+    if len == 0 || len > 20 {
+        return None;
+    }
+
+    let mut bytes = [0u8; 20];
+    value.copy_into_slice(&mut bytes[..len]);
+
+    let mut out: u64 = 0;
+    for b in bytes[..len].iter() {
+        if !b.is_ascii_digit() {
+            return None;
+        }
+        out = out.checked_mul(10)?.checked_add((b - b'0') as u64)?;
+    }
+
+    Some(out)
+}
+
+/// Parses a 64-character hex string into 32 bytes.
+pub fn parse_hex_32(value: &String) -> Option<[u8; 32]> {
+    let len = value.len() as usize;
+    if len != 64 {
+        return None;
+    }
+
+    let mut raw = [0u8; 64];
+    value.copy_into_slice(&mut raw);
+
+    let mut out = [0u8; 32];
+    let mut i = 0usize;
+    while i < 32 {
+        let hi = hex_nibble(raw[i * 2])?;
+        let lo = hex_nibble(raw[i * 2 + 1])?;
+        out[i] = (hi << 4) | lo;
+        i += 1;
+    }
+
+    Some(out)
+}
+
+fn hex_nibble(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(10 + (b - b'a')),
+        b'A'..=b'F' => Some(10 + (b - b'A')),
+        _ => None,
+    }
+}
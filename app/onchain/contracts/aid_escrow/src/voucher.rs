@@ -0,0 +1,64 @@
+//! Distributor-signed claim vouchers.
+//!
+//! Field workers frequently verify a beneficiary without network access.
+//! A distributor registers an ed25519 public key once, then signs vouchers
+//! off-chain that bind `(package_id, claimant, nonce, expires_at)`.  The
+//! recipient (or their delegate) later redeems the voucher on-chain through
+//! `claim_with_voucher`.
+//!
+//! - The signed payload is the XDR encoding of
+//!   `(escrow_contract_address, ClaimVoucher)`, so a voucher cannot be
+//!   replayed against another deployment.
+//! - Each `(distributor, nonce)` pair can be redeemed at most once.
+//! - A voucher is only honoured while its signer is still the admin or a
+//!   registered distributor.
+
+use soroban_sdk::{contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimVoucher {
+    pub package_id: u64,
+    pub claimant: Address,
+    pub distributor: Address,
+    pub nonce: u64,
+    /// Ledger timestamp after which the voucher can no longer be redeemed.
+    pub expires_at: u64,
+}
+
+/// Persists the ed25519 public key used to verify `distributor`'s vouchers.
+pub fn set_signing_key(env: &Env, distributor: &Address, public_key: &BytesN<32>) {
+    let key = (symbol_short!("dkey"), distributor.clone());
+    env.storage().persistent().set(&key, public_key);
+}
+
+/// Returns the registered signing key for `distributor`, if any.
+pub fn get_signing_key(env: &Env, distributor: &Address) -> Option<BytesN<32>> {
+    let key = (symbol_short!("dkey"), distributor.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Returns `true` if `nonce` has already been redeemed for `distributor`.
+pub fn is_nonce_used(env: &Env, distributor: &Address, nonce: u64) -> bool {
+    let key = (symbol_short!("vnonce"), distributor.clone(), nonce);
+    env.storage().persistent().has(&key)
+}
+
+/// Marks `nonce` as redeemed for `distributor` (replay protection).
+pub fn mark_nonce_used(env: &Env, distributor: &Address, nonce: u64) {
+    let key = (symbol_short!("vnonce"), distributor.clone(), nonce);
+    env.storage().persistent().set(&key, &true);
+}
+
+/// Builds the exact byte payload a distributor must sign for `voucher`.
+pub fn payload(env: &Env, voucher: &ClaimVoucher) -> Bytes {
+    (env.current_contract_address(), voucher.clone()).to_xdr(env)
+}
+
+/// Verifies `signature` over the voucher payload with `public_key`.
+///
+/// The host traps on an invalid signature, aborting the whole invocation.
+pub fn verify(env: &Env, voucher: &ClaimVoucher, public_key: &BytesN<32>, signature: &BytesN<64>) {
+    let message = payload(env, voucher);
+    env.crypto().ed25519_verify(public_key, &message, signature);
+}
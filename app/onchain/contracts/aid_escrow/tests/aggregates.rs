@@ -290,6 +290,7 @@ fn test_aggregates_disburse_counts_as_claimed() {
 
     let agg = client.get_aggregates(&token_client.address);
     assert_eq!(agg.total_claimed, 30_000_000);
+    assert_eq!(client.get_total_claimed(&token_client.address), 30_000_000);
 }
 
 #[test]
@@ -0,0 +1,99 @@
+#![cfg(test)]
+
+mod common;
+
+use aid_escrow::{Error, PackageStatus, PackageTerms, PolicyError};
+use common::{Escrow, UNIT};
+use mock_attestor::{MockAttestor, MockAttestorClient};
+use soroban_sdk::{symbol_short, testutils::Address as _, Address, InvokeError, Map, Symbol};
+use std::ops::Deref;
+
+struct AttestorTest {
+    escrow: Escrow,
+    attestor: MockAttestorClient<'static>,
+}
+
+impl Deref for AttestorTest {
+    type Target = Escrow;
+
+    fn deref(&self) -> &Escrow {
+        &self.escrow
+    }
+}
+
+impl AttestorTest {
+    fn new() -> Self {
+        let escrow = Escrow::new();
+
+        let attestor_id = escrow.env.register(MockAttestor, ());
+        let attestor = MockAttestorClient::new(&escrow.env, &attestor_id);
+
+        Self { escrow, attestor }
+    }
+
+    fn create(
+        &self,
+        id: u64,
+        attestor: Option<Address>,
+        condition_id: Option<Symbol>,
+    ) -> Result<u64, Error> {
+        let terms = PackageTerms {
+            attestor,
+            condition_id,
+            ..Default::default()
+        };
+        self.client
+            .try_create_package_with_terms(
+                &self.admin,
+                &id,
+                &Address::generate(&self.env),
+                &UNIT,
+                &self.token,
+                &(self.env.ledger().timestamp() + 86400),
+                &terms,
+                &Map::new(&self.env),
+            )
+            .map(|r| r.unwrap())
+            .map_err(|e| e.unwrap())
+    }
+}
+
+#[test]
+fn package_is_released_once_its_condition_is_attested() {
+    let t = AttestorTest::new();
+    let drought = symbol_short!("drought");
+    t.create(1, Some(t.attestor.address.clone()), Some(drought.clone()))
+        .unwrap();
+    t.create(2, Some(t.attestor.address.clone()), Some(drought.clone()))
+        .unwrap();
+
+    let not_attested = Err(Err(InvokeError::from(PolicyError::ConditionNotAttested)));
+    assert_eq!(t.client.try_claim(&1), not_attested);
+    assert_eq!(t.client.try_disburse(&2), not_attested);
+
+    t.attestor.set_attested(&drought, &true);
+    t.client.claim(&1);
+    t.client.disburse(&2);
+    assert_eq!(t.client.get_package(&1).status, PackageStatus::Claimed);
+    assert_eq!(t.client.get_package(&2).status, PackageStatus::Claimed);
+}
+
+#[test]
+fn unreachable_attestors_and_half_set_conditions_are_rejected() {
+    let t = AttestorTest::new();
+    let flood = symbol_short!("flood");
+
+    // An address with no contract behind it can never attest.
+    t.create(1, Some(Address::generate(&t.env)), Some(flood.clone()))
+        .unwrap();
+    assert_eq!(
+        t.client.try_claim(&1),
+        Err(Err(InvokeError::from(PolicyError::ConditionNotAttested)))
+    );
+
+    assert_eq!(
+        t.create(2, Some(t.attestor.address.clone()), None),
+        Err(Error::MalformedTerm)
+    );
+    assert_eq!(t.create(2, None, Some(flood)), Err(Error::MalformedTerm));
+}
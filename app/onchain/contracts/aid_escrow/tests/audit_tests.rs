@@ -0,0 +1,216 @@
+#![cfg(test)]
+
+mod common;
+
+use aid_escrow::{AuditEntry, Config, Error, PackageStatus, Role};
+use common::{Escrow, UNIT};
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec,
+};
+use std::ops::Deref;
+
+struct AuditTest {
+    escrow: Escrow,
+    recipient: Address,
+    /// Every `audit_recorded` payload seen so far, in order.
+    records: std::vec::Vec<Map<Symbol, Val>>,
+}
+
+impl Deref for AuditTest {
+    type Target = Escrow;
+
+    fn deref(&self) -> &Escrow {
+        &self.escrow
+    }
+}
+
+impl AuditTest {
+    fn new() -> Self {
+        let escrow = Escrow::deploy();
+        let recipient = Address::generate(&escrow.env);
+
+        let mut t = Self {
+            escrow,
+            recipient,
+            records: std::vec::Vec::new(),
+        };
+        t.client.init(&t.admin);
+        t.collect();
+        t
+    }
+
+    /// Appends the audit records emitted by the last invocation.
+    fn collect(&mut self) {
+        let topic = Symbol::new(&self.env, "audit_recorded");
+        for (id, topics, data) in self.env.events().all().iter() {
+            if id != self.client.address {
+                continue;
+            }
+            let first = topics.first().unwrap();
+            if Symbol::try_from_val(&self.env, &first).ok() == Some(topic.clone()) {
+                self.records
+                    .push(Map::<Symbol, Val>::try_from_val(&self.env, &data).unwrap());
+            }
+        }
+    }
+
+    fn field<T: TryFromVal<Env, Val>>(&self, record: &Map<Symbol, Val>, name: &str) -> T {
+        let val = record.get(Symbol::new(&self.env, name)).unwrap();
+        T::try_from_val(&self.env, &val).ok().unwrap()
+    }
+
+    fn create_package(&mut self, id: u64) {
+        self.client.create_package(
+            &self.admin,
+            &id,
+            &self.recipient,
+            &UNIT,
+            &self.token,
+            &(self.env.ledger().timestamp() + 86400),
+            &Map::new(&self.env),
+        );
+        self.collect();
+    }
+}
+
+#[test]
+fn every_mutation_advances_the_head() {
+    let mut t = AuditTest::new();
+    assert_eq!(t.client.get_audit_head().seq, 1);
+
+    t.client.fund(&t.token, &t.admin, &(5 * UNIT));
+    t.collect();
+    assert_eq!(t.client.get_audit_head().seq, 2);
+
+    t.create_package(1);
+    t.create_package(2);
+    t.client.claim(&1);
+    t.collect();
+    t.client.revoke(&2);
+    t.collect();
+    t.client.set_config(&Config {
+        min_amount: 1,
+        max_expires_in: 0,
+        allowed_tokens: Vec::new(&t.env),
+    });
+    t.collect();
+    t.client.grant_role(&Role::RecoveryAgent, &t.recipient);
+    t.collect();
+
+    assert_eq!(t.client.get_audit_head().seq, 8);
+    let actions: std::vec::Vec<Symbol> = t.records.iter().map(|r| t.field(r, "action")).collect();
+    let expected = [
+        "init",
+        "fund",
+        "created",
+        "created",
+        "claimed",
+        "revoked",
+        "set_config",
+        "grant_role",
+    ];
+    for (action, name) in actions.iter().zip(expected) {
+        assert_eq!(*action, Symbol::new(&t.env, name));
+    }
+}
+
+impl AuditTest {
+    /// Funds the escrow, creates package 1 and disburses it, returning the
+    /// change committed by each audit record: the entrypoint arguments, or
+    /// the package after a status change.
+    fn fund_create_and_disburse(&mut self) -> std::vec::Vec<Val> {
+        self.client.fund(&self.token, &self.admin, &(5 * UNIT));
+        self.collect();
+        self.create_package(1);
+        self.client.disburse(&1);
+        self.collect();
+
+        let disbursed = self.client.get_package(&1);
+        let mut created = disbursed.clone();
+        created.status = PackageStatus::Created;
+        std::vec![
+            self.admin.into_val(&self.env),
+            (self.token.clone(), self.admin.clone(), 5 * UNIT).into_val(&self.env),
+            created.into_val(&self.env),
+            disbursed.into_val(&self.env),
+        ]
+    }
+
+    /// Recomputes the chain from the recorded events, taking each entry's
+    /// change from `changes` rather than from the event.
+    fn replay(&self, changes: &[Val]) -> BytesN<32> {
+        let mut hash = BytesN::from_array(&self.env, &[0u8; 32]);
+        for (i, (record, change)) in self.records.iter().zip(changes).enumerate() {
+            let seq: u64 = self.field(record, "seq");
+            assert_eq!(seq, i as u64 + 1);
+
+            let package_val = record.get(Symbol::new(&self.env, "package_id")).unwrap();
+            let entry = AuditEntry {
+                seq,
+                action: self.field(record, "action"),
+                actor: self.field(record, "actor"),
+                package_id: Option::<u64>::try_from_val(&self.env, &package_val).unwrap(),
+                timestamp: self.field(record, "timestamp"),
+                change: self.env.crypto().sha256(&change.to_xdr(&self.env)).into(),
+            };
+            let mut preimage = Bytes::from_array(&self.env, &hash.to_array());
+            preimage.append(&entry.to_xdr(&self.env));
+            hash = self.env.crypto().sha256(&preimage).into();
+        }
+        hash
+    }
+}
+
+#[test]
+fn replaying_events_reproduces_the_head() {
+    let mut t = AuditTest::new();
+    let changes = t.fund_create_and_disburse();
+    assert_eq!(t.records.len(), changes.len());
+
+    let mut prev = BytesN::from_array(&t.env, &[0u8; 32]);
+    for (record, change) in t.records.iter().zip(&changes) {
+        let committed: BytesN<32> = t.field(record, "change");
+        let expected: BytesN<32> = t.env.crypto().sha256(&change.to_xdr(&t.env)).into();
+        assert_eq!(committed, expected);
+        let recorded_prev: BytesN<32> = t.field(record, "prev_hash");
+        assert_eq!(recorded_prev, prev);
+        prev = t.field(record, "hash");
+    }
+
+    let head = t.client.get_audit_head();
+    assert_eq!(head.seq, t.records.len() as u64);
+    assert_eq!(head.hash, t.replay(&changes));
+    let last: BytesN<32> = t.field(t.records.last().unwrap(), "hash");
+    assert_eq!(last, head.hash);
+}
+
+#[test]
+fn tampered_payloads_do_not_reproduce_the_head() {
+    let mut t = AuditTest::new();
+    let changes = t.fund_create_and_disburse();
+    let head = t.client.get_audit_head();
+    assert_eq!(t.replay(&changes), head.hash);
+
+    // Claim the escrow was funded with more than it was.
+    let mut changes = changes.clone();
+    changes[1] = (t.token.clone(), t.admin.clone(), 6 * UNIT).into_val(&t.env);
+    assert_ne!(t.replay(&changes), head.hash);
+
+    // Claim the package went to someone else.
+    let mut package = t.client.get_package(&1);
+    package.recipient = Address::generate(&t.env);
+    changes[3] = package.into_val(&t.env);
+    assert_ne!(t.replay(&changes), head.hash);
+}
+
+#[test]
+fn failed_calls_leave_the_head_unchanged() {
+    let t = AuditTest::new();
+    let before = t.client.get_audit_head();
+
+    assert_eq!(t.client.try_revoke(&99), Err(Ok(Error::PackageNotFound)));
+
+    assert_eq!(t.client.get_audit_head(), before);
+}
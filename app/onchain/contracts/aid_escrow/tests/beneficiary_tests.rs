@@ -0,0 +1,252 @@
+#![cfg(all(test, feature = "registry"))]
+
+mod common;
+
+#[cfg(feature = "recovery")]
+use aid_escrow::RecoveryScope;
+use aid_escrow::{BeneficiaryPolicy, PolicyError};
+use beneficiary_registry::{BeneficiaryRegistry, BeneficiaryRegistryClient};
+use common::{Escrow, UNIT};
+use soroban_sdk::{
+    testutils::Address as _, Address, BytesN, InvokeError, Map, String, Symbol, Vec,
+};
+use std::ops::Deref;
+
+struct BeneficiaryTest {
+    escrow: Escrow,
+    registry: BeneficiaryRegistryClient<'static>,
+}
+
+impl Deref for BeneficiaryTest {
+    type Target = Escrow;
+
+    fn deref(&self) -> &Escrow {
+        &self.escrow
+    }
+}
+
+impl BeneficiaryTest {
+    fn new(require_registered: bool) -> Self {
+        let escrow = Escrow::new();
+
+        let registry_id = escrow.env.register(BeneficiaryRegistry, ());
+        let registry = BeneficiaryRegistryClient::new(&escrow.env, &registry_id);
+        registry.init(&escrow.admin);
+        escrow
+            .client
+            .set_beneficiary_registry(&Some(BeneficiaryPolicy {
+                registry: registry_id,
+                require_registered,
+            }));
+
+        Self { escrow, registry }
+    }
+
+    fn metadata(&self, campaign: &str) -> Map<Symbol, String> {
+        let mut metadata = Map::new(&self.env);
+        metadata.set(
+            Symbol::new(&self.env, "campaign_ref"),
+            String::from_str(&self.env, campaign),
+        );
+        metadata
+    }
+
+    fn create(&self, id: u64, recipient: &Address, campaign: &str) -> Result<u64, InvokeError> {
+        self.client
+            .try_create_package(
+                &self.admin,
+                &id,
+                recipient,
+                &UNIT,
+                &self.token,
+                &(self.env.ledger().timestamp() + 86400),
+                &self.metadata(campaign),
+            )
+            .map(|r| r.unwrap())
+            .map_err(|e| e.err().unwrap())
+    }
+}
+
+#[test]
+fn unregistered_recipients_are_rejected_only_when_required() {
+    let t = BeneficiaryTest::new(true);
+    let recipient = Address::generate(&t.env);
+
+    assert_eq!(
+        t.create(1, &recipient, "flood"),
+        Err(PolicyError::RecipientNotRegistered.into())
+    );
+    t.registry
+        .register(&BytesN::from_array(&t.env, &[1; 32]), &recipient);
+    assert_eq!(t.create(1, &recipient, "flood"), Ok(1));
+
+    t.client.set_beneficiary_registry(&Some(BeneficiaryPolicy {
+        registry: t.registry.address.clone(),
+        require_registered: false,
+    }));
+    assert_eq!(t.create(2, &Address::generate(&t.env), "flood"), Ok(2));
+}
+
+#[test]
+fn an_identity_holds_one_live_package_per_campaign() {
+    let t = BeneficiaryTest::new(false);
+    let identity = BytesN::from_array(&t.env, &[7; 32]);
+    let first = Address::generate(&t.env);
+    let second = Address::generate(&t.env);
+    t.registry.register(&identity, &first);
+    t.create(1, &first, "flood").unwrap();
+
+    // The same person re-enrolled at another address is still recognised.
+    t.registry.deregister(&identity);
+    t.registry.register(&identity, &second);
+    let duplicate = Err(PolicyError::DuplicateIdentity.into());
+    assert_eq!(t.create(2, &second, "flood"), duplicate);
+    assert_eq!(t.create(2, &second, "drought"), Ok(2));
+
+    let recipients = Vec::from_array(&t.env, [Address::generate(&t.env), second.clone()]);
+    assert_eq!(
+        t.client.try_batch_create_packages(
+            &t.admin,
+            &recipients,
+            &Vec::from_array(&t.env, [UNIT, UNIT]),
+            &t.token,
+            &3600,
+            &Vec::from_array(&t.env, [t.metadata("flood"), t.metadata("flood")]),
+        ),
+        Err(Err(PolicyError::DuplicateIdentity.into()))
+    );
+
+    // Cancelling the first package frees the identity's slot.
+    t.client.revoke(&1);
+    assert_eq!(t.create(3, &second, "flood"), Ok(3));
+    assert_eq!(
+        t.client
+            .get_identity_package(&String::from_str(&t.env, "flood"), &identity),
+        Some(3)
+    );
+}
+
+#[test]
+#[cfg(feature = "amendments")]
+fn moving_a_package_to_another_campaign_rechecks_the_identity() {
+    let t = BeneficiaryTest::new(false);
+    let identity = BytesN::from_array(&t.env, &[3; 32]);
+    let recipient = Address::generate(&t.env);
+    t.registry.register(&identity, &recipient);
+    t.create(1, &recipient, "flood").unwrap();
+    t.create(2, &recipient, "decoy").unwrap();
+
+    assert_eq!(
+        t.client
+            .try_update_package_metadata(&t.admin, &2, &t.metadata("flood"), &Vec::new(&t.env)),
+        Err(Err(PolicyError::DuplicateIdentity.into()))
+    );
+
+    // Moving the first package away frees its slot for the second.
+    t.client
+        .update_package_metadata(&t.admin, &1, &t.metadata("drought"), &Vec::new(&t.env));
+    t.client
+        .update_package_metadata(&t.admin, &2, &t.metadata("flood"), &Vec::new(&t.env));
+    let flood = String::from_str(&t.env, "flood");
+    let drought = String::from_str(&t.env, "drought");
+    assert_eq!(t.client.get_identity_package(&flood, &identity), Some(2));
+    assert_eq!(t.client.get_identity_package(&drought, &identity), Some(1));
+}
+
+#[test]
+#[cfg(feature = "recovery")]
+fn recovery_moves_the_identity_slot_to_a_registered_recipient() {
+    use soroban_sdk::testutils::Ledger;
+
+    let t = BeneficiaryTest::new(true);
+    let lost = Address::generate(&t.env);
+    let replacement = Address::generate(&t.env);
+    let old_identity = BytesN::from_array(&t.env, &[5; 32]);
+    let new_identity = BytesN::from_array(&t.env, &[6; 32]);
+    t.registry.register(&old_identity, &lost);
+    t.create(1, &lost, "flood").unwrap();
+
+    let id = t
+        .client
+        .propose_recovery(&t.admin, &lost, &replacement, &RecoveryScope::Package(1));
+    t.env
+        .ledger()
+        .set_timestamp(t.env.ledger().timestamp() + 3 * 86400);
+    assert_eq!(
+        t.client.try_execute_recovery(&t.admin, &id, &50),
+        Err(Err(PolicyError::RecipientNotRegistered.into()))
+    );
+
+    t.registry.register(&new_identity, &replacement);
+    t.client.execute_recovery(&t.admin, &id, &50);
+    let flood = String::from_str(&t.env, "flood");
+    assert_eq!(
+        t.client.get_identity_package(&flood, &new_identity),
+        Some(1)
+    );
+    assert_eq!(t.client.get_identity_package(&flood, &old_identity), None);
+    t.create(2, &lost, "flood").unwrap();
+}
+
+#[test]
+fn a_failing_registry_rejects_packages_instead_of_skipping_checks() {
+    let t = BeneficiaryTest::new(false);
+    t.client.set_beneficiary_registry(&Some(BeneficiaryPolicy {
+        registry: t.token.clone(),
+        require_registered: false,
+    }));
+
+    assert_eq!(
+        t.create(1, &Address::generate(&t.env), "flood"),
+        Err(PolicyError::RegistryUnavailable.into())
+    );
+}
+
+#[test]
+#[cfg(feature = "hash-locks")]
+fn hash_locked_packages_check_the_payout_address_at_redemption() {
+    use soroban_sdk::{testutils::Ledger, xdr::ToXdr, Bytes};
+
+    let t = BeneficiaryTest::new(true);
+    t.env.ledger().set_sequence_number(100);
+    let secret = Bytes::from_slice(&t.env, b"483-209-117");
+    let claim_hash: BytesN<32> = t.env.crypto().sha256(&secret).into();
+    t.client.create_hashlocked_package(
+        &t.admin,
+        &1,
+        &claim_hash,
+        &UNIT,
+        &t.token,
+        &(t.env.ledger().timestamp() + 86400),
+        &t.metadata("flood"),
+    );
+
+    let identity = BytesN::from_array(&t.env, &[9; 32]);
+    let holder = Address::generate(&t.env);
+    let wallet = Address::generate(&t.env);
+    t.registry.register(&identity, &holder);
+    t.create(2, &holder, "flood").unwrap();
+
+    let claim = |payout: &Address| {
+        let mut data = secret.clone();
+        data.append(&payout.clone().to_xdr(&t.env));
+        let commitment: BytesN<32> = t.env.crypto().sha256(&data).into();
+        t.client.commit_secret_claim(&1, payout, &commitment);
+        let seq = t.env.ledger().sequence();
+        t.env.ledger().set_sequence_number(seq + 1);
+        t.client
+            .try_claim_with_secret(&1, &secret, payout)
+            .map(|r| r.unwrap())
+            .map_err(|e| e.err().unwrap())
+    };
+
+    assert_eq!(
+        claim(&wallet),
+        Err(PolicyError::RecipientNotRegistered.into())
+    );
+    assert_eq!(claim(&holder), Err(PolicyError::DuplicateIdentity.into()));
+
+    t.registry
+        .register(&BytesN::from_array(&t.env, &[4; 32]), &wallet);
+    assert_eq!(claim(&wallet), Ok(()));
+}
@@ -0,0 +1,182 @@
+#![cfg(all(test, feature = "benefit-caps"))]
+
+mod common;
+
+use aid_escrow::{BenefitCap, CapMode, Error, PackageStatus, PolicyError};
+use common::{Escrow, UNIT};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, InvokeError, Map,
+};
+
+const DAY: u64 = 86400;
+
+type BenefitCapTest = Escrow;
+
+impl BenefitCapTest {
+    fn cap(&self, amount: i128, mode: CapMode) {
+        self.client.set_benefit_cap(
+            &self.token,
+            &Some(BenefitCap {
+                amount,
+                window: 30 * DAY,
+                mode,
+            }),
+        );
+    }
+
+    fn create(&self, id: u64, recipient: &Address, amount: i128) -> u64 {
+        self.client.create_package(
+            &self.admin,
+            &id,
+            recipient,
+            &amount,
+            &self.token,
+            &(self.env.ledger().timestamp() + 90 * DAY),
+            &Map::new(&self.env),
+        )
+    }
+
+    fn claim(&self, id: u64) -> Result<(), InvokeError> {
+        self.client
+            .try_claim(&id)
+            .map(|r| r.unwrap())
+            .map_err(|e| e.err().unwrap())
+    }
+
+    fn advance(&self, seconds: u64) {
+        let now = self.env.ledger().timestamp();
+        self.env.ledger().set_timestamp(now + seconds);
+    }
+}
+
+#[test]
+fn claims_over_the_cap_are_rejected_until_the_window_rolls_over() {
+    let t = BenefitCapTest::new();
+    t.cap(3 * UNIT, CapMode::Reject);
+    let recipient = Address::generate(&t.env);
+    t.create(1, &recipient, 2 * UNIT);
+    t.create(2, &recipient, 2 * UNIT);
+
+    t.claim(1).unwrap();
+    assert_eq!(
+        t.claim(2),
+        Err(InvokeError::from(PolicyError::BenefitCapExceeded))
+    );
+    assert_eq!(t.client.get_package(&2).status, PackageStatus::Created);
+
+    t.advance(30 * DAY);
+    t.claim(2).unwrap();
+
+    let totals = t.client.get_recipient_totals(&recipient, &t.token);
+    assert_eq!(totals.claimed, 4 * UNIT);
+    assert_eq!(totals.payouts, 2);
+    assert_eq!(totals.last_claimed_at, t.env.ledger().timestamp());
+    assert_eq!(totals.window_claimed, 2 * UNIT);
+}
+
+#[test]
+fn partial_pay_settles_up_to_the_cap_and_returns_the_rest() {
+    let t = BenefitCapTest::new();
+    t.cap(3 * UNIT, CapMode::PayPartial);
+    let recipient = Address::generate(&t.env);
+    t.create(1, &recipient, 2 * UNIT);
+    t.create(2, &recipient, 2 * UNIT);
+    t.create(3, &recipient, UNIT);
+
+    t.claim(1).unwrap();
+    t.claim(2).unwrap();
+    assert_eq!(t.balance(&recipient), 3 * UNIT);
+    let package = t.client.get_package(&2);
+    assert_eq!(package.status, PackageStatus::Claimed);
+    assert_eq!(package.amount, 2 * UNIT);
+
+    let aggregates = t.client.get_aggregates(&t.token);
+    assert_eq!(aggregates.total_claimed, 3 * UNIT);
+    assert_eq!(aggregates.total_committed, UNIT);
+    assert_eq!(t.client.get_total_claimed(&t.token), 3 * UNIT);
+    assert_eq!(t.client.get_total_locked(&t.token), UNIT);
+
+    // Nothing is left under the cap, so nothing is paid.
+    assert_eq!(
+        t.claim(3),
+        Err(InvokeError::from(PolicyError::BenefitCapExceeded))
+    );
+}
+
+#[test]
+#[cfg(feature = "merchants")]
+fn redemptions_count_towards_the_cap_and_are_never_shortened() {
+    use aid_escrow::PackageTerms;
+    use soroban_sdk::{symbol_short, Vec};
+
+    let t = BenefitCapTest::new();
+    t.cap(UNIT / 2, CapMode::PayPartial);
+    let recipient = Address::generate(&t.env);
+    let shop = Address::generate(&t.env);
+    t.client
+        .add_merchant(&shop, &Vec::from_array(&t.env, [symbol_short!("food")]));
+    let terms = PackageTerms {
+        restricted: true,
+        ..Default::default()
+    };
+    t.client.create_package_with_terms(
+        &t.admin,
+        &1,
+        &recipient,
+        &UNIT,
+        &t.token,
+        &(t.env.ledger().timestamp() + DAY),
+        &terms,
+        &Map::new(&t.env),
+    );
+
+    t.client.redeem(&1, &shop, &(4 * UNIT / 10));
+    assert_eq!(
+        t.client.try_redeem(&1, &shop, &(2 * UNIT / 10)),
+        Err(Err(InvokeError::from(PolicyError::BenefitCapExceeded)))
+    );
+    t.client.redeem(&1, &shop, &(UNIT / 10));
+
+    let totals = t.client.get_recipient_totals(&recipient, &t.token);
+    assert_eq!(totals.claimed, UNIT / 2);
+    assert_eq!(totals.payouts, 2);
+    assert_eq!(totals.window_claimed, UNIT / 2);
+}
+
+#[test]
+fn totals_are_kept_without_a_cap_and_caps_can_be_removed() {
+    let t = BenefitCapTest::new();
+    let recipient = Address::generate(&t.env);
+    t.create(1, &recipient, UNIT);
+    t.claim(1).unwrap();
+
+    let totals = t.client.get_recipient_totals(&recipient, &t.token);
+    assert_eq!(totals.claimed, UNIT);
+    assert_eq!(totals.payouts, 1);
+    assert_eq!(totals.window_claimed, 0);
+
+    assert_eq!(
+        t.client.try_set_benefit_cap(
+            &t.token,
+            &Some(BenefitCap {
+                amount: UNIT,
+                window: 0,
+                mode: CapMode::Reject,
+            }),
+        ),
+        Err(Ok(Error::InvalidAmount))
+    );
+    t.cap(UNIT / 2, CapMode::Reject);
+    assert_eq!(t.client.get_benefit_cap(&t.token).unwrap().amount, UNIT / 2);
+    t.create(2, &recipient, UNIT);
+    assert!(t.claim(2).is_err());
+
+    t.client.set_benefit_cap(&t.token, &None);
+    assert_eq!(t.client.get_benefit_cap(&t.token), None);
+    t.claim(2).unwrap();
+    assert_eq!(
+        t.client.get_recipient_totals(&recipient, &t.token).claimed,
+        2 * UNIT
+    );
+}
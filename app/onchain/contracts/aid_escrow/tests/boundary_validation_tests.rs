@@ -546,7 +546,7 @@ mod edge_cases {
             &metadata,
         );
         // Should fail because claim_starts_at < created_at
-        assert_eq!(result, Err(Ok(Error::InvalidState)));
+        assert_eq!(result, Err(Ok(Error::InvalidClaimWindow)));
     }
 
     #[test]
@@ -573,6 +573,6 @@ mod edge_cases {
             &metadata,
         );
         // Should fail because claim_starts_at > expires_at
-        assert_eq!(result, Err(Ok(Error::InvalidState)));
+        assert_eq!(result, Err(Ok(Error::InvalidClaimWindow)));
     }
 }
@@ -0,0 +1,69 @@
+//! Fixture shared by the feature test files.
+//!
+//! Each file aliases [`Escrow`] and adds its own helpers. A file that needs
+//! extra accounts or companion contracts wraps it instead and derefs to it.
+
+// Every test file compiles this module, and none uses all of it.
+#![allow(dead_code)]
+
+use aid_escrow::{AidEscrow, AidEscrowClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+/// One whole token of a 7-decimal asset.
+pub const UNIT: i128 = 10_000_000;
+
+pub struct Escrow {
+    pub env: Env,
+    pub client: AidEscrowClient<'static>,
+    pub admin: Address,
+    pub token: Address,
+}
+
+impl Escrow {
+    /// An initialised escrow holding `10 * UNIT` of a fresh token, with all
+    /// auths mocked and the ledger at timestamp 1_000_000.
+    pub fn new() -> Self {
+        let escrow = Self::deploy();
+        escrow.client.init(&escrow.admin);
+        escrow
+            .client
+            .fund(&escrow.token, &escrow.admin, &(10 * UNIT));
+        escrow
+    }
+
+    /// Like [`Escrow::new`], but the escrow is neither initialised nor funded;
+    /// the admin holds the `10 * UNIT` instead.
+    pub fn deploy() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000_000);
+
+        let admin = Address::generate(&env);
+        let token = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        StellarAssetClient::new(&env, &token).mint(&admin, &(10 * UNIT));
+
+        let contract_id = env.register(AidEscrow, ());
+        let client = AidEscrowClient::new(&env, &contract_id);
+
+        Self {
+            env,
+            client,
+            admin,
+            token,
+        }
+    }
+
+    pub fn token_client(&self) -> TokenClient<'static> {
+        TokenClient::new(&self.env, &self.token)
+    }
+
+    pub fn balance(&self, account: &Address) -> i128 {
+        self.token_client().balance(account)
+    }
+}
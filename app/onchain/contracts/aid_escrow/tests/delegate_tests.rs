@@ -1,10 +1,10 @@
 #![cfg(test)]
 
-use aid_escrow::{AidEscrow, AidEscrowClient, Error, PackageStatus};
+use aid_escrow::{AidEscrow, AidEscrowClient, Error, PackageStatus, Role};
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
     token::{StellarAssetClient, TokenClient},
-    Address, Env, Map, String, Vec,
+    Address, Env, Map, String, Symbol, Vec,
 };
 
 const UNIT: i128 = 10_000_000;
@@ -79,10 +79,10 @@ fn test_set_and_query_delegate() {
 
     // Query info
     let info = client.get_delegate_info(&pkg_id);
-    assert!(info.is_some());
-    let (addr, expiry) = info.unwrap();
-    assert_eq!(addr, delegate);
-    assert_eq!(expiry, None);
+    assert_eq!(info.len(), 1);
+    let entry = info.get(0).unwrap();
+    assert_eq!(entry.delegate, delegate);
+    assert_eq!(entry.expires_at, 0);
 }
 
 #[test]
@@ -96,10 +96,10 @@ fn test_set_delegate_with_expiry_and_query() {
     client.set_delegate_with_expiry(&admin, &pkg_id, &delegate, &expires_at);
 
     let info = client.get_delegate_info(&pkg_id);
-    assert!(info.is_some());
-    let (addr, expiry) = info.unwrap();
-    assert_eq!(addr, delegate);
-    assert_eq!(expiry, Some(expires_at));
+    assert_eq!(info.len(), 1);
+    let entry = info.get(0).unwrap();
+    assert_eq!(entry.delegate, delegate);
+    assert_eq!(entry.expires_at, expires_at);
 
     // Before expiry, delegate is returned
     assert_eq!(client.get_delegate(&pkg_id), Some(delegate.clone()));
@@ -134,17 +134,17 @@ fn test_delegate_history_tracking() {
     let delegate2 = Address::generate(&env);
 
     // No history initially
-    let history = client.get_delegate_history(&pkg_id);
+    let history = client.get_delegate_history(&pkg_id, &0, &50);
     assert_eq!(history.len(), 0);
 
     // Set first delegate
     client.set_delegate(&admin, &pkg_id, &delegate);
-    let history = client.get_delegate_history(&pkg_id);
+    let history = client.get_delegate_history(&pkg_id, &0, &50);
     assert_eq!(history.len(), 1);
 
     // Update to second delegate
     client.set_delegate(&admin, &pkg_id, &delegate2);
-    let history = client.get_delegate_history(&pkg_id);
+    let history = client.get_delegate_history(&pkg_id, &0, &50);
     assert_eq!(history.len(), 2);
 
     let first = history.get(0).unwrap();
@@ -198,7 +198,7 @@ fn test_delegate_claim_clears_delegate() {
     assert_eq!(client.get_delegate(&pkg_id), None);
 
     // History should reflect the clearing
-    let history = client.get_delegate_history(&pkg_id);
+    let history = client.get_delegate_history(&pkg_id, &0, &50);
     assert!(history.len() >= 2);
 }
 
@@ -223,6 +223,22 @@ fn test_recipient_claim_also_clears_delegate() {
     assert_eq!(token_client.balance(&recipient), UNIT);
 }
 
+#[test]
+fn test_disburse_clears_delegate() {
+    let (_env, client, admin, recipient, delegate, token_client, _) = setup();
+    let pkg_id = 1;
+
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, pkg_id);
+
+    client.set_delegate(&admin, &pkg_id, &delegate);
+    client.disburse(&pkg_id);
+
+    assert_eq!(client.get_delegate(&pkg_id), None);
+    assert_eq!(client.get_total_claimed(&token), UNIT);
+    assert_eq!(token_client.balance(&recipient), UNIT);
+}
+
 #[test]
 fn test_stranger_cannot_claim_as_delegate() {
     let (env, client, admin, recipient, _delegate, token_client, _) = setup();
@@ -328,12 +344,380 @@ fn test_cleanup_expired_delegates() {
     env.ledger().set_timestamp(now + 100);
 
     // Cleanup
-    let cleaned = client.cleanup_expired_delegates(&admin);
-    assert_eq!(cleaned, 1);
+    let cleanup = client.cleanup_expired_delegates(&admin, &0, &10);
+    assert_eq!(cleanup.removed, 1);
+    assert_eq!(cleanup.next_cursor, 2);
+    assert!(cleanup.done);
 
-    // First delegate should be gone
+    // First delegate should be gone, with the pruning on record
     assert_eq!(client.get_delegate(&1), None);
+    let history = client.get_delegate_history(&1, &0, &10);
+    let pruned = history.get(history.len() - 1).unwrap();
+    assert_eq!(pruned.previous_delegate, Some(delegate1.clone()));
+    assert_eq!(pruned.changed_by, admin);
+    assert_eq!(pruned.reason, Symbol::new(&env, "expired"));
 
     // Second delegate should remain
     assert_eq!(client.get_delegate(&2), Some(delegate2));
 }
+
+#[test]
+fn test_non_admin_cannot_set_delegate() {
+    let (env, client, admin, recipient, delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, 1);
+
+    let stranger = Address::generate(&env);
+    let result = client.try_set_delegate(&stranger, &1, &delegate);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+    let result = client.try_set_delegate_with_expiry(&stranger, &1, &delegate, &0);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+    assert_eq!(client.get_delegate(&1), None);
+}
+
+#[test]
+fn test_delegate_manager_can_set_delegate() {
+    let (env, client, admin, recipient, delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, 1);
+
+    let manager = Address::generate(&env);
+    client.grant_role(&Role::DelegateManager, &manager);
+    client.set_delegate(&manager, &1, &delegate);
+    assert_eq!(client.get_delegate(&1), Some(delegate.clone()));
+
+    let history = client.get_delegate_history(&1, &0, &50);
+    assert_eq!(history.get(0).unwrap().changed_by, manager);
+}
+
+#[test]
+#[cfg(feature = "nominations")]
+fn test_recipient_nomination_requires_acceptance() {
+    let (env, client, admin, recipient, delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, 1);
+
+    let expires_at = env.ledger().timestamp() + 500;
+    client.nominate_delegate(&1, &delegate, &expires_at);
+
+    // Not active until accepted
+    assert_eq!(client.get_delegate(&1), None);
+    let empty_proof: Vec<String> = Vec::new(&env);
+    let result = client.try_claim_with_proof(&1, &delegate, &empty_proof);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    let nomination = client.get_delegate_nomination(&1).unwrap();
+    assert_eq!(nomination.delegate, delegate);
+    assert_eq!(nomination.nominated_by, recipient);
+
+    client.accept_delegation(&1, &delegate);
+    assert_eq!(client.get_delegate(&1), Some(delegate.clone()));
+    assert_eq!(
+        client.get_delegate_info(&1).get(0).unwrap().expires_at,
+        expires_at
+    );
+    assert_eq!(client.get_delegate_nomination(&1), None);
+
+    let history = client.get_delegate_history(&1, &0, &50);
+    assert_eq!(history.len(), 2);
+    assert_eq!(
+        history.get(0).unwrap().reason,
+        Symbol::new(&env, "delegate_nominated")
+    );
+    assert_eq!(history.get(0).unwrap().changed_by, recipient);
+    assert_eq!(
+        history.get(1).unwrap().reason,
+        Symbol::new(&env, "delegate_accepted")
+    );
+    assert_eq!(history.get(1).unwrap().changed_by, delegate);
+
+    client.claim_with_proof(&1, &delegate, &empty_proof);
+    assert_eq!(token_client.balance(&delegate), UNIT);
+}
+
+#[test]
+#[cfg(feature = "nominations")]
+fn test_only_nominee_can_accept() {
+    let (env, client, admin, recipient, delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, 1);
+
+    client.nominate_delegate(&1, &delegate, &0);
+
+    let other = Address::generate(&env);
+    let result = client.try_accept_delegation(&1, &other);
+    assert_eq!(result, Err(Ok(Error::NoPendingNomination)));
+    let result = client.try_accept_delegation(&2, &delegate);
+    assert_eq!(result, Err(Ok(Error::PackageNotFound)));
+}
+
+#[test]
+#[cfg(feature = "nominations")]
+fn test_nomination_can_be_rejected_or_cancelled() {
+    let (env, client, admin, recipient, delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, 1);
+
+    client.nominate_delegate(&1, &delegate, &0);
+    client.reject_delegation(&1, &delegate);
+    assert_eq!(client.get_delegate_nomination(&1), None);
+    assert_eq!(
+        client.try_accept_delegation(&1, &delegate),
+        Err(Ok(Error::NoPendingNomination))
+    );
+
+    client.nominate_delegate(&1, &delegate, &0);
+    client.cancel_delegate_nomination(&1);
+    assert_eq!(client.get_delegate_nomination(&1), None);
+    assert_eq!(client.get_delegate(&1), None);
+
+    let history = client.get_delegate_history(&1, &0, &50);
+    assert_eq!(history.len(), 4);
+    assert_eq!(
+        history.get(1).unwrap().reason,
+        Symbol::new(&env, "nomination_rejected")
+    );
+    assert_eq!(
+        history.get(3).unwrap().reason,
+        Symbol::new(&env, "nomination_cancelled")
+    );
+}
+
+#[test]
+#[cfg(feature = "nominations")]
+fn test_recipient_cannot_nominate_self() {
+    let (_env, client, admin, recipient, _delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, 1);
+
+    let result = client.try_nominate_delegate(&1, &recipient, &0);
+    assert_eq!(result, Err(Ok(Error::InvalidState)));
+}
+
+#[test]
+fn test_recipient_can_revoke_own_delegate() {
+    let (env, client, admin, recipient, delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, 1);
+
+    client.set_delegate(&admin, &1, &delegate);
+    client.revoke_delegate(&recipient, &1);
+    assert_eq!(client.get_delegate(&1), None);
+
+    client.set_delegate(&admin, &1, &delegate);
+    let stranger = Address::generate(&env);
+    let result = client.try_revoke_delegate(&stranger, &1);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+}
+
+#[test]
+#[cfg(feature = "multi-delegates")]
+fn test_multiple_delegates_each_with_own_expiry() {
+    let (env, client, admin, recipient, delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, 1);
+
+    let second = Address::generate(&env);
+    let now = env.ledger().timestamp();
+    client.add_delegate(&admin, &1, &delegate, &(now + 100));
+    client.add_delegate(&admin, &1, &second, &0);
+
+    assert_eq!(
+        client.get_delegates(&1),
+        Vec::from_array(&env, [delegate.clone(), second.clone()])
+    );
+    let info = client.get_delegate_info(&1);
+    assert_eq!(info.len(), 2);
+    assert_eq!(info.get(0).unwrap().expires_at, now + 100);
+    assert_eq!(info.get(1).unwrap().expires_at, 0);
+
+    // First delegate lapses; the second keeps its authority
+    env.ledger().set_timestamp(now + 101);
+    assert_eq!(
+        client.get_delegates(&1),
+        Vec::from_array(&env, [second.clone()])
+    );
+    assert_eq!(client.get_delegate(&1), Some(second.clone()));
+
+    let empty_proof: Vec<String> = Vec::new(&env);
+    let result = client.try_claim_with_proof(&1, &delegate, &empty_proof);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+    client.claim_with_proof(&1, &second, &empty_proof);
+    assert_eq!(token_client.balance(&second), UNIT);
+    assert_eq!(client.get_delegate_info(&1).len(), 0);
+}
+
+#[test]
+#[cfg(feature = "multi-delegates")]
+fn test_remove_single_delegate_keeps_others() {
+    let (env, client, admin, recipient, delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, 1);
+
+    let second = Address::generate(&env);
+    client.add_delegate(&admin, &1, &delegate, &0);
+    client.add_delegate(&admin, &1, &second, &0);
+
+    client.remove_delegate(&recipient, &1, &delegate);
+    assert_eq!(
+        client.get_delegates(&1),
+        Vec::from_array(&env, [second.clone()])
+    );
+
+    let result = client.try_remove_delegate(&recipient, &1, &delegate);
+    assert_eq!(result, Err(Ok(Error::DelegateNotFound)));
+}
+
+#[test]
+#[cfg(feature = "multi-delegates")]
+fn test_delegate_set_is_bounded() {
+    let (env, client, admin, recipient, _delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, 1);
+
+    for _ in 0..5 {
+        client.add_delegate(&admin, &1, &Address::generate(&env), &0);
+    }
+    let result = client.try_add_delegate(&admin, &1, &Address::generate(&env), &0);
+    assert_eq!(result, Err(Ok(Error::DelegateLimitReached)));
+}
+
+#[test]
+#[cfg(feature = "multi-delegates")]
+fn test_threshold_requires_cosigned_delegate_claim() {
+    let (env, client, admin, recipient, delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, 1);
+
+    let second = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    client.add_delegate(&admin, &1, &delegate, &0);
+    client.add_delegate(&admin, &1, &second, &0);
+    client.set_delegate_threshold(&admin, &1, &2);
+    assert_eq!(client.get_delegate_threshold(&1), 2);
+
+    // A lone delegate can no longer claim on its own signature
+    let empty_proof: Vec<String> = Vec::new(&env);
+    let result = client.try_claim_with_proof(&1, &delegate, &empty_proof);
+    assert_eq!(result, Err(Ok(Error::DelegateThresholdNotMet)));
+    let result = client.try_claim_with_delegates(&1, &Vec::from_array(&env, [delegate.clone()]));
+    assert_eq!(result, Err(Ok(Error::DelegateThresholdNotMet)));
+    let result = client.try_claim_with_delegates(
+        &1,
+        &Vec::from_array(&env, [delegate.clone(), delegate.clone()]),
+    );
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+    let result =
+        client.try_claim_with_delegates(&1, &Vec::from_array(&env, [delegate.clone(), stranger]));
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    client.claim_with_delegates(
+        &1,
+        &Vec::from_array(&env, [second.clone(), delegate.clone()]),
+    );
+    assert_eq!(token_client.balance(&second), UNIT);
+    assert_eq!(client.get_package(&1).status, PackageStatus::Claimed);
+    assert_eq!(client.get_delegate_threshold(&1), 1);
+}
+
+#[test]
+#[cfg(feature = "multi-delegates")]
+fn test_threshold_does_not_affect_recipient_claim() {
+    let (env, client, admin, recipient, delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, 1);
+
+    let second = Address::generate(&env);
+    client.add_delegate(&admin, &1, &delegate, &0);
+    client.add_delegate(&admin, &1, &second, &0);
+    client.set_delegate_threshold(&admin, &1, &2);
+
+    let result = client.try_set_delegate_threshold(&admin, &1, &6);
+    assert_eq!(result, Err(Ok(Error::InvalidState)));
+
+    client.claim(&1);
+    assert_eq!(token_client.balance(&recipient), UNIT);
+}
+
+#[test]
+#[cfg(all(feature = "multi-delegates", feature = "nominations"))]
+fn test_accepted_nomination_joins_existing_delegates() {
+    let (env, client, admin, recipient, delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, 1);
+
+    let nominee = Address::generate(&env);
+    client.set_delegate(&admin, &1, &delegate);
+    client.nominate_delegate(&1, &nominee, &0);
+    client.accept_delegation(&1, &nominee);
+
+    assert_eq!(
+        client.get_delegates(&1),
+        Vec::from_array(&env, [delegate.clone(), nominee.clone()])
+    );
+
+    client.revoke_delegate(&recipient, &1);
+    assert_eq!(client.get_delegates(&1).len(), 0);
+}
+
+#[test]
+fn test_delegate_history_is_paged_per_package() {
+    let (env, client, admin, recipient, _delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    create_package(&client, &admin, &recipient, &token, 1);
+    create_package(&client, &admin, &recipient, &token, 2);
+
+    let delegates: Vec<Address> = Vec::from_array(
+        &env,
+        [
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        ],
+    );
+    for d in delegates.iter() {
+        client.set_delegate(&admin, &1, &d);
+    }
+    client.set_delegate(&admin, &2, &delegates.get(0).unwrap());
+
+    assert_eq!(client.get_delegate_history_len(&1), 5);
+    assert_eq!(client.get_delegate_history_len(&2), 1);
+
+    let first = client.get_delegate_history(&1, &0, &2);
+    assert_eq!(first.len(), 2);
+    assert_eq!(
+        first.get(0).unwrap().new_delegate,
+        delegates.get(0).unwrap()
+    );
+
+    let last = client.get_delegate_history(&1, &4, &2);
+    assert_eq!(last.len(), 1);
+    assert_eq!(last.get(0).unwrap().new_delegate, delegates.get(4).unwrap());
+    assert_eq!(client.get_delegate_history(&1, &5, &2).len(), 0);
+}
+
+#[test]
+fn test_cleanup_expired_delegates_resumes_from_cursor() {
+    let (env, client, admin, recipient, _delegate, token_client, _) = setup();
+    let token = token_client.address.clone();
+    let now = env.ledger().timestamp();
+
+    for id in 1..=3u64 {
+        create_package(&client, &admin, &recipient, &token, id);
+        client.set_delegate_with_expiry(&admin, &id, &Address::generate(&env), &(now + 10));
+    }
+    env.ledger().set_timestamp(now + 11);
+
+    let page = client.cleanup_expired_delegates(&admin, &0, &2);
+    assert_eq!(page.removed, 2);
+    assert_eq!(page.next_cursor, 2);
+    assert!(!page.done);
+    assert_eq!(client.get_delegate_info(&3).len(), 1);
+
+    let page = client.cleanup_expired_delegates(&admin, &page.next_cursor, &2);
+    assert_eq!(page.removed, 1);
+    assert!(page.done);
+    assert_eq!(client.get_delegate_info(&3).len(), 0);
+}
@@ -0,0 +1,191 @@
+#![cfg(all(test, feature = "denylist"))]
+
+mod common;
+
+#[cfg(feature = "recovery")]
+use aid_escrow::RecoveryScope;
+use aid_escrow::{Error, PackageStatus, PolicyError, Role};
+use common::{Escrow, UNIT};
+use soroban_sdk::{testutils::Address as _, Address, InvokeError, Map, String, Symbol, Vec};
+
+const SANCTIONS: u32 = 1;
+const FRAUD: u32 = 2;
+
+type DenylistTest = Escrow;
+
+impl DenylistTest {
+    fn create(&self, id: u64, recipient: &Address) -> Result<u64, InvokeError> {
+        self.client
+            .try_create_package(
+                &self.admin,
+                &id,
+                recipient,
+                &UNIT,
+                &self.token,
+                &(self.env.ledger().timestamp() + 86400),
+                &Map::new(&self.env),
+            )
+            .map(|r| r.unwrap())
+            .map_err(|e| e.err().unwrap())
+    }
+
+    fn deny(&self, address: &Address, reason: u32) {
+        self.client.add_to_denylist(&self.admin, address, &reason);
+    }
+}
+
+fn denied() -> InvokeError {
+    InvokeError::from(PolicyError::AddressDenied)
+}
+
+#[test]
+fn denied_recipients_get_no_new_packages() {
+    let t = DenylistTest::new();
+    let allowed = Address::generate(&t.env);
+    let blocked = Address::generate(&t.env);
+    t.deny(&blocked, SANCTIONS);
+
+    assert_eq!(t.create(1, &blocked), Err(denied()));
+    t.create(1, &allowed).unwrap();
+
+    let recipients = Vec::from_array(&t.env, [allowed.clone(), blocked.clone()]);
+    let amounts = Vec::from_array(&t.env, [UNIT, UNIT]);
+    let metadatas: Vec<Map<Symbol, String>> =
+        Vec::from_array(&t.env, [Map::new(&t.env), Map::new(&t.env)]);
+    assert_eq!(
+        t.client.try_batch_create_packages(
+            &t.admin,
+            &recipients,
+            &amounts,
+            &t.token,
+            &86400,
+            &metadatas,
+        ),
+        Err(Err(denied()))
+    );
+}
+
+#[test]
+fn denied_packages_are_frozen_and_restored_on_removal() {
+    let t = DenylistTest::new();
+    let recipient = Address::generate(&t.env);
+    let delegate = Address::generate(&t.env);
+    t.create(1, &recipient).unwrap();
+    t.create(2, &recipient).unwrap();
+    t.client.set_delegate(&t.admin, &2, &delegate);
+
+    t.deny(&recipient, FRAUD);
+    assert_eq!(t.client.try_claim(&1), Err(Err(denied())));
+    assert_eq!(t.client.try_disburse(&1), Err(Err(denied())));
+    #[cfg(feature = "relayers")]
+    assert_eq!(
+        t.client
+            .try_claim_with_relayer(&2, &delegate, &Address::generate(&t.env)),
+        Err(Err(denied()))
+    );
+    assert_eq!(t.client.get_package(&1).status, PackageStatus::Created);
+
+    t.client.remove_from_denylist(&t.admin, &recipient);
+    t.client.claim(&1);
+    assert_eq!(t.balance(&recipient), UNIT);
+
+    // A denied delegate cannot collect for an allowed recipient.
+    t.deny(&delegate, FRAUD);
+    #[cfg(feature = "relayers")]
+    assert_eq!(
+        t.client
+            .try_claim_with_relayer(&2, &delegate, &Address::generate(&t.env)),
+        Err(Err(denied()))
+    );
+}
+
+#[test]
+fn surplus_cannot_be_withdrawn_to_a_denied_address() {
+    let t = DenylistTest::new();
+    let destination = Address::generate(&t.env);
+    t.deny(&destination, SANCTIONS);
+
+    assert_eq!(
+        t.client.try_withdraw_surplus(&destination, &UNIT, &t.token),
+        Err(Err(denied()))
+    );
+    t.client
+        .withdraw_surplus(&Address::generate(&t.env), &UNIT, &t.token);
+}
+
+#[test]
+#[cfg(feature = "recovery")]
+fn recovery_cannot_move_packages_to_a_denied_address() {
+    use soroban_sdk::testutils::Ledger;
+
+    let t = DenylistTest::new();
+    let lost = Address::generate(&t.env);
+    let replacement = Address::generate(&t.env);
+    t.create(1, &lost).unwrap();
+    let scope = RecoveryScope::Package(1);
+
+    t.deny(&replacement, FRAUD);
+    assert_eq!(
+        t.client
+            .try_propose_recovery(&t.admin, &lost, &replacement, &scope),
+        Err(Err(denied()))
+    );
+
+    t.client.remove_from_denylist(&t.admin, &replacement);
+    let id = t
+        .client
+        .propose_recovery(&t.admin, &lost, &replacement, &scope);
+    t.deny(&replacement, FRAUD);
+    t.env
+        .ledger()
+        .set_timestamp(t.env.ledger().timestamp() + t.client.get_recovery_delay());
+    assert_eq!(
+        t.client.try_execute_recovery(&t.admin, &id, &50),
+        Err(Err(denied()))
+    );
+    assert_eq!(t.client.get_package(&1).recipient, lost);
+}
+
+#[test]
+fn compliance_officers_manage_a_paged_denylist() {
+    let t = DenylistTest::new();
+    let officer = Address::generate(&t.env);
+    let outsider = Address::generate(&t.env);
+    let addresses: [Address; 3] = core::array::from_fn(|_| Address::generate(&t.env));
+
+    assert_eq!(
+        t.client
+            .try_add_to_denylist(&officer, &addresses[0], &SANCTIONS),
+        Err(Ok(Error::NotAuthorized))
+    );
+    t.client.grant_role(&Role::Compliance, &officer);
+    for address in &addresses {
+        t.client.add_to_denylist(&officer, address, &SANCTIONS);
+    }
+    t.client.add_to_denylist(&officer, &addresses[1], &FRAUD);
+    assert_eq!(t.client.get_denylist_count(), 3);
+
+    let entry = t.client.get_denylist_entry(&addresses[1]).unwrap();
+    assert_eq!(entry.reason, FRAUD);
+    assert_eq!(entry.added_by, officer);
+    assert!(!t.client.is_denied(&outsider));
+
+    t.client.remove_from_denylist(&officer, &addresses[0]);
+    assert_eq!(
+        t.client.try_remove_from_denylist(&officer, &addresses[0]),
+        Err(Ok(Error::InvalidState))
+    );
+
+    let first = t.client.get_denylist(&0, &1);
+    let second = t.client.get_denylist(&1, &10);
+    assert_eq!(first.len() + second.len(), 2);
+    let listed: Vec<Address> = Vec::from_array(
+        &t.env,
+        [
+            first.get(0).unwrap().address,
+            second.get(0).unwrap().address,
+        ],
+    );
+    assert!(listed.contains(&addresses[1]) && listed.contains(&addresses[2]));
+    assert!(!t.client.is_denied(&addresses[0]));
+}
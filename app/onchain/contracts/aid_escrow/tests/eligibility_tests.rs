@@ -0,0 +1,116 @@
+#![cfg(all(test, feature = "registry"))]
+
+mod common;
+
+use aid_escrow::{PackageStatus, PolicyError};
+use common::{Escrow, UNIT};
+use eligibility_issuer::{EligibilityIssuer, EligibilityIssuerClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, InvokeError, Map, String, Symbol,
+};
+use std::ops::Deref;
+
+struct EligibilityTest {
+    escrow: Escrow,
+    issuer: EligibilityIssuerClient<'static>,
+    campaign: String,
+}
+
+impl Deref for EligibilityTest {
+    type Target = Escrow;
+
+    fn deref(&self) -> &Escrow {
+        &self.escrow
+    }
+}
+
+impl EligibilityTest {
+    fn new() -> Self {
+        let escrow = Escrow::new();
+
+        let issuer_id = escrow.env.register(EligibilityIssuer, ());
+        let issuer = EligibilityIssuerClient::new(&escrow.env, &issuer_id);
+        issuer.init(&escrow.admin);
+
+        let campaign = String::from_str(&escrow.env, "flood");
+        escrow
+            .client
+            .set_eligibility_issuer(&campaign, &Some(issuer_id));
+
+        Self {
+            escrow,
+            issuer,
+            campaign,
+        }
+    }
+
+    fn create(&self, id: u64, recipient: &Address, campaign: &str) -> u64 {
+        let mut metadata = Map::new(&self.env);
+        metadata.set(
+            Symbol::new(&self.env, "campaign_ref"),
+            String::from_str(&self.env, campaign),
+        );
+        self.client.create_package(
+            &self.admin,
+            &id,
+            recipient,
+            &UNIT,
+            &self.token,
+            &(self.env.ledger().timestamp() + 86400),
+            &metadata,
+        )
+    }
+}
+
+#[test]
+fn payouts_need_a_current_credential_from_the_campaign_issuer() {
+    let t = EligibilityTest::new();
+    let holder = Address::generate(&t.env);
+    let unknown = Address::generate(&t.env);
+    let revoked = Address::generate(&t.env);
+    let expired = Address::generate(&t.env);
+    let now = t.env.ledger().timestamp();
+    t.issuer.issue(&holder, &t.campaign, &0);
+    t.issuer.issue(&revoked, &t.campaign, &0);
+    t.issuer.revoke(&revoked, &t.campaign);
+    t.issuer.issue(&expired, &t.campaign, &(now + 10));
+
+    t.create(1, &holder, "flood");
+    t.create(2, &unknown, "flood");
+    t.create(3, &revoked, "flood");
+    t.create(4, &expired, "flood");
+    t.create(5, &unknown, "drought");
+
+    let not_eligible = Err(Err(InvokeError::from(PolicyError::NotEligible)));
+    t.env.ledger().set_timestamp(now + 11);
+    t.client.claim(&1);
+    assert_eq!(t.client.try_claim(&2), not_eligible);
+    assert_eq!(t.client.try_claim(&3), not_eligible);
+    assert_eq!(t.client.try_disburse(&4), not_eligible);
+    // Campaigns without an issuer are not checked.
+    t.client.claim(&5);
+
+    t.client.set_eligibility_issuer(&t.campaign, &None);
+    t.client.claim(&2);
+    assert_eq!(t.client.get_package(&2).status, PackageStatus::Claimed);
+}
+
+#[test]
+fn check_eligibility_reports_what_a_payout_would_decide() {
+    let t = EligibilityTest::new();
+    let holder = Address::generate(&t.env);
+    let unknown = Address::generate(&t.env);
+    t.issuer.issue(&holder, &t.campaign, &0);
+    t.create(1, &holder, "flood");
+    t.create(2, &unknown, "flood");
+    t.create(3, &unknown, "drought");
+
+    assert!(t.client.check_eligibility(&1));
+    assert!(!t.client.check_eligibility(&2));
+    assert!(t.client.check_eligibility(&3));
+    assert_eq!(
+        t.client.get_eligibility_issuer(&t.campaign),
+        Some(t.issuer.address.clone())
+    );
+}
@@ -0,0 +1,257 @@
+#![cfg(all(test, feature = "registry"))]
+
+mod common;
+
+#[cfg(feature = "recovery")]
+use aid_escrow::RecoveryScope;
+use aid_escrow::{Enrolment, EnrolmentPolicy, PolicyError};
+use common::{Escrow, UNIT};
+use soroban_sdk::{testutils::Address as _, Address, InvokeError, Map, String, Symbol, Vec};
+
+type EnrolmentTest = Escrow;
+
+impl EnrolmentTest {
+    fn campaign(&self, name: &str) -> String {
+        String::from_str(&self.env, name)
+    }
+
+    fn metadata(&self, campaign: &str) -> Map<Symbol, String> {
+        let mut metadata = Map::new(&self.env);
+        metadata.set(
+            Symbol::new(&self.env, "campaign_ref"),
+            self.campaign(campaign),
+        );
+        metadata
+    }
+
+    fn create(&self, id: u64, recipient: &Address, campaign: &str) -> Result<u64, InvokeError> {
+        self.client
+            .try_create_package(
+                &self.admin,
+                &id,
+                recipient,
+                &UNIT,
+                &self.token,
+                &(self.env.ledger().timestamp() + 86400),
+                &self.metadata(campaign),
+            )
+            .map(|r| r.unwrap())
+            .map_err(|e| e.err().unwrap())
+    }
+}
+
+#[test]
+fn one_package_per_recipient_in_single_and_batch_creation() {
+    let t = EnrolmentTest::new();
+    t.client.set_enrolment_policy(
+        &t.campaign("flood"),
+        &Some(EnrolmentPolicy {
+            max_packages: 1,
+            max_amount: 0,
+        }),
+    );
+    let recipient = Address::generate(&t.env);
+    let limit_reached = Err(PolicyError::EnrolmentLimitReached.into());
+
+    assert_eq!(t.create(1, &recipient, "flood"), Ok(1));
+    assert_eq!(t.create(2, &recipient, "flood"), limit_reached);
+    assert_eq!(t.create(2, &recipient, "drought"), Ok(2));
+
+    let other = Address::generate(&t.env);
+    assert_eq!(
+        t.client.try_batch_create_packages(
+            &t.admin,
+            &Vec::from_array(&t.env, [other.clone(), other.clone()]),
+            &Vec::from_array(&t.env, [UNIT, UNIT]),
+            &t.token,
+            &3600,
+            &Vec::from_array(&t.env, [t.metadata("flood"), t.metadata("flood")]),
+        ),
+        Err(Err(PolicyError::EnrolmentLimitReached.into()))
+    );
+
+    // Cancelling the package gives the slot back.
+    t.client.cancel_package(&1);
+    assert_eq!(
+        t.client.get_enrolment(&t.campaign("flood"), &recipient),
+        Enrolment::default()
+    );
+    assert_eq!(t.create(3, &recipient, "flood"), Ok(3));
+}
+
+#[test]
+fn total_amount_per_recipient_is_capped() {
+    let t = EnrolmentTest::new();
+    let recipient = Address::generate(&t.env);
+    t.create(1, &recipient, "flood").unwrap();
+
+    // Packages created before the policy count against it.
+    t.client.set_enrolment_policy(
+        &t.campaign("flood"),
+        &Some(EnrolmentPolicy {
+            max_packages: 0,
+            max_amount: 2 * UNIT,
+        }),
+    );
+    assert_eq!(t.create(2, &recipient, "flood"), Ok(2));
+    assert_eq!(
+        t.create(3, &recipient, "flood"),
+        Err(PolicyError::EnrolmentLimitReached.into())
+    );
+    assert_eq!(
+        t.client.get_enrolment(&t.campaign("flood"), &recipient),
+        Enrolment {
+            packages: 2,
+            amount: 2 * UNIT,
+        }
+    );
+
+    t.client.set_enrolment_policy(&t.campaign("flood"), &None);
+    assert_eq!(t.create(3, &recipient, "flood"), Ok(3));
+}
+
+#[test]
+#[cfg(feature = "amendments")]
+fn moving_a_package_into_a_campaign_rechecks_its_limits() {
+    let t = EnrolmentTest::new();
+    t.client.set_enrolment_policy(
+        &t.campaign("flood"),
+        &Some(EnrolmentPolicy {
+            max_packages: 1,
+            max_amount: 0,
+        }),
+    );
+    let recipient = Address::generate(&t.env);
+    t.create(1, &recipient, "flood").unwrap();
+    t.create(2, &recipient, "decoy").unwrap();
+
+    let campaign_ref = Symbol::new(&t.env, "campaign_ref");
+    let retag = t.metadata("flood");
+    assert_eq!(
+        t.client
+            .try_update_package_metadata(&t.admin, &2, &retag, &Vec::new(&t.env)),
+        Err(Err(PolicyError::EnrolmentLimitReached.into()))
+    );
+
+    // Moving it out of the campaign releases its old allocation, and
+    // cancelling it afterwards does not touch the other package's.
+    t.client.update_package_metadata(
+        &t.admin,
+        &1,
+        &Map::new(&t.env),
+        &Vec::from_array(&t.env, [campaign_ref]),
+    );
+    assert_eq!(
+        t.client.get_enrolment(&t.campaign("flood"), &recipient),
+        Enrolment::default()
+    );
+    t.client
+        .update_package_metadata(&t.admin, &2, &retag, &Vec::new(&t.env));
+    t.client.cancel_package(&1);
+    assert_eq!(
+        t.client.get_enrolment(&t.campaign("flood"), &recipient),
+        Enrolment {
+            packages: 1,
+            amount: UNIT,
+        }
+    );
+    assert_eq!(
+        t.client.get_enrolment(&t.campaign("decoy"), &recipient),
+        Enrolment::default()
+    );
+}
+
+#[test]
+#[cfg(feature = "recovery")]
+fn recovery_moves_enrolments_to_the_new_recipient() {
+    use soroban_sdk::testutils::Ledger;
+
+    let t = EnrolmentTest::new();
+    t.client.set_enrolment_policy(
+        &t.campaign("flood"),
+        &Some(EnrolmentPolicy {
+            max_packages: 1,
+            max_amount: 0,
+        }),
+    );
+    let lost = Address::generate(&t.env);
+    let replacement = Address::generate(&t.env);
+    t.create(1, &lost, "flood").unwrap();
+
+    let id = t
+        .client
+        .propose_recovery(&t.admin, &lost, &replacement, &RecoveryScope::Package(1));
+    t.env
+        .ledger()
+        .set_timestamp(t.client.get_recovery(&id).executable_at);
+    t.client.execute_recovery(&t.admin, &id, &50);
+
+    assert_eq!(
+        t.client.get_enrolment(&t.campaign("flood"), &lost),
+        Enrolment::default()
+    );
+    assert_eq!(
+        t.client.get_enrolment(&t.campaign("flood"), &replacement),
+        Enrolment {
+            packages: 1,
+            amount: UNIT,
+        }
+    );
+    assert_eq!(
+        t.create(2, &replacement, "flood"),
+        Err(PolicyError::EnrolmentLimitReached.into())
+    );
+    assert_eq!(t.create(2, &lost, "flood"), Ok(2));
+}
+
+#[test]
+#[cfg(feature = "hash-locks")]
+fn hash_locked_packages_count_against_the_payout_address() {
+    use soroban_sdk::{testutils::Ledger, xdr::ToXdr, Bytes, BytesN};
+
+    let t = EnrolmentTest::new();
+    t.client.set_enrolment_policy(
+        &t.campaign("flood"),
+        &Some(EnrolmentPolicy {
+            max_packages: 1,
+            max_amount: 0,
+        }),
+    );
+    let secret = Bytes::from_slice(&t.env, b"483-209-117");
+    let claim_hash: BytesN<32> = t.env.crypto().sha256(&secret).into();
+    for id in [1, 2] {
+        t.client.create_hashlocked_package(
+            &t.admin,
+            &id,
+            &claim_hash,
+            &UNIT,
+            &t.token,
+            &(t.env.ledger().timestamp() + 86400),
+            &t.metadata("flood"),
+        );
+    }
+
+    let wallet = Address::generate(&t.env);
+    let claim = |id: u64| {
+        let mut data = secret.clone();
+        data.append(&wallet.clone().to_xdr(&t.env));
+        let commitment: BytesN<32> = t.env.crypto().sha256(&data).into();
+        t.client.commit_secret_claim(&id, &wallet, &commitment);
+        let seq = t.env.ledger().sequence();
+        t.env.ledger().set_sequence_number(seq + 1);
+        t.client
+            .try_claim_with_secret(&id, &secret, &wallet)
+            .map(|r| r.unwrap())
+            .map_err(|e| e.err().unwrap())
+    };
+
+    assert_eq!(claim(1), Ok(()));
+    assert_eq!(
+        t.client.get_enrolment(&t.campaign("flood"), &wallet),
+        Enrolment {
+            packages: 1,
+            amount: UNIT,
+        }
+    );
+    assert_eq!(claim(2), Err(PolicyError::EnrolmentLimitReached.into()));
+}
@@ -2,7 +2,7 @@
 
 #![cfg(test)]
 
-use aid_escrow::{AidEscrow, AidEscrowClient};
+use aid_escrow::{AidEscrow, AidEscrowClient, Config, PackageStatus};
 use soroban_sdk::{
     testutils::{Address as _, Events, Ledger},
     token::{StellarAssetClient, TokenClient},
@@ -51,6 +51,12 @@ fn last_event_data(env: &Env, contract_id: &Address, topic: &str) -> Val {
     );
 }
 
+fn data_u32(env: &Env, data: &Val, field: &str) -> u32 {
+    let map = soroban_sdk::Map::<Symbol, Val>::try_from_val(env, data).unwrap();
+    let val = map.get(sym(env, field)).expect("missing field");
+    u32::try_from_val(env, &val).expect("not u32")
+}
+
 fn data_u64(env: &Env, data: &Val, field: &str) -> u64 {
     let map = soroban_sdk::Map::<Symbol, Val>::try_from_val(env, data).unwrap();
     let val = map.get(sym(env, field)).expect("missing field");
@@ -81,6 +87,11 @@ fn data_vec_u64(env: &Env, data: &Val, field: &str) -> soroban_sdk::Vec<u64> {
     soroban_sdk::Vec::<u64>::try_from_val(env, &val).expect("not Vec<u64>")
 }
 
+fn data_field(env: &Env, data: &Val, field: &str) -> Val {
+    let map = soroban_sdk::Map::<Symbol, Val>::try_from_val(env, data).unwrap();
+    map.get(sym(env, field)).expect("missing field")
+}
+
 fn assert_field_exists(env: &Env, data: &Val, field: &str) {
     let map = soroban_sdk::Map::<Symbol, Val>::try_from_val(env, data).unwrap();
     assert!(
@@ -230,6 +241,53 @@ fn test_package_disbursed_event() {
     );
 }
 
+#[test]
+#[cfg(feature = "relayers")]
+fn test_package_claimed_by_relayer_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    token_admin_client.mint(&admin, &(10 * UNIT));
+    client.fund(&token_client.address, &admin, &(5 * UNIT));
+
+    let mut metadata = Map::new(&env);
+    metadata.set(
+        sym(&env, "receipt_hash"),
+        soroban_sdk::String::from_str(&env, "rcpt-1"),
+    );
+    client.create_package(
+        &admin,
+        &0u64,
+        &recipient,
+        &UNIT,
+        &token_client.address,
+        &(env.ledger().timestamp() + 86400),
+        &metadata,
+    );
+    client.claim_with_relayer(&0u64, &recipient, &relayer);
+
+    let data = last_event_data(&env, &contract_id, "package_claimed_by_relayer");
+    assert_eq!(data_u64(&env, &data, "package_id"), 0);
+    assert_eq!(data_address(&env, &data, "recipient"), recipient);
+    assert_eq!(data_address(&env, &data, "relayer"), relayer);
+    assert_eq!(data_i128(&env, &data, "amount"), UNIT);
+    assert_eq!(data_i128(&env, &data, "relayer_fee"), 0);
+    assert_eq!(data_i128(&env, &data, "net_amount"), UNIT);
+    assert_eq!(
+        data_string(&env, &data, "receipt_hash"),
+        soroban_sdk::String::from_str(&env, "rcpt-1")
+    );
+    assert_eq!(client.get_total_claimed(&token_client.address), UNIT);
+}
+
 #[test]
 fn test_package_revoked_event() {
     let env = Env::default();
@@ -400,6 +458,8 @@ fn test_surplus_withdrawn_event() {
     assert_eq!(data_address(&env, &data, "to"), recipient);
     assert_eq!(data_address(&env, &data, "token"), token_client.address);
     assert_eq!(data_i128(&env, &data, "amount"), UNIT);
+    assert_eq!(data_address(&env, &data, "admin"), admin);
+    assert_field_exists(&env, &data, "timestamp");
 }
 
 #[test]
@@ -416,6 +476,7 @@ fn test_contract_paused_event() {
 
     let data = last_event_data(&env, &contract_id, "contract_paused_event");
     assert_eq!(data_address(&env, &data, "admin"), admin);
+    assert_field_exists(&env, &data, "timestamp");
 }
 
 #[test]
@@ -593,8 +654,17 @@ fn test_delegate_claimed_event() {
     assert_eq!(data_address(&env, &data, "recipient"), recipient);
     assert_eq!(data_address(&env, &data, "delegate"), delegate);
     assert_eq!(data_i128(&env, &data, "amount"), UNIT);
+    assert_eq!(data_i128(&env, &data, "paid_to_delegate"), UNIT);
+    assert_eq!(data_i128(&env, &data, "paid_to_recipient"), 0);
+    assert_field_exists(&env, &data, "payout_policy");
     assert_field_exists(&env, &data, "timestamp");
 
+    // PackageClaimed names the package recipient and the delegate as actor
+    let claimed = last_event_data(&env, &contract_id, "package_claimed");
+    assert_eq!(data_address(&env, &claimed, "recipient"), recipient);
+    assert_eq!(data_address(&env, &claimed, "actor"), delegate);
+    assert_eq!(data_i128(&env, &claimed, "paid_to_delegate"), UNIT);
+
     // Also check that DelegateRevoked event was emitted (delegate is cleared after claim)
     let revoked_data = last_event_data(&env, &contract_id, "delegate_revoked");
     assert_eq!(data_u64(&env, &revoked_data, "package_id"), 42);
@@ -644,3 +714,504 @@ fn test_token_removed_event() {
     assert_eq!(data_address(&env, &data, "token"), token_client.address);
     assert_field_exists(&env, &data, "timestamp");
 }
+
+#[test]
+fn test_package_status_changed_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    token_admin_client.mint(&admin, &(10 * UNIT));
+    client.fund(&token_client.address, &admin, &(5 * UNIT));
+
+    client.create_package(
+        &admin,
+        &0u64,
+        &recipient,
+        &UNIT,
+        &token_client.address,
+        &(env.ledger().timestamp() + 86400),
+        &Map::new(&env),
+    );
+    let data = last_event_data(&env, &contract_id, "package_status_changed");
+    assert_eq!(data_u64(&env, &data, "package_id"), 0);
+    assert!(data_field(&env, &data, "from").is_void());
+    assert_eq!(data_u32(&env, &data, "to"), PackageStatus::Created as u32);
+    assert_eq!(data_symbol(&env, &data, "reason"), sym(&env, "created"));
+    assert_eq!(data_address(&env, &data, "actor"), admin);
+
+    client.claim(&0u64);
+    let data = last_event_data(&env, &contract_id, "package_status_changed");
+    assert_eq!(data_u32(&env, &data, "from"), PackageStatus::Created as u32);
+    assert_eq!(data_u32(&env, &data, "to"), PackageStatus::Claimed as u32);
+    assert_eq!(data_symbol(&env, &data, "reason"), sym(&env, "claimed"));
+    assert_eq!(data_address(&env, &data, "actor"), recipient);
+    assert_eq!(
+        data_u32(&env, &data, "schema_version"),
+        client.get_event_schema_version()
+    );
+}
+
+#[test]
+fn test_cancel_package_emits_package_cancelled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let (token_client, token_admin_client) = setup_token(&env, &admin);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    token_admin_client.mint(&admin, &(10 * UNIT));
+    client.fund(&token_client.address, &admin, &(5 * UNIT));
+
+    client.create_package(
+        &admin,
+        &0u64,
+        &recipient,
+        &UNIT,
+        &token_client.address,
+        &(env.ledger().timestamp() + 86400),
+        &Map::new(&env),
+    );
+    client.cancel_package(&0u64);
+
+    let data = last_event_data(&env, &contract_id, "package_cancelled");
+    assert_eq!(data_u64(&env, &data, "package_id"), 0);
+    assert_eq!(data_address(&env, &data, "actor"), admin);
+
+    let data = last_event_data(&env, &contract_id, "package_status_changed");
+    assert_eq!(data_u32(&env, &data, "to"), PackageStatus::Cancelled as u32);
+    assert_eq!(data_symbol(&env, &data, "reason"), sym(&env, "cancelled"));
+}
+
+#[test]
+fn test_admin_mutation_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let distributor = Address::generate(&env);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    let data = last_event_data(&env, &contract_id, "contract_initialized");
+    assert_eq!(data_address(&env, &data, "admin"), admin);
+    assert_eq!(data_u32(&env, &data, "version"), 1);
+
+    client.add_distributor(&distributor);
+    let data = last_event_data(&env, &contract_id, "distributor_added");
+    assert_eq!(data_address(&env, &data, "distributor"), distributor);
+
+    client.remove_distributor(&distributor);
+    let data = last_event_data(&env, &contract_id, "distributor_removed");
+    assert_eq!(data_address(&env, &data, "distributor"), distributor);
+    assert_eq!(data_address(&env, &data, "admin"), admin);
+
+    client.set_config(&Config {
+        min_amount: 5,
+        max_expires_in: 100,
+        allowed_tokens: Vec::new(&env),
+    });
+    let data = last_event_data(&env, &contract_id, "config_updated");
+    assert_eq!(data_i128(&env, &data, "min_amount"), 5);
+    assert_eq!(data_u64(&env, &data, "max_expires_in"), 100);
+
+    client.migrate(&2);
+    let data = last_event_data(&env, &contract_id, "contract_migrated");
+    assert_eq!(data_u32(&env, &data, "from_version"), 1);
+    assert_eq!(data_u32(&env, &data, "to_version"), 2);
+}
+
+#[test]
+#[cfg(feature = "metadata-rules")]
+fn test_metadata_rules_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    client.set_metadata_rules(&aid_escrow::MetadataRules {
+        allowed_keys: Vec::from_array(&env, [sym(&env, "note")]),
+        max_keys: 4,
+        max_value_len: 64,
+    });
+    let data = last_event_data(&env, &contract_id, "metadata_rules_set");
+    assert_eq!(data_u32(&env, &data, "max_keys"), 4);
+    assert_eq!(data_u32(&env, &data, "max_value_len"), 64);
+    assert_eq!(data_address(&env, &data, "admin"), admin);
+    assert_field_exists(&env, &data, "allowed_keys");
+
+    let campaign = soroban_sdk::String::from_str(&env, "flood");
+    client.set_required_metadata(&campaign, &Vec::from_array(&env, [sym(&env, "region")]));
+    let data = last_event_data(&env, &contract_id, "required_metadata_set");
+    assert_eq!(data_string(&env, &data, "campaign"), campaign);
+    assert_field_exists(&env, &data, "keys");
+}
+
+#[test]
+#[cfg(feature = "amendments")]
+fn test_package_metadata_updated_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let (token_client, token_admin) = setup_token(&env, &admin);
+    token_admin.mint(&admin, &10_000_000);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    client.fund(&token_client.address, &admin, &10_000_000);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &10_000_000,
+        &token_client.address,
+        &(env.ledger().timestamp() + 1000),
+        &Map::new(&env),
+    );
+
+    let mut set = Map::new(&env);
+    set.set(
+        sym(&env, "receipt_hash"),
+        soroban_sdk::String::from_str(&env, "r-1"),
+    );
+    client.update_package_metadata(&admin, &1, &set, &Vec::new(&env));
+
+    let data = last_event_data(&env, &contract_id, "package_metadata_updated");
+    assert_eq!(data_u64(&env, &data, "package_id"), 1);
+    assert_eq!(data_address(&env, &data, "actor"), admin);
+    assert_eq!(data_u32(&env, &data, "amendment"), 0);
+    assert_field_exists(&env, &data, "set_keys");
+    assert_field_exists(&env, &data, "removed_keys");
+}
+
+#[test]
+#[cfg(feature = "receipts")]
+fn test_receipt_acknowledged_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let (token_client, token_admin) = setup_token(&env, &admin);
+    token_admin.mint(&admin, &10_000_000);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    client.fund(&token_client.address, &admin, &10_000_000);
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &10_000_000,
+        &token_client.address,
+        &(env.ledger().timestamp() + 1000),
+        &Map::new(&env),
+    );
+    client.claim(&1);
+
+    let receipt_hash = soroban_sdk::String::from_str(&env, "r-1");
+    client.acknowledge_receipt(&recipient, &1, &receipt_hash, &5);
+
+    let data = last_event_data(&env, &contract_id, "receipt_acknowledged");
+    assert_eq!(data_u64(&env, &data, "package_id"), 1);
+    assert_eq!(data_address(&env, &data, "by"), recipient);
+    assert_eq!(data_string(&env, &data, "receipt_hash"), receipt_hash);
+    assert_eq!(data_u32(&env, &data, "rating"), 5);
+}
+
+#[test]
+#[cfg(feature = "merchants")]
+fn test_merchant_and_redemption_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    let (token_client, token_admin) = setup_token(&env, &admin);
+    token_admin.mint(&admin, &10_000_000);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    client.fund(&token_client.address, &admin, &10_000_000);
+
+    client.add_merchant(&merchant, &Vec::from_array(&env, [sym(&env, "food")]));
+    let data = last_event_data(&env, &contract_id, "merchant_added");
+    assert_eq!(data_address(&env, &data, "merchant"), merchant);
+    assert_eq!(data_address(&env, &data, "admin"), admin);
+    assert_field_exists(&env, &data, "categories");
+
+    client.set_merchant_suspended(&merchant, &true);
+    let data = last_event_data(&env, &contract_id, "merchant_updated");
+    assert_eq!(data_address(&env, &data, "merchant"), merchant);
+    assert!(bool::try_from_val(&env, &data_field(&env, &data, "suspended")).unwrap());
+    client.set_merchant_suspended(&merchant, &false);
+
+    let terms = aid_escrow::PackageTerms {
+        restricted: true,
+        ..Default::default()
+    };
+    client.create_package_with_terms(
+        &admin,
+        &1,
+        &recipient,
+        &10_000_000,
+        &token_client.address,
+        &(env.ledger().timestamp() + 1000),
+        &terms,
+        &Map::new(&env),
+    );
+    client.redeem(&1, &merchant, &4_000_000);
+
+    let data = last_event_data(&env, &contract_id, "package_redeemed");
+    assert_eq!(data_u64(&env, &data, "package_id"), 1);
+    assert_eq!(data_address(&env, &data, "recipient"), recipient);
+    assert_eq!(data_address(&env, &data, "merchant"), merchant);
+    assert_eq!(data_i128(&env, &data, "amount"), 4_000_000);
+    assert_eq!(data_i128(&env, &data, "net_amount"), 4_000_000);
+    assert_eq!(data_i128(&env, &data, "remaining"), 6_000_000);
+    assert_eq!(
+        Option::<Symbol>::try_from_val(&env, &data_field(&env, &data, "category")).unwrap(),
+        Some(sym(&env, "food"))
+    );
+}
+
+#[test]
+#[cfg(feature = "registry")]
+fn test_eligibility_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let issuer = env.register(eligibility_issuer::EligibilityIssuer, ());
+    let (token_client, token_admin) = setup_token(&env, &admin);
+    token_admin.mint(&admin, &10_000_000);
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    client.fund(&token_client.address, &admin, &10_000_000);
+
+    let campaign = soroban_sdk::String::from_str(&env, "flood");
+    client.set_eligibility_issuer(&campaign, &Some(issuer.clone()));
+    let data = last_event_data(&env, &contract_id, "eligibility_issuer_set");
+    assert_eq!(data_string(&env, &data, "campaign"), campaign);
+    assert_eq!(data_address(&env, &data, "issuer"), issuer);
+    assert_eq!(data_address(&env, &data, "admin"), admin);
+
+    let mut metadata = Map::new(&env);
+    metadata.set(sym(&env, "campaign_ref"), campaign.clone());
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &10_000_000,
+        &token_client.address,
+        &(env.ledger().timestamp() + 1000),
+        &metadata,
+    );
+    assert!(!client.check_eligibility(&1));
+
+    let data = last_event_data(&env, &contract_id, "eligibility_check_failed");
+    assert_eq!(data_u64(&env, &data, "package_id"), 1);
+    assert_eq!(data_address(&env, &data, "recipient"), recipient);
+    assert_eq!(data_string(&env, &data, "campaign"), campaign);
+    assert_eq!(data_address(&env, &data, "issuer"), issuer);
+}
+
+#[test]
+#[cfg(feature = "registry")]
+fn test_beneficiary_registry_set_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let registry = Address::generate(&env);
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    client.set_beneficiary_registry(&Some(aid_escrow::BeneficiaryPolicy {
+        registry,
+        require_registered: true,
+    }));
+    let data = last_event_data(&env, &contract_id, "beneficiary_registry_set");
+    assert_eq!(data_address(&env, &data, "admin"), admin);
+    assert_field_exists(&env, &data, "policy");
+}
+
+#[test]
+#[cfg(feature = "registry")]
+fn test_enrolment_policy_set_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    let campaign = soroban_sdk::String::from_str(&env, "flood");
+    client.set_enrolment_policy(
+        &campaign,
+        &Some(aid_escrow::EnrolmentPolicy {
+            max_packages: 1,
+            max_amount: 0,
+        }),
+    );
+    let data = last_event_data(&env, &contract_id, "enrolment_policy_set");
+    assert_eq!(data_string(&env, &data, "campaign"), campaign);
+    assert_eq!(data_address(&env, &data, "admin"), admin);
+    assert_field_exists(&env, &data, "policy");
+}
+
+#[test]
+#[cfg(feature = "benefit-caps")]
+fn test_benefit_cap_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let (token_client, token_admin) = setup_token(&env, &admin);
+    token_admin.mint(&admin, &(10 * UNIT));
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    client.fund(&token_client.address, &admin, &(10 * UNIT));
+
+    client.set_benefit_cap(
+        &token_client.address,
+        &Some(aid_escrow::BenefitCap {
+            amount: 3 * UNIT,
+            window: 86400,
+            mode: aid_escrow::CapMode::PayPartial,
+        }),
+    );
+    let data = last_event_data(&env, &contract_id, "benefit_cap_set");
+    assert_eq!(data_address(&env, &data, "token"), token_client.address);
+    assert_eq!(data_address(&env, &data, "admin"), admin);
+    assert_field_exists(&env, &data, "cap");
+
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &(5 * UNIT),
+        &token_client.address,
+        &(env.ledger().timestamp() + 1000),
+        &Map::new(&env),
+    );
+    client.claim(&1);
+
+    let data = last_event_data(&env, &contract_id, "benefit_cap_applied");
+    assert_eq!(data_u64(&env, &data, "package_id"), 1);
+    assert_eq!(data_address(&env, &data, "recipient"), recipient);
+    assert_eq!(data_i128(&env, &data, "requested"), 5 * UNIT);
+    assert_eq!(data_i128(&env, &data, "paid"), 3 * UNIT);
+    assert_eq!(data_i128(&env, &data, "withheld"), 2 * UNIT);
+}
+
+#[test]
+#[cfg(feature = "denylist")]
+fn test_denylist_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let address = Address::generate(&env);
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    client.add_to_denylist(&admin, &address, &7);
+    let data = last_event_data(&env, &contract_id, "denylist_entry_added");
+    assert_eq!(data_address(&env, &data, "address"), address);
+    assert_eq!(data_u32(&env, &data, "reason"), 7);
+    assert_eq!(data_address(&env, &data, "operator"), admin);
+
+    client.remove_from_denylist(&admin, &address);
+    let data = last_event_data(&env, &contract_id, "denylist_entry_removed");
+    assert_eq!(data_address(&env, &data, "address"), address);
+    assert_eq!(data_u32(&env, &data, "reason"), 7);
+    assert_field_exists(&env, &data, "timestamp");
+}
+
+#[test]
+#[cfg(feature = "recovery")]
+fn test_recovery_delay_set_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+
+    client.set_recovery_delay(&86400);
+    let data = last_event_data(&env, &contract_id, "recovery_delay_set");
+    assert_eq!(data_u64(&env, &data, "old"), 3 * 86400);
+    assert_eq!(data_u64(&env, &data, "new"), 86400);
+    assert_eq!(data_address(&env, &data, "admin"), admin);
+    assert_field_exists(&env, &data, "timestamp");
+}
+
+#[test]
+fn test_delegate_removed_event_on_cleanup() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    let (token_client, token_admin) = setup_token(&env, &admin);
+    token_admin.mint(&admin, &(10 * UNIT));
+
+    let contract_id = env.register(AidEscrow, ());
+    let client = AidEscrowClient::new(&env, &contract_id);
+    client.init(&admin);
+    client.fund(&token_client.address, &admin, &(10 * UNIT));
+    client.create_package(
+        &admin,
+        &1,
+        &recipient,
+        &UNIT,
+        &token_client.address,
+        &(env.ledger().timestamp() + 1000),
+        &Map::new(&env),
+    );
+
+    let now = env.ledger().timestamp();
+    client.set_delegate_with_expiry(&admin, &1, &delegate, &(now + 10));
+    env.ledger().set_timestamp(now + 11);
+    client.cleanup_expired_delegates(&admin, &0, &10);
+
+    let data = last_event_data(&env, &contract_id, "delegate_removed");
+    assert_eq!(data_u64(&env, &data, "package_id"), 1);
+    assert_eq!(data_address(&env, &data, "recipient"), recipient);
+    assert_eq!(data_address(&env, &data, "delegate"), delegate);
+    assert_eq!(data_symbol(&env, &data, "reason"), sym(&env, "expired"));
+    assert_eq!(data_address(&env, &data, "actor"), admin);
+}
@@ -0,0 +1,192 @@
+#![cfg(all(test, feature = "hash-locks"))]
+
+mod common;
+
+use aid_escrow::{Error, PackageStatus};
+use common::{Escrow, UNIT};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Map, String, Symbol,
+};
+
+type HashLockTest = Escrow;
+
+impl HashLockTest {
+    fn secret(&self) -> Bytes {
+        Bytes::from_slice(&self.env, b"483-209-117")
+    }
+
+    fn claim_hash(&self, secret: &Bytes) -> BytesN<32> {
+        self.env.crypto().sha256(secret).into()
+    }
+
+    fn commitment(&self, secret: &Bytes, payout: &Address) -> BytesN<32> {
+        let mut data = secret.clone();
+        data.append(&payout.clone().to_xdr(&self.env));
+        self.env.crypto().sha256(&data).into()
+    }
+
+    fn create_package(&self, id: u64, expires_at: u64, metadata: &Map<Symbol, String>) {
+        let hash = self.claim_hash(&self.secret());
+        self.client.create_hashlocked_package(
+            &self.admin,
+            &id,
+            &hash,
+            &UNIT,
+            &self.token,
+            &expires_at,
+            metadata,
+        );
+    }
+
+    fn next_ledger(&self) {
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 1);
+    }
+}
+
+#[test]
+fn commit_then_reveal_pays_any_address() {
+    let t = HashLockTest::new();
+    t.create_package(1, 0, &Map::new(&t.env));
+    let wallet = Address::generate(&t.env);
+
+    assert_eq!(t.client.get_claim_hash(&1), Some(t.claim_hash(&t.secret())));
+
+    t.client
+        .commit_secret_claim(&1, &wallet, &t.commitment(&t.secret(), &wallet));
+    t.next_ledger();
+    t.client.claim_with_secret(&1, &t.secret(), &wallet);
+
+    let package = t.client.get_package(&1);
+    assert_eq!(package.status, PackageStatus::Claimed);
+    assert_eq!(package.recipient, wallet);
+    assert_eq!(t.balance(&wallet), UNIT);
+    assert_eq!(t.client.get_total_locked(&t.token), 0);
+}
+
+#[test]
+fn reveal_in_same_ledger_as_commit_is_rejected() {
+    let t = HashLockTest::new();
+    t.create_package(1, 0, &Map::new(&t.env));
+    let wallet = Address::generate(&t.env);
+
+    t.client
+        .commit_secret_claim(&1, &wallet, &t.commitment(&t.secret(), &wallet));
+    let result = t.client.try_claim_with_secret(&1, &t.secret(), &wallet);
+    assert_eq!(result, Err(Ok(Error::CommitmentTooRecent)));
+}
+
+#[test]
+fn front_runner_without_commitment_cannot_claim() {
+    let t = HashLockTest::new();
+    t.create_package(1, 0, &Map::new(&t.env));
+    let wallet = Address::generate(&t.env);
+    let attacker = Address::generate(&t.env);
+
+    t.client
+        .commit_secret_claim(&1, &wallet, &t.commitment(&t.secret(), &wallet));
+    t.next_ledger();
+
+    // The attacker saw the secret in the reveal transaction but never committed.
+    let result = t.client.try_claim_with_secret(&1, &t.secret(), &attacker);
+    assert_eq!(result, Err(Ok(Error::CommitmentNotFound)));
+
+    // Re-using the victim's commitment under another address does not match either.
+    t.client
+        .commit_secret_claim(&1, &attacker, &t.commitment(&t.secret(), &wallet));
+    t.next_ledger();
+    let result = t.client.try_claim_with_secret(&1, &t.secret(), &attacker);
+    assert_eq!(result, Err(Ok(Error::InvalidSecret)));
+}
+
+#[test]
+fn wrong_secret_is_rejected() {
+    let t = HashLockTest::new();
+    t.create_package(1, 0, &Map::new(&t.env));
+    let wallet = Address::generate(&t.env);
+    let wrong = Bytes::from_slice(&t.env, b"000-000-000");
+
+    t.client
+        .commit_secret_claim(&1, &wallet, &t.commitment(&wrong, &wallet));
+    t.next_ledger();
+    let result = t.client.try_claim_with_secret(&1, &wrong, &wallet);
+    assert_eq!(result, Err(Ok(Error::InvalidSecret)));
+}
+
+#[test]
+fn secret_claim_respects_expiry_and_start_time() {
+    let t = HashLockTest::new();
+    let now = t.env.ledger().timestamp();
+    let mut metadata = Map::new(&t.env);
+    metadata.set(
+        Symbol::new(&t.env, "claim_starts_at"),
+        String::from_str(&t.env, "1000100"),
+    );
+    t.create_package(1, now + 200, &metadata);
+    let wallet = Address::generate(&t.env);
+
+    t.client
+        .commit_secret_claim(&1, &wallet, &t.commitment(&t.secret(), &wallet));
+    t.next_ledger();
+
+    let result = t.client.try_claim_with_secret(&1, &t.secret(), &wallet);
+    assert_eq!(result, Err(Ok(Error::ClaimTooEarly)));
+
+    t.env.ledger().set_timestamp(now + 201);
+    let result = t.client.try_claim_with_secret(&1, &t.secret(), &wallet);
+    assert_eq!(result, Err(Ok(Error::PackageExpired)));
+}
+
+#[test]
+fn secret_claim_blocked_while_paused() {
+    let t = HashLockTest::new();
+    t.create_package(1, 0, &Map::new(&t.env));
+    let wallet = Address::generate(&t.env);
+
+    t.client
+        .commit_secret_claim(&1, &wallet, &t.commitment(&t.secret(), &wallet));
+    t.next_ledger();
+    t.client.pause_action(&symbol_short!("claim"));
+
+    let result = t.client.try_claim_with_secret(&1, &t.secret(), &wallet);
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+}
+
+#[test]
+fn hashlocked_package_rejects_address_based_paths() {
+    let t = HashLockTest::new();
+    t.create_package(1, 0, &Map::new(&t.env));
+
+    assert_eq!(t.client.try_claim(&1), Err(Ok(Error::HashLockedPackage)));
+    assert_eq!(t.client.try_disburse(&1), Err(Ok(Error::HashLockedPackage)));
+    #[cfg(feature = "relayers")]
+    assert_eq!(
+        t.client
+            .try_claim_with_relayer(&1, &Address::generate(&t.env), &Address::generate(&t.env)),
+        Err(Ok(Error::HashLockedPackage))
+    );
+}
+
+#[test]
+fn regular_package_cannot_be_committed_against() {
+    let t = HashLockTest::new();
+    let recipient = Address::generate(&t.env);
+    t.client.create_package(
+        &t.admin,
+        &1,
+        &recipient,
+        &UNIT,
+        &t.token,
+        &0,
+        &Map::new(&t.env),
+    );
+
+    let result =
+        t.client
+            .try_commit_secret_claim(&1, &recipient, &t.commitment(&t.secret(), &recipient));
+    assert_eq!(result, Err(Ok(Error::InvalidState)));
+    assert_eq!(t.client.get_claim_hash(&1), None);
+}
@@ -0,0 +1,195 @@
+#![cfg(all(test, feature = "merchants"))]
+
+mod common;
+
+use aid_escrow::{Error, PackageStatus, PackageTerms, PolicyError};
+use common::{Escrow, UNIT};
+use soroban_sdk::{
+    symbol_short, testutils::Address as _, Address, InvokeError, Map, String, Symbol, Vec,
+};
+
+type MerchantTest = Escrow;
+
+impl MerchantTest {
+    fn create(&self, id: u64, restricted: bool) -> u64 {
+        let terms = PackageTerms {
+            restricted,
+            ..Default::default()
+        };
+        self.create_with_terms(id, &terms).unwrap()
+    }
+
+    fn create_with_terms(&self, id: u64, terms: &PackageTerms) -> Result<u64, Error> {
+        self.client
+            .try_create_package_with_terms(
+                &self.admin,
+                &id,
+                &Address::generate(&self.env),
+                &UNIT,
+                &self.token,
+                &(self.env.ledger().timestamp() + 86400),
+                terms,
+                &Map::new(&self.env),
+            )
+            .map(|r| r.unwrap())
+            .map_err(|e| e.unwrap())
+    }
+
+    fn merchant(&self) -> Address {
+        self.merchant_with(&[symbol_short!("food")])
+    }
+
+    fn merchant_with(&self, categories: &[Symbol]) -> Address {
+        let merchant = Address::generate(&self.env);
+        self.client
+            .add_merchant(&merchant, &Vec::from_slice(&self.env, categories));
+        merchant
+    }
+
+    fn redeem(&self, id: u64, merchant: &Address, amount: i128) -> Result<(), Error> {
+        self.client
+            .try_redeem(&id, merchant, &amount)
+            .map(|r| r.unwrap())
+            .map_err(|e| e.unwrap())
+    }
+}
+
+#[test]
+fn restricted_package_is_redeemed_in_parts_at_merchants() {
+    let t = MerchantTest::new();
+    let id = t.create(1, true);
+    let shop = t.merchant();
+    let pharmacy = t.merchant();
+
+    assert_eq!(t.client.try_claim(&id), Err(Ok(Error::RestrictedPackage)));
+
+    t.redeem(id, &shop, 3 * UNIT / 10).unwrap();
+    assert_eq!(t.client.get_package(&id).status, PackageStatus::Created);
+    assert_eq!(
+        t.redeem(id, &pharmacy, 8 * UNIT / 10),
+        Err(Error::InvalidAmount)
+    );
+    assert_eq!(t.redeem(id, &pharmacy, 0), Err(Error::InvalidAmount));
+    assert_eq!(
+        t.redeem(id, &pharmacy, i128::MIN),
+        Err(Error::InvalidAmount)
+    );
+
+    t.redeem(id, &pharmacy, 7 * UNIT / 10).unwrap();
+    assert_eq!(t.client.get_package(&id).status, PackageStatus::Claimed);
+    assert_eq!(t.client.get_redeemed(&id), UNIT);
+    assert_eq!(t.balance(&shop), 3 * UNIT / 10);
+    assert_eq!(t.balance(&pharmacy), 7 * UNIT / 10);
+    assert_eq!(
+        t.client.get_merchant_settled(&shop, &t.token),
+        3 * UNIT / 10
+    );
+    assert_eq!(t.client.get_total_claimed(&t.token), UNIT);
+    assert_eq!(t.redeem(id, &shop, 1), Err(Error::PackageNotActive));
+}
+
+#[test]
+fn only_active_merchants_accept_redemptions_of_restricted_packages() {
+    let t = MerchantTest::new();
+    let restricted = t.create(1, true);
+    let unrestricted = t.create(2, false);
+    let shop = t.merchant();
+
+    assert_eq!(
+        t.redeem(restricted, &Address::generate(&t.env), UNIT),
+        Err(Error::MerchantUnavailable)
+    );
+    assert_eq!(
+        t.redeem(unrestricted, &shop, UNIT),
+        Err(Error::InvalidState)
+    );
+
+    t.client.set_merchant_suspended(&shop, &true);
+    assert_eq!(
+        t.redeem(restricted, &shop, UNIT),
+        Err(Error::MerchantUnavailable)
+    );
+    t.client.set_merchant_suspended(&shop, &false);
+    t.redeem(restricted, &shop, UNIT).unwrap();
+
+    let pharmacy = symbol_short!("pharmacy");
+    t.client
+        .set_merchant_categories(&shop, &Vec::from_array(&t.env, [pharmacy.clone()]));
+    let info = t.client.get_merchant(&shop).unwrap();
+    assert!(!info.suspended);
+    assert_eq!(info.categories, Vec::from_array(&t.env, [pharmacy]));
+}
+
+#[test]
+fn revoking_a_partly_redeemed_package_returns_only_the_rest() {
+    let t = MerchantTest::new();
+    let id = t.create(1, true);
+    let shop = t.merchant();
+    t.redeem(id, &shop, 4 * UNIT / 10).unwrap();
+
+    t.client.revoke(&id);
+    let before = t.balance(&t.admin);
+    t.client.refund(&id);
+    assert_eq!(t.balance(&t.admin) - before, 6 * UNIT / 10);
+
+    let aggregates = t.client.get_aggregates(&t.token);
+    assert_eq!(aggregates.total_claimed, 4 * UNIT / 10);
+    assert_eq!(aggregates.total_expired_cancelled, 6 * UNIT / 10);
+    assert_eq!(aggregates.total_committed, 0);
+}
+
+#[test]
+fn redemptions_are_limited_to_allowed_categories_and_counted_per_campaign() {
+    let t = MerchantTest::new();
+    let campaign = String::from_str(&t.env, "flood");
+    let food = symbol_short!("food");
+    let medicine = symbol_short!("medicine");
+    let medicine_only = PackageTerms {
+        restricted: true,
+        campaign_id: Some(campaign.clone()),
+        allowed_categories: Some(Vec::from_array(&t.env, [medicine.clone()])),
+        ..Default::default()
+    };
+    let any_category = PackageTerms {
+        restricted: true,
+        campaign_id: Some(campaign.clone()),
+        ..Default::default()
+    };
+    t.create_with_terms(1, &medicine_only).unwrap();
+    t.create_with_terms(2, &any_category).unwrap();
+    let grocer = t.merchant_with(&[food.clone()]);
+    let supermarket = t.merchant_with(&[food.clone(), medicine.clone()]);
+
+    assert_eq!(
+        t.client.try_redeem(&1, &grocer, &UNIT),
+        Err(Err(InvokeError::from(PolicyError::CategoryNotAllowed)))
+    );
+    t.redeem(1, &supermarket, UNIT / 4).unwrap();
+    t.redeem(2, &supermarket, UNIT / 2).unwrap();
+    t.redeem(2, &grocer, UNIT / 4).unwrap();
+
+    let spend = t.client.get_campaign_category_spend(&campaign, &t.token);
+    assert_eq!(spend.get(medicine), Some(UNIT / 4));
+    assert_eq!(spend.get(food), Some(3 * UNIT / 4));
+}
+
+#[test]
+fn allowed_categories_require_a_restricted_package() {
+    let t = MerchantTest::new();
+    let food = Vec::from_array(&t.env, [symbol_short!("food")]);
+
+    let unrestricted = PackageTerms {
+        allowed_categories: Some(food),
+        ..Default::default()
+    };
+    assert_eq!(
+        t.create_with_terms(1, &unrestricted),
+        Err(Error::MalformedTerm)
+    );
+    let empty = PackageTerms {
+        restricted: true,
+        allowed_categories: Some(Vec::new(&t.env)),
+        ..Default::default()
+    };
+    assert_eq!(t.create_with_terms(1, &empty), Err(Error::MalformedTerm));
+}
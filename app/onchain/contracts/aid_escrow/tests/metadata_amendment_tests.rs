@@ -0,0 +1,207 @@
+#![cfg(all(test, feature = "amendments"))]
+
+mod common;
+
+use aid_escrow::Error;
+#[cfg(feature = "metadata-rules")]
+use aid_escrow::MetadataRules;
+use common::{Escrow, UNIT};
+use soroban_sdk::{testutils::Address as _, Address, Map, String, Symbol, Vec};
+use std::ops::Deref;
+
+struct AmendTest {
+    escrow: Escrow,
+    distributor: Address,
+}
+
+impl Deref for AmendTest {
+    type Target = Escrow;
+
+    fn deref(&self) -> &Escrow {
+        &self.escrow
+    }
+}
+
+impl AmendTest {
+    fn new() -> Self {
+        let escrow = Escrow::new();
+
+        let distributor = Address::generate(&escrow.env);
+        escrow.client.add_distributor(&distributor);
+
+        Self {
+            escrow,
+            distributor,
+        }
+    }
+
+    fn now(&self) -> u64 {
+        self.env.ledger().timestamp()
+    }
+
+    fn meta(&self, entries: &[(&str, &str)]) -> Map<Symbol, String> {
+        let mut metadata = Map::new(&self.env);
+        for (k, v) in entries {
+            metadata.set(Symbol::new(&self.env, k), String::from_str(&self.env, v));
+        }
+        metadata
+    }
+
+    fn keys(&self, keys: &[&str]) -> Vec<Symbol> {
+        let mut out = Vec::new(&self.env);
+        for k in keys {
+            out.push_back(Symbol::new(&self.env, k));
+        }
+        out
+    }
+
+    fn create(&self, operator: &Address, id: u64, metadata: &Map<Symbol, String>) -> u64 {
+        self.client.create_package(
+            operator,
+            &id,
+            &Address::generate(&self.env),
+            &UNIT,
+            &self.token,
+            &(self.now() + 86400),
+            metadata,
+        )
+    }
+
+    fn update(
+        &self,
+        operator: &Address,
+        id: u64,
+        set: &[(&str, &str)],
+        remove: &[&str],
+    ) -> Result<(), Error> {
+        self.client
+            .try_update_package_metadata(operator, &id, &self.meta(set), &self.keys(remove))
+            .map(|r| r.unwrap())
+            .map_err(|e| e.unwrap())
+    }
+}
+
+#[test]
+fn receipt_hash_can_be_attached_after_claim() {
+    let t = AmendTest::new();
+    let id = t.create(&t.admin, 1, &t.meta(&[("note", "rice")]));
+    t.client.claim(&id);
+
+    t.update(&t.admin, id, &[("receipt_hash", "r-42")], &["note"])
+        .unwrap();
+
+    let package = t.client.get_package(&id);
+    assert_eq!(package.metadata, t.meta(&[("receipt_hash", "r-42")]));
+    assert_eq!(
+        t.client.get_package_terms(&id).receipt_hash,
+        Some(String::from_str(&t.env, "r-42"))
+    );
+
+    let log = t.client.get_metadata_amendments(&id);
+    assert_eq!(log.len(), 1);
+    let entry = log.get(0).unwrap();
+    assert_eq!(entry.actor, t.admin);
+    assert_eq!(entry.set, t.meta(&[("receipt_hash", "r-42")]));
+    assert_eq!(entry.removed, t.keys(&["note"]));
+}
+
+#[test]
+fn only_admin_or_creator_can_amend() {
+    let t = AmendTest::new();
+    let other = Address::generate(&t.env);
+    t.client.add_distributor(&other);
+    let id = t.create(&t.distributor, 1, &Map::new(&t.env));
+
+    assert_eq!(
+        t.update(&other, id, &[("note", "x")], &[]),
+        Err(Error::NotAuthorized)
+    );
+    t.update(&t.distributor, id, &[("note", "x")], &[]).unwrap();
+    t.update(&t.admin, id, &[("note", "y")], &[]).unwrap();
+
+    // A creator that is no longer a distributor loses the right.
+    t.client.remove_distributor(&t.distributor);
+    assert_eq!(
+        t.update(&t.distributor, id, &[("note", "z")], &[]),
+        Err(Error::NotAuthorized)
+    );
+    assert_eq!(t.client.get_metadata_amendments(&id).len(), 2);
+}
+
+#[test]
+fn behaviour_keys_are_frozen_after_created() {
+    let t = AmendTest::new();
+    let id = t.create(&t.admin, 1, &t.meta(&[("campaign_ref", "flod")]));
+
+    t.update(&t.admin, id, &[("campaign_ref", "flood")], &[])
+        .unwrap();
+    assert_eq!(
+        t.client
+            .get_campaign_package_count(&String::from_str(&t.env, "flood")),
+        1
+    );
+
+    t.client.claim(&id);
+    assert_eq!(
+        t.update(&t.admin, id, &[("campaign_ref", "drought")], &[]),
+        Err(Error::PackageNotActive)
+    );
+    assert_eq!(
+        t.update(&t.admin, id, &[], &["claim_starts_at"]),
+        Err(Error::PackageNotActive)
+    );
+}
+
+#[test]
+fn claim_start_is_revalidated_against_expiry() {
+    let t = AmendTest::new();
+    let id = t.create(&t.admin, 1, &Map::new(&t.env));
+
+    let too_late = (t.now() + 86401).to_string();
+    assert_eq!(
+        t.update(&t.admin, id, &[("claim_starts_at", &too_late)], &[]),
+        Err(Error::InvalidClaimWindow)
+    );
+    assert_eq!(
+        t.update(&t.admin, id, &[("claim_starts_at", "later")], &[]),
+        Err(Error::MalformedTerm)
+    );
+
+    let start = (t.now() + 100).to_string();
+    t.update(&t.admin, id, &[("claim_starts_at", &start)], &[])
+        .unwrap();
+    assert_eq!(t.client.get_package(&id).claim_starts_at, t.now() + 100);
+    assert_eq!(t.client.try_claim(&id), Err(Ok(Error::ClaimTooEarly)));
+
+    // Removing the key restores the default start.
+    t.update(&t.admin, id, &[], &["claim_starts_at"]).unwrap();
+    t.client.claim(&id);
+}
+
+#[test]
+#[cfg(feature = "metadata-rules")]
+fn invalid_amendments_are_rejected() {
+    let t = AmendTest::new();
+    let id = t.create(&t.admin, 1, &Map::new(&t.env));
+
+    assert_eq!(t.update(&t.admin, id, &[], &[]), Err(Error::InvalidState));
+    assert_eq!(
+        t.update(&t.admin, id, &[("note", "x")], &["note"]),
+        Err(Error::InvalidState)
+    );
+    assert_eq!(
+        t.update(&t.admin, 99, &[("note", "x")], &[]),
+        Err(Error::PackageNotFound)
+    );
+
+    t.client.set_metadata_rules(&MetadataRules {
+        allowed_keys: t.keys(&["note"]),
+        max_keys: 0,
+        max_value_len: 0,
+    });
+    assert_eq!(
+        t.update(&t.admin, id, &[("national_id", "123")], &[]),
+        Err(Error::MetadataKeyNotAllowed)
+    );
+    assert_eq!(t.client.get_metadata_amendments(&id).len(), 0);
+}
@@ -0,0 +1,160 @@
+#![cfg(all(test, feature = "metadata-rules"))]
+
+mod common;
+
+use aid_escrow::{Error, MetadataRules, PackageTerms};
+use common::{Escrow, UNIT};
+use soroban_sdk::{testutils::Address as _, Address, Map, String, Symbol, Vec};
+
+type RulesTest = Escrow;
+
+impl RulesTest {
+    fn meta(&self, entries: &[(&str, &str)]) -> Map<Symbol, String> {
+        let mut metadata = Map::new(&self.env);
+        for (k, v) in entries {
+            metadata.set(Symbol::new(&self.env, k), String::from_str(&self.env, v));
+        }
+        metadata
+    }
+
+    fn keys(&self, keys: &[&str]) -> Vec<Symbol> {
+        let mut out = Vec::new(&self.env);
+        for k in keys {
+            out.push_back(Symbol::new(&self.env, k));
+        }
+        out
+    }
+
+    fn create(&self, id: u64, metadata: &Map<Symbol, String>) -> Result<u64, Error> {
+        self.client
+            .try_create_package(
+                &self.admin,
+                &id,
+                &Address::generate(&self.env),
+                &UNIT,
+                &self.token,
+                &(self.env.ledger().timestamp() + 86400),
+                metadata,
+            )
+            .map(|r| r.unwrap())
+            .map_err(|e| e.unwrap())
+    }
+}
+
+#[test]
+fn default_rules_accept_any_metadata() {
+    let t = RulesTest::new();
+    assert_eq!(
+        t.client.get_metadata_rules(),
+        MetadataRules {
+            allowed_keys: Vec::new(&t.env),
+            max_keys: 0,
+            max_value_len: 0,
+        }
+    );
+    assert_eq!(t.create(1, &t.meta(&[("anything", "goes")])), Ok(1));
+}
+
+#[test]
+fn create_rejects_metadata_outside_the_rules() {
+    let t = RulesTest::new();
+    t.client.set_metadata_rules(&MetadataRules {
+        allowed_keys: t.keys(&["note", "region"]),
+        max_keys: 1,
+        max_value_len: 5,
+    });
+
+    assert_eq!(
+        t.create(1, &t.meta(&[("note", "a"), ("region", "b")])),
+        Err(Error::MetadataTooManyKeys)
+    );
+    assert_eq!(
+        t.create(1, &t.meta(&[("national_id", "123")])),
+        Err(Error::MetadataKeyNotAllowed)
+    );
+    assert_eq!(
+        t.create(1, &t.meta(&[("note", "too long")])),
+        Err(Error::MetadataValueTooLong)
+    );
+    assert_eq!(t.create(1, &t.meta(&[("note", "ok")])), Ok(1));
+}
+
+#[test]
+fn campaign_required_keys_are_enforced() {
+    let t = RulesTest::new();
+    let campaign = String::from_str(&t.env, "flood");
+    t.client
+        .set_required_metadata(&campaign, &t.keys(&["region"]));
+    assert_eq!(
+        t.client.get_required_metadata(&campaign),
+        t.keys(&["region"])
+    );
+
+    assert_eq!(
+        t.create(1, &t.meta(&[("campaign_ref", "flood")])),
+        Err(Error::MissingRequiredMetadata)
+    );
+    // Campaigns without requirements are unaffected.
+    assert_eq!(t.create(1, &t.meta(&[("campaign_ref", "drought")])), Ok(1));
+
+    // The campaign may also come from the typed terms.
+    let terms = PackageTerms {
+        campaign_id: Some(campaign.clone()),
+        ..Default::default()
+    };
+    let result = t.client.try_create_package_with_terms(
+        &t.admin,
+        &2,
+        &Address::generate(&t.env),
+        &UNIT,
+        &t.token,
+        &(t.env.ledger().timestamp() + 86400),
+        &terms,
+        &Map::new(&t.env),
+    );
+    assert_eq!(result, Err(Ok(Error::MissingRequiredMetadata)));
+    assert_eq!(
+        t.create(
+            2,
+            &t.meta(&[("campaign_ref", "flood"), ("region", "north")])
+        ),
+        Ok(2)
+    );
+
+    t.client.set_required_metadata(&campaign, &Vec::new(&t.env));
+    assert_eq!(t.create(3, &t.meta(&[("campaign_ref", "flood")])), Ok(3));
+}
+
+#[test]
+fn batch_create_rejects_any_violation() {
+    let t = RulesTest::new();
+    t.client.set_metadata_rules(&MetadataRules {
+        allowed_keys: Vec::new(&t.env),
+        max_keys: 0,
+        max_value_len: 3,
+    });
+
+    let recipients = Vec::from_array(
+        &t.env,
+        [Address::generate(&t.env), Address::generate(&t.env)],
+    );
+    let amounts = Vec::from_array(&t.env, [UNIT, UNIT]);
+    let metadatas = Vec::from_array(
+        &t.env,
+        [t.meta(&[("note", "ok")]), t.meta(&[("note", "too long")])],
+    );
+
+    let result = t.client.try_batch_create_packages(
+        &t.admin,
+        &recipients,
+        &amounts,
+        &t.token,
+        &3600,
+        &metadatas,
+    );
+    assert_eq!(result, Err(Ok(Error::MetadataValueTooLong)));
+    assert_eq!(
+        t.client.try_get_package(&0),
+        Err(Ok(Error::PackageNotFound))
+    );
+}
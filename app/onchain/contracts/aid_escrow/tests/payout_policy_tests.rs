@@ -0,0 +1,182 @@
+#![cfg(all(test, feature = "payout-policies"))]
+
+mod common;
+
+#[cfg(feature = "standing-delegates")]
+use aid_escrow::DelegationScope;
+use aid_escrow::{Error, PayoutPolicy};
+use common::{Escrow, UNIT};
+use soroban_sdk::{testutils::Address as _, Address, Map, String, Vec};
+use std::ops::Deref;
+
+struct PayoutTest {
+    escrow: Escrow,
+    recipient: Address,
+    delegate: Address,
+}
+
+impl Deref for PayoutTest {
+    type Target = Escrow;
+
+    fn deref(&self) -> &Escrow {
+        &self.escrow
+    }
+}
+
+impl PayoutTest {
+    fn new() -> Self {
+        let escrow = Escrow::new();
+
+        let recipient = Address::generate(&escrow.env);
+        let delegate = Address::generate(&escrow.env);
+
+        Self {
+            escrow,
+            recipient,
+            delegate,
+        }
+    }
+
+    fn create_package(&self, id: u64) {
+        self.client.create_package(
+            &self.admin,
+            &id,
+            &self.recipient,
+            &UNIT,
+            &self.token,
+            &0,
+            &Map::new(&self.env),
+        );
+    }
+
+    fn delegate_claim(&self, id: u64) {
+        let proof: Vec<String> = Vec::new(&self.env);
+        self.client.claim_with_proof(&id, &self.delegate, &proof);
+    }
+}
+
+#[test]
+fn default_policy_pays_the_delegate() {
+    let t = PayoutTest::new();
+    t.create_package(1);
+    t.client.set_delegate(&t.admin, &1, &t.delegate);
+
+    assert_eq!(t.client.get_payout_policy(&1), PayoutPolicy::ToDelegate);
+    t.delegate_claim(1);
+    assert_eq!(t.balance(&t.delegate), UNIT);
+    assert_eq!(t.balance(&t.recipient), 0);
+}
+
+#[test]
+fn to_recipient_policy_sends_funds_to_recipient() {
+    let t = PayoutTest::new();
+    t.create_package(1);
+    t.client.set_delegate(&t.admin, &1, &t.delegate);
+    t.client
+        .set_payout_policy(&t.admin, &1, &PayoutPolicy::ToRecipient);
+
+    t.delegate_claim(1);
+    assert_eq!(t.balance(&t.delegate), 0);
+    assert_eq!(t.balance(&t.recipient), UNIT);
+    assert_eq!(t.client.get_total_locked(&t.token), 0);
+}
+
+#[test]
+#[cfg(feature = "relayers")]
+fn split_policy_applies_to_relayed_claims() {
+    let t = PayoutTest::new();
+    t.create_package(1);
+    t.client.set_delegate(&t.admin, &1, &t.delegate);
+    t.client
+        .set_payout_policy(&t.admin, &1, &PayoutPolicy::Split(2_500));
+
+    let relayer = Address::generate(&t.env);
+    t.client.claim_with_relayer(&1, &t.delegate, &relayer);
+    assert_eq!(t.balance(&t.delegate), UNIT / 4);
+    assert_eq!(t.balance(&t.recipient), UNIT - UNIT / 4);
+}
+
+#[test]
+fn delegation_policy_overrides_package_policy() {
+    let t = PayoutTest::new();
+    t.create_package(1);
+    t.client.set_delegate(&t.admin, &1, &t.delegate);
+    t.client
+        .set_payout_policy(&t.admin, &1, &PayoutPolicy::ToDelegate);
+    t.client.set_delegate_payout_policy(
+        &t.admin,
+        &1,
+        &t.delegate,
+        &Some(PayoutPolicy::ToRecipient),
+    );
+    assert_eq!(
+        t.client.get_delegate_payout_policy(&1, &t.delegate),
+        Some(PayoutPolicy::ToRecipient)
+    );
+
+    t.delegate_claim(1);
+    assert_eq!(t.balance(&t.recipient), UNIT);
+}
+
+#[test]
+#[cfg(feature = "standing-delegates")]
+fn standing_delegation_policy_is_honoured() {
+    let t = PayoutTest::new();
+    t.create_package(1);
+    t.client.grant_standing_delegate(
+        &t.recipient,
+        &t.recipient,
+        &t.delegate,
+        &DelegationScope::All,
+        &0,
+    );
+    t.client.set_standing_payout_policy(
+        &t.recipient,
+        &t.recipient,
+        &t.delegate,
+        &Some(PayoutPolicy::Split(5_000)),
+    );
+
+    t.delegate_claim(1);
+    assert_eq!(t.balance(&t.delegate), UNIT / 2);
+    assert_eq!(t.balance(&t.recipient), UNIT / 2);
+}
+
+#[test]
+fn recipient_claim_ignores_policy() {
+    let t = PayoutTest::new();
+    t.create_package(1);
+    t.client.set_delegate(&t.admin, &1, &t.delegate);
+    t.client
+        .set_payout_policy(&t.admin, &1, &PayoutPolicy::Split(10_000));
+
+    t.client.claim(&1);
+    assert_eq!(t.balance(&t.recipient), UNIT);
+    assert_eq!(t.balance(&t.delegate), 0);
+}
+
+#[test]
+fn invalid_split_and_unauthorised_callers_are_rejected() {
+    let t = PayoutTest::new();
+    t.create_package(1);
+    t.client.set_delegate(&t.admin, &1, &t.delegate);
+
+    let result = t
+        .client
+        .try_set_payout_policy(&t.admin, &1, &PayoutPolicy::Split(10_001));
+    assert_eq!(result, Err(Ok(Error::InvalidPayoutPolicy)));
+
+    let result = t
+        .client
+        .try_set_payout_policy(&t.delegate, &1, &PayoutPolicy::ToDelegate);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    let stranger = Address::generate(&t.env);
+    let result = t.client.try_set_delegate_payout_policy(
+        &t.admin,
+        &1,
+        &stranger,
+        &Some(PayoutPolicy::ToRecipient),
+    );
+    assert_eq!(result, Err(Ok(Error::DelegateNotFound)));
+}
@@ -0,0 +1,225 @@
+#![cfg(all(test, feature = "fees"))]
+
+mod common;
+
+use aid_escrow::{Error, FeeScope, FeeTiming, ProtocolFee};
+use common::{Escrow, UNIT};
+use soroban_sdk::{testutils::Address as _, Address, Map, String, Symbol};
+use std::ops::Deref;
+
+struct FeeTest {
+    escrow: Escrow,
+    recipient: Address,
+    treasury: Address,
+}
+
+impl Deref for FeeTest {
+    type Target = Escrow;
+
+    fn deref(&self) -> &Escrow {
+        &self.escrow
+    }
+}
+
+impl FeeTest {
+    fn new() -> Self {
+        let escrow = Escrow::new();
+
+        let recipient = Address::generate(&escrow.env);
+        let treasury = Address::generate(&escrow.env);
+
+        Self {
+            escrow,
+            recipient,
+            treasury,
+        }
+    }
+
+    fn set_token_fee(&self, bps: u32, timing: FeeTiming) {
+        self.client.set_protocol_fee(
+            &FeeScope::Token(self.token.clone()),
+            &Some(ProtocolFee { bps, timing }),
+        );
+    }
+
+    fn create_package(&self, id: u64, amount: i128, metadata: &Map<Symbol, String>) {
+        self.client.create_package(
+            &self.admin,
+            &id,
+            &self.recipient,
+            &amount,
+            &self.token,
+            &(self.env.ledger().timestamp() + 86400),
+            metadata,
+        );
+    }
+
+    fn campaign(&self, name: &str) -> Map<Symbol, String> {
+        let mut metadata = Map::new(&self.env);
+        metadata.set(
+            Symbol::new(&self.env, "campaign_ref"),
+            String::from_str(&self.env, name),
+        );
+        metadata
+    }
+}
+
+#[test]
+fn creation_fee_is_charged_on_top_and_excluded_from_surplus() {
+    let t = FeeTest::new();
+    t.set_token_fee(100, FeeTiming::AtCreation);
+
+    t.create_package(1, UNIT, &Map::new(&t.env));
+
+    let fee = UNIT / 100;
+    assert_eq!(t.client.get_fee_totals(&t.token).accrued, fee);
+    assert_eq!(t.client.get_total_locked(&t.token), UNIT);
+
+    // Only balance - locked - held fees can be withdrawn
+    let surplus = 10 * UNIT - UNIT - fee;
+    assert_eq!(
+        t.client
+            .try_withdraw_surplus(&t.admin, &(surplus + 1), &t.token),
+        Err(Ok(Error::InsufficientSurplus))
+    );
+    t.client.withdraw_surplus(&t.admin, &surplus, &t.token);
+
+    // The package still pays out in full
+    t.client.claim(&1);
+    assert_eq!(t.balance(&t.recipient), UNIT);
+}
+
+#[test]
+fn creation_fee_counts_towards_solvency() {
+    let t = FeeTest::new();
+    t.set_token_fee(100, FeeTiming::AtCreation);
+
+    let result = t.client.try_create_package(
+        &t.admin,
+        &1,
+        &t.recipient,
+        &(10 * UNIT),
+        &t.token,
+        &(t.env.ledger().timestamp() + 86400),
+        &Map::new(&t.env),
+    );
+    assert_eq!(result, Err(Ok(Error::InsufficientFunds)));
+}
+
+#[test]
+fn claim_fee_is_deducted_from_every_payout_path() {
+    let t = FeeTest::new();
+    t.set_token_fee(250, FeeTiming::AtClaim);
+    t.create_package(1, UNIT, &Map::new(&t.env));
+    t.create_package(2, UNIT, &Map::new(&t.env));
+
+    let fee = UNIT * 250 / 10_000;
+    t.client.claim(&1);
+    assert_eq!(t.balance(&t.recipient), UNIT - fee);
+
+    t.client.disburse(&2);
+    assert_eq!(t.balance(&t.recipient), 2 * (UNIT - fee));
+
+    // Fees are reported apart from what was paid out.
+    assert_eq!(t.client.get_fee_totals(&t.token).accrued, 2 * fee);
+    assert_eq!(t.client.get_total_claimed(&t.token), 2 * (UNIT - fee));
+}
+
+#[test]
+#[cfg(feature = "merchants")]
+fn claim_fee_is_deducted_from_redemptions() {
+    use aid_escrow::PackageTerms;
+    use soroban_sdk::{symbol_short, Vec};
+
+    let t = FeeTest::new();
+    t.set_token_fee(250, FeeTiming::AtClaim);
+    let fee = UNIT * 250 / 10_000;
+
+    let shop = Address::generate(&t.env);
+    t.client
+        .add_merchant(&shop, &Vec::from_array(&t.env, [symbol_short!("food")]));
+    t.client.create_package_with_terms(
+        &t.admin,
+        &1,
+        &t.recipient,
+        &UNIT,
+        &t.token,
+        &(t.env.ledger().timestamp() + 86400),
+        &PackageTerms {
+            restricted: true,
+            ..Default::default()
+        },
+        &Map::new(&t.env),
+    );
+    t.client.redeem(&1, &shop, &UNIT);
+    assert_eq!(t.balance(&shop), UNIT - fee);
+    assert_eq!(t.client.get_fee_totals(&t.token).accrued, fee);
+}
+
+#[test]
+fn claim_fee_rate_is_fixed_at_creation() {
+    let t = FeeTest::new();
+    t.set_token_fee(100, FeeTiming::AtClaim);
+    t.create_package(1, UNIT, &Map::new(&t.env));
+
+    t.client
+        .set_protocol_fee(&FeeScope::Token(t.token.clone()), &None);
+    t.client.claim(&1);
+
+    assert_eq!(t.balance(&t.recipient), UNIT - UNIT / 100);
+}
+
+#[test]
+fn campaign_fee_overrides_token_fee() {
+    let t = FeeTest::new();
+    t.set_token_fee(100, FeeTiming::AtClaim);
+    t.client.set_protocol_fee(
+        &FeeScope::Campaign(String::from_str(&t.env, "flood")),
+        &Some(ProtocolFee {
+            bps: 0,
+            timing: FeeTiming::AtClaim,
+        }),
+    );
+
+    t.create_package(1, UNIT, &t.campaign("flood"));
+    t.create_package(2, UNIT, &t.campaign("drought"));
+    t.client.claim(&1);
+    t.client.claim(&2);
+
+    assert_eq!(t.client.get_fee_totals(&t.token).accrued, UNIT / 100);
+}
+
+#[test]
+fn collect_fees_sweeps_held_fees_to_treasury() {
+    let t = FeeTest::new();
+    t.set_token_fee(100, FeeTiming::AtClaim);
+    t.create_package(1, UNIT, &Map::new(&t.env));
+    t.client.claim(&1);
+
+    assert_eq!(
+        t.client.try_collect_fees(&t.token),
+        Err(Ok(Error::TreasuryNotSet))
+    );
+
+    t.client.set_treasury(&t.treasury);
+    assert_eq!(t.client.collect_fees(&t.token), UNIT / 100);
+    assert_eq!(t.balance(&t.treasury), UNIT / 100);
+
+    let totals = t.client.get_fee_totals(&t.token);
+    assert_eq!(totals.accrued, totals.collected);
+    assert_eq!(t.client.collect_fees(&t.token), 0);
+}
+
+#[test]
+fn fee_above_full_rate_is_rejected() {
+    let t = FeeTest::new();
+
+    let result = t.client.try_set_protocol_fee(
+        &FeeScope::Token(t.token.clone()),
+        &Some(ProtocolFee {
+            bps: 10_001,
+            timing: FeeTiming::AtClaim,
+        }),
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidProtocolFee)));
+}
@@ -0,0 +1,110 @@
+#![cfg(all(test, feature = "receipts"))]
+
+mod common;
+
+use aid_escrow::{AckStats, Error, ReceiptAck};
+use common::{Escrow, UNIT};
+use soroban_sdk::{testutils::Address as _, Address, Map, String, Symbol, Vec};
+
+type ReceiptTest = Escrow;
+
+impl ReceiptTest {
+    fn create(&self, id: u64, recipient: &Address, campaign: &str) -> u64 {
+        let mut metadata = Map::new(&self.env);
+        metadata.set(
+            Symbol::new(&self.env, "campaign_ref"),
+            String::from_str(&self.env, campaign),
+        );
+        self.client.create_package(
+            &self.admin,
+            &id,
+            recipient,
+            &UNIT,
+            &self.token,
+            &(self.env.ledger().timestamp() + 86400),
+            &metadata,
+        )
+    }
+
+    fn ack(&self, caller: &Address, id: u64, rating: u32) -> Result<(), Error> {
+        self.client
+            .try_acknowledge_receipt(caller, &id, &String::from_str(&self.env, "r-1"), &rating)
+            .map(|r| r.unwrap())
+            .map_err(|e| e.unwrap())
+    }
+}
+
+#[test]
+fn recipient_acknowledges_a_claimed_package_once() {
+    let t = ReceiptTest::new();
+    let recipient = Address::generate(&t.env);
+    let id = t.create(1, &recipient, "flood");
+
+    assert_eq!(t.ack(&recipient, id, 5), Err(Error::InvalidState));
+    t.client.claim(&id);
+
+    assert_eq!(t.ack(&recipient, id, 0), Err(Error::InvalidRating));
+    assert_eq!(t.ack(&recipient, id, 6), Err(Error::InvalidRating));
+    assert_eq!(
+        t.ack(&Address::generate(&t.env), id, 4),
+        Err(Error::NotAuthorized)
+    );
+
+    t.ack(&recipient, id, 4).unwrap();
+    assert_eq!(
+        t.client.get_receipt_ack(&id),
+        Some(ReceiptAck {
+            by: recipient.clone(),
+            receipt_hash: String::from_str(&t.env, "r-1"),
+            rating: 4,
+            timestamp: t.env.ledger().timestamp(),
+        })
+    );
+    assert_eq!(t.ack(&recipient, id, 4), Err(Error::AlreadyAcknowledged));
+}
+
+#[test]
+fn collecting_delegate_can_acknowledge_disbursed_or_claimed_packages() {
+    let t = ReceiptTest::new();
+    let recipient = Address::generate(&t.env);
+    let delegate = Address::generate(&t.env);
+    let id = t.create(1, &recipient, "flood");
+    t.client.set_delegate(&t.admin, &id, &delegate);
+    t.client.claim_with_proof(&id, &delegate, &Vec::new(&t.env));
+
+    // Package delegates are cleared on payout, but the collector is kept.
+    t.ack(&delegate, id, 3).unwrap();
+
+    let disbursed = t.create(2, &recipient, "flood");
+    t.client.disburse(&disbursed);
+    assert_eq!(t.ack(&delegate, disbursed, 3), Err(Error::NotAuthorized));
+    t.ack(&recipient, disbursed, 5).unwrap();
+}
+
+#[test]
+fn campaign_stats_split_acknowledged_and_unacknowledged() {
+    let t = ReceiptTest::new();
+    let recipient = Address::generate(&t.env);
+    for id in 0..3 {
+        t.create(id, &recipient, "flood");
+        t.client.claim(&id);
+    }
+    t.create(3, &recipient, "flood");
+    t.create(4, &recipient, "drought");
+    t.client.claim(&4);
+
+    t.ack(&recipient, 0, 5).unwrap();
+    t.ack(&recipient, 1, 2).unwrap();
+    t.ack(&recipient, 4, 1).unwrap();
+
+    assert_eq!(
+        t.client
+            .get_campaign_ack_stats(&String::from_str(&t.env, "flood")),
+        AckStats {
+            claimed: 3,
+            acknowledged: 2,
+            unacknowledged: 1,
+            rating_total: 7,
+        }
+    );
+}
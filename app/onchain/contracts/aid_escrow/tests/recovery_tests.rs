@@ -0,0 +1,276 @@
+#![cfg(all(test, feature = "recovery"))]
+
+mod common;
+
+use aid_escrow::{Error, PackageStatus, RecoveryScope, RecoveryStatus, Role};
+use common::{Escrow, UNIT};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Map,
+};
+use std::ops::Deref;
+
+const DAY: u64 = 24 * 60 * 60;
+const DELAY: u64 = 3 * DAY;
+
+struct RecoveryTest {
+    escrow: Escrow,
+    lost: Address,
+    replacement: Address,
+}
+
+impl Deref for RecoveryTest {
+    type Target = Escrow;
+
+    fn deref(&self) -> &Escrow {
+        &self.escrow
+    }
+}
+
+impl RecoveryTest {
+    fn new() -> Self {
+        let escrow = Escrow::new();
+
+        let lost = Address::generate(&escrow.env);
+        let replacement = Address::generate(&escrow.env);
+
+        Self {
+            escrow,
+            lost,
+            replacement,
+        }
+    }
+
+    fn create_package(&self, id: u64, recipient: &Address) {
+        self.client.create_package(
+            &self.admin,
+            &id,
+            recipient,
+            &UNIT,
+            &self.token,
+            &0,
+            &Map::new(&self.env),
+        );
+    }
+
+    fn advance(&self, seconds: u64) {
+        let now = self.env.ledger().timestamp();
+        self.env.ledger().set_timestamp(now + seconds);
+    }
+}
+
+#[test]
+fn single_package_recovery_after_delay() {
+    let t = RecoveryTest::new();
+    t.create_package(1, &t.lost);
+    t.create_package(2, &t.lost);
+
+    let id = t.client.propose_recovery(
+        &t.admin,
+        &t.lost,
+        &t.replacement,
+        &RecoveryScope::Package(1),
+    );
+
+    assert_eq!(
+        t.client.try_execute_recovery(&t.admin, &id, &50),
+        Err(Ok(Error::RecoveryNotReady))
+    );
+
+    t.advance(DELAY);
+    assert_eq!(t.client.execute_recovery(&t.admin, &id, &50).rotated, 1);
+
+    assert_eq!(t.client.get_package(&1).recipient, t.replacement);
+    assert_eq!(t.client.get_package(&2).recipient, t.lost);
+    assert_eq!(t.client.get_recovery(&id).status, RecoveryStatus::Executed);
+
+    let history = t.client.get_recipient_history(&1);
+    assert_eq!(history.len(), 1);
+    let change = history.get(0).unwrap();
+    assert_eq!(change.old_recipient, t.lost);
+    assert_eq!(change.new_recipient, t.replacement);
+    assert_eq!(change.recovery_id, id);
+
+    assert_eq!(
+        t.client
+            .list_recipient_packages(&t.replacement, &0, &10)
+            .len(),
+        1
+    );
+
+    t.client.claim(&1);
+    assert_eq!(t.balance(&t.replacement), UNIT);
+}
+
+#[test]
+fn all_open_recovery_skips_settled_and_foreign_packages() {
+    let t = RecoveryTest::new();
+    let other = Address::generate(&t.env);
+    t.create_package(1, &t.lost);
+    t.create_package(2, &t.lost);
+    t.create_package(3, &other);
+    t.create_package(4, &t.lost);
+    t.client.claim(&2);
+
+    let id = t
+        .client
+        .propose_recovery(&t.admin, &t.lost, &t.replacement, &RecoveryScope::AllOpen);
+    // Package 5 is created after the proposal and is still picked up at execution.
+    t.create_package(5, &t.lost);
+    t.advance(DELAY);
+
+    assert_eq!(t.client.execute_recovery(&t.admin, &id, &50).rotated, 3);
+    assert_eq!(t.client.get_package(&1).recipient, t.replacement);
+    assert_eq!(t.client.get_package(&2).recipient, t.lost);
+    assert_eq!(t.client.get_package(&2).status, PackageStatus::Claimed);
+    assert_eq!(t.client.get_package(&3).recipient, other);
+    assert_eq!(t.client.get_package(&4).recipient, t.replacement);
+    assert_eq!(t.client.get_package(&5).recipient, t.replacement);
+}
+
+#[test]
+fn original_recipient_can_object() {
+    let t = RecoveryTest::new();
+    t.create_package(1, &t.lost);
+
+    let id = t.client.propose_recovery(
+        &t.admin,
+        &t.lost,
+        &t.replacement,
+        &RecoveryScope::Package(1),
+    );
+    t.client.object_recovery(&id);
+    t.advance(DELAY);
+
+    assert_eq!(
+        t.client.try_execute_recovery(&t.admin, &id, &50),
+        Err(Ok(Error::InvalidState))
+    );
+    assert_eq!(t.client.get_recovery(&id).status, RecoveryStatus::Objected);
+    assert_eq!(t.client.get_package(&1).recipient, t.lost);
+}
+
+#[test]
+fn recovery_agent_role_can_operate_recoveries() {
+    let t = RecoveryTest::new();
+    let agent = Address::generate(&t.env);
+    t.create_package(1, &t.lost);
+
+    assert_eq!(
+        t.client
+            .try_propose_recovery(&agent, &t.lost, &t.replacement, &RecoveryScope::Package(1)),
+        Err(Ok(Error::NotAuthorized))
+    );
+
+    t.client.grant_role(&Role::RecoveryAgent, &agent);
+    assert!(t.client.has_role(&Role::RecoveryAgent, &agent));
+
+    let id = t
+        .client
+        .propose_recovery(&agent, &t.lost, &t.replacement, &RecoveryScope::Package(1));
+    t.client.cancel_recovery(&agent, &id);
+    assert_eq!(t.client.get_recovery(&id).status, RecoveryStatus::Cancelled);
+
+    t.client.revoke_role(&Role::RecoveryAgent, &agent);
+    assert!(!t.client.has_role(&Role::RecoveryAgent, &agent));
+}
+
+#[test]
+fn proposal_for_foreign_or_closed_package_is_rejected() {
+    let t = RecoveryTest::new();
+    let other = Address::generate(&t.env);
+    t.create_package(1, &other);
+    t.create_package(2, &t.lost);
+    t.client.revoke(&2);
+
+    assert_eq!(
+        t.client.try_propose_recovery(
+            &t.admin,
+            &t.lost,
+            &t.replacement,
+            &RecoveryScope::Package(1)
+        ),
+        Err(Ok(Error::InvalidState))
+    );
+    assert_eq!(
+        t.client.try_propose_recovery(
+            &t.admin,
+            &t.lost,
+            &t.replacement,
+            &RecoveryScope::Package(2)
+        ),
+        Err(Ok(Error::InvalidState))
+    );
+    assert_eq!(
+        t.client
+            .try_propose_recovery(&t.admin, &t.lost, &t.lost, &RecoveryScope::AllOpen),
+        Err(Ok(Error::InvalidState))
+    );
+}
+
+#[test]
+fn recovery_delay_is_configurable() {
+    let t = RecoveryTest::new();
+    assert_eq!(t.client.get_recovery_delay(), DELAY);
+    assert_eq!(
+        t.client.try_set_recovery_delay(&0),
+        Err(Ok(Error::InvalidState))
+    );
+    assert_eq!(
+        t.client.try_set_recovery_delay(&(DAY - 1)),
+        Err(Ok(Error::InvalidState))
+    );
+    t.client.set_recovery_delay(&DAY);
+    t.create_package(1, &t.lost);
+
+    let id = t.client.propose_recovery(
+        &t.admin,
+        &t.lost,
+        &t.replacement,
+        &RecoveryScope::Package(1),
+    );
+    assert_eq!(t.client.get_recovery(&id).executable_at, 1_000_000 + DAY);
+    t.advance(DAY);
+    assert_eq!(t.client.execute_recovery(&t.admin, &id, &50).rotated, 1);
+}
+
+#[test]
+fn all_open_recovery_runs_in_pages() {
+    let t = RecoveryTest::new();
+    for id in 0..5 {
+        t.create_package(id, &t.lost);
+    }
+    let id = t
+        .client
+        .propose_recovery(&t.admin, &t.lost, &t.replacement, &RecoveryScope::AllOpen);
+    t.advance(DELAY);
+
+    let progress = t.client.execute_recovery(&t.admin, &id, &2);
+    assert_eq!(progress.rotated, 2);
+    assert_eq!(progress.next_cursor, 2);
+    assert!(!progress.done);
+    assert_eq!(t.client.get_recovery(&id).status, RecoveryStatus::Executing);
+    assert_eq!(t.client.get_package(&1).recipient, t.replacement);
+    assert_eq!(t.client.get_package(&2).recipient, t.lost);
+
+    // Once started, the rotation can no longer be blocked half-way.
+    assert_eq!(
+        t.client.try_object_recovery(&id),
+        Err(Ok(Error::InvalidState))
+    );
+    assert_eq!(
+        t.client.try_cancel_recovery(&t.admin, &id),
+        Err(Ok(Error::InvalidState))
+    );
+
+    let progress = t.client.execute_recovery(&t.admin, &id, &100);
+    assert_eq!(progress.rotated, 3);
+    assert!(progress.done);
+    let request = t.client.get_recovery(&id);
+    assert_eq!(request.status, RecoveryStatus::Executed);
+    assert_eq!(request.rotated, 5);
+    assert_eq!(
+        t.client.try_execute_recovery(&t.admin, &id, &50),
+        Err(Ok(Error::InvalidState))
+    );
+}
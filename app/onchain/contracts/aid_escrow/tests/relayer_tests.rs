@@ -1,6 +1,8 @@
-#![cfg(test)]
+#![cfg(all(test, feature = "relayers"))]
 
-use aid_escrow::{AidEscrow, AidEscrowClient, Error, PackageStatus};
+use aid_escrow::{
+    AidEscrow, AidEscrowClient, Error, PackageStatus, RelayerFee, RelayerFeeKind, RelayerFeeSource,
+};
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
@@ -283,3 +285,137 @@ fn relayed_claim_fails_when_already_claimed_by_direct_claim() {
         .try_claim_with_relayer(&1, &t.recipient, &t.relayer);
     assert_eq!(result, Err(Ok(Error::PackageNotActive)));
 }
+
+#[test]
+fn registered_relayer_is_paid_fee_from_payout() {
+    let t = RelayerTest::new();
+    t.create_package(1);
+    t.client.register_relayer(&t.relayer);
+    t.client.set_relayer_fee(
+        &t.token,
+        &Some(RelayerFee {
+            kind: RelayerFeeKind::Bps(100),
+            cap: 0,
+            source: RelayerFeeSource::Payout,
+        }),
+    );
+
+    t.client.claim_with_relayer(&1, &t.recipient, &t.relayer);
+
+    let fee = UNIT / 100;
+    assert_eq!(t.balance_of(&t.relayer), fee);
+    assert_eq!(t.balance_of(&t.recipient), UNIT - fee);
+    assert_eq!(t.client.get_relayer_fees_earned(&t.relayer, &t.token), fee);
+    assert_eq!(t.client.get_relayer(&t.relayer).unwrap().claims_relayed, 1);
+}
+
+#[test]
+fn surplus_sourced_fee_leaves_payout_untouched() {
+    let t = RelayerTest::new();
+    t.create_package(1);
+    t.client.register_relayer(&t.relayer);
+    t.client.set_relayer_fee(
+        &t.token,
+        &Some(RelayerFee {
+            kind: RelayerFeeKind::Fixed(UNIT),
+            cap: UNIT / 2,
+            source: RelayerFeeSource::Surplus,
+        }),
+    );
+
+    t.client.claim_with_relayer(&1, &t.recipient, &t.relayer);
+
+    assert_eq!(t.balance_of(&t.recipient), UNIT);
+    assert_eq!(t.balance_of(&t.relayer), UNIT / 2);
+    assert_eq!(t.balance_of(&t.client.address), 10 * UNIT - UNIT - UNIT / 2);
+}
+
+#[test]
+fn surplus_sourced_fee_is_limited_to_available_surplus() {
+    let t = RelayerTest::new();
+    // Lock the entire pool so no surplus remains
+    for id in 1..=10 {
+        t.create_package(id);
+    }
+    t.client.register_relayer(&t.relayer);
+    t.client.set_relayer_fee(
+        &t.token,
+        &Some(RelayerFee {
+            kind: RelayerFeeKind::Fixed(UNIT / 10),
+            cap: 0,
+            source: RelayerFeeSource::Surplus,
+        }),
+    );
+
+    t.client.claim_with_relayer(&1, &t.recipient, &t.relayer);
+
+    assert_eq!(t.balance_of(&t.recipient), UNIT);
+    assert_eq!(t.balance_of(&t.relayer), 0);
+    assert_eq!(t.balance_of(&t.client.address), 9 * UNIT);
+}
+
+#[test]
+fn unregistered_relayer_is_not_paid_fee() {
+    let t = RelayerTest::new();
+    t.create_package(1);
+    t.client.set_relayer_fee(
+        &t.token,
+        &Some(RelayerFee {
+            kind: RelayerFeeKind::Fixed(UNIT / 10),
+            cap: 0,
+            source: RelayerFeeSource::Payout,
+        }),
+    );
+
+    t.client.claim_with_relayer(&1, &t.recipient, &t.relayer);
+
+    assert_eq!(t.balance_of(&t.recipient), UNIT);
+    assert_eq!(t.balance_of(&t.relayer), 0);
+    assert!(t.client.get_relayer(&t.relayer).is_none());
+}
+
+#[test]
+fn disabled_relayer_is_rejected() {
+    let t = RelayerTest::new();
+    t.create_package(1);
+    t.client.register_relayer(&t.relayer);
+    t.client.set_relayer_enabled(&t.relayer, &false);
+
+    let result = t
+        .client
+        .try_claim_with_relayer(&1, &t.recipient, &t.relayer);
+    assert_eq!(result, Err(Ok(Error::RelayerDisabled)));
+
+    t.client.set_relayer_enabled(&t.relayer, &true);
+    t.client.claim_with_relayer(&1, &t.recipient, &t.relayer);
+    assert_eq!(t.client.get_package(&1).status, PackageStatus::Claimed);
+}
+
+#[test]
+fn relayer_registry_admin_errors() {
+    let t = RelayerTest::new();
+
+    assert_eq!(
+        t.client.try_set_relayer_enabled(&t.relayer, &false),
+        Err(Ok(Error::RelayerNotRegistered))
+    );
+
+    t.client.register_relayer(&t.relayer);
+    assert_eq!(
+        t.client.try_register_relayer(&t.relayer),
+        Err(Ok(Error::InvalidState))
+    );
+
+    let invalid = RelayerFee {
+        kind: RelayerFeeKind::Bps(10_001),
+        cap: 0,
+        source: RelayerFeeSource::Payout,
+    };
+    assert_eq!(
+        t.client.try_set_relayer_fee(&t.token, &Some(invalid)),
+        Err(Ok(Error::InvalidRelayerFee))
+    );
+
+    t.client.set_relayer_fee(&t.token, &None);
+    assert!(t.client.get_relayer_fee(&t.token).is_none());
+}
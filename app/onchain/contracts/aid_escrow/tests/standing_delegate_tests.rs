@@ -0,0 +1,215 @@
+#![cfg(all(test, feature = "standing-delegates"))]
+
+mod common;
+
+use aid_escrow::{DelegationScope, Error, PackageStatus, Role};
+use common::{Escrow, UNIT};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Map, String, Symbol, Vec,
+};
+use std::ops::Deref;
+
+struct StandingTest {
+    escrow: Escrow,
+    recipient: Address,
+    caregiver: Address,
+}
+
+impl Deref for StandingTest {
+    type Target = Escrow;
+
+    fn deref(&self) -> &Escrow {
+        &self.escrow
+    }
+}
+
+impl StandingTest {
+    fn new() -> Self {
+        let escrow = Escrow::new();
+
+        let recipient = Address::generate(&escrow.env);
+        let caregiver = Address::generate(&escrow.env);
+
+        Self {
+            escrow,
+            recipient,
+            caregiver,
+        }
+    }
+
+    fn create_package(&self, id: u64, campaign: Option<&str>) {
+        let mut metadata = Map::new(&self.env);
+        if let Some(campaign) = campaign {
+            metadata.set(
+                Symbol::new(&self.env, "campaign_ref"),
+                String::from_str(&self.env, campaign),
+            );
+        }
+        self.client.create_package(
+            &self.admin,
+            &id,
+            &self.recipient,
+            &UNIT,
+            &self.token,
+            &0,
+            &metadata,
+        );
+    }
+
+    fn claim_as(&self, id: u64, claimant: &Address) -> Result<(), Error> {
+        let proof: Vec<String> = Vec::new(&self.env);
+        match self.client.try_claim_with_proof(&id, claimant, &proof) {
+            Ok(_) => Ok(()),
+            Err(Ok(e)) => Err(e),
+            Err(Err(_)) => panic!("unexpected host error"),
+        }
+    }
+}
+
+#[test]
+fn standing_delegate_covers_current_and_future_packages() {
+    let t = StandingTest::new();
+    t.create_package(1, None);
+
+    t.client.grant_standing_delegate(
+        &t.recipient,
+        &t.recipient,
+        &t.caregiver,
+        &DelegationScope::All,
+        &0,
+    );
+    t.create_package(2, None);
+
+    assert_eq!(t.claim_as(1, &t.caregiver), Ok(()));
+    assert_eq!(t.claim_as(2, &t.caregiver), Ok(()));
+    assert_eq!(t.balance(&t.caregiver), 2 * UNIT);
+    assert_eq!(t.client.get_package(&2).status, PackageStatus::Claimed);
+
+    // The delegation survives the claims
+    assert_eq!(t.client.get_standing_delegates(&t.recipient).len(), 1);
+}
+
+#[test]
+fn scope_limits_standing_delegate_to_token_or_campaign() {
+    let t = StandingTest::new();
+    t.create_package(1, Some("flood-2026"));
+    t.create_package(2, Some("winter-2026"));
+
+    t.client.grant_standing_delegate(
+        &t.recipient,
+        &t.recipient,
+        &t.caregiver,
+        &DelegationScope::Campaign(String::from_str(&t.env, "flood-2026")),
+        &0,
+    );
+    assert_eq!(t.claim_as(2, &t.caregiver), Err(Error::NotAuthorized));
+    assert_eq!(t.claim_as(1, &t.caregiver), Ok(()));
+
+    let other_token = Address::generate(&t.env);
+    t.client.grant_standing_delegate(
+        &t.recipient,
+        &t.recipient,
+        &t.caregiver,
+        &DelegationScope::Token(other_token),
+        &0,
+    );
+    assert_eq!(t.claim_as(2, &t.caregiver), Err(Error::NotAuthorized));
+
+    t.client.grant_standing_delegate(
+        &t.recipient,
+        &t.recipient,
+        &t.caregiver,
+        &DelegationScope::Token(t.token.clone()),
+        &0,
+    );
+    assert_eq!(t.claim_as(2, &t.caregiver), Ok(()));
+}
+
+#[test]
+fn expired_standing_delegate_cannot_claim() {
+    let t = StandingTest::new();
+    t.create_package(1, None);
+    let now = t.env.ledger().timestamp();
+
+    t.client.grant_standing_delegate(
+        &t.recipient,
+        &t.recipient,
+        &t.caregiver,
+        &DelegationScope::All,
+        &(now + 60),
+    );
+    t.env.ledger().set_timestamp(now + 61);
+    assert_eq!(t.claim_as(1, &t.caregiver), Err(Error::NotAuthorized));
+}
+
+#[test]
+#[cfg(feature = "multi-delegates")]
+fn cosign_threshold_overrides_standing_delegate() {
+    let t = StandingTest::new();
+    t.create_package(1, None);
+    t.client
+        .add_delegate(&t.admin, &1, &Address::generate(&t.env), &0);
+    t.client
+        .add_delegate(&t.admin, &1, &Address::generate(&t.env), &0);
+    t.client.set_delegate_threshold(&t.admin, &1, &2);
+
+    t.client.grant_standing_delegate(
+        &t.recipient,
+        &t.recipient,
+        &t.caregiver,
+        &DelegationScope::All,
+        &0,
+    );
+    assert_eq!(
+        t.claim_as(1, &t.caregiver),
+        Err(Error::DelegateThresholdNotMet)
+    );
+}
+
+#[test]
+fn standing_delegations_are_managed_by_recipient_or_delegate_manager() {
+    let t = StandingTest::new();
+    let stranger = Address::generate(&t.env);
+    let manager = Address::generate(&t.env);
+
+    let result = t.client.try_grant_standing_delegate(
+        &stranger,
+        &t.recipient,
+        &t.caregiver,
+        &DelegationScope::All,
+        &0,
+    );
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    t.client.grant_role(&Role::DelegateManager, &manager);
+    t.client.grant_standing_delegate(
+        &manager,
+        &t.recipient,
+        &t.caregiver,
+        &DelegationScope::All,
+        &0,
+    );
+    assert_eq!(
+        t.client
+            .get_standing_delegates(&t.recipient)
+            .get(0)
+            .unwrap()
+            .granted_by,
+        manager
+    );
+
+    let result = t
+        .client
+        .try_revoke_standing_delegate(&stranger, &t.recipient, &t.caregiver);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    // The delegate may renounce its own delegation
+    t.client
+        .revoke_standing_delegate(&t.caregiver, &t.recipient, &t.caregiver);
+    assert_eq!(t.client.get_standing_delegates(&t.recipient).len(), 0);
+    let result = t
+        .client
+        .try_revoke_standing_delegate(&t.recipient, &t.recipient, &t.caregiver);
+    assert_eq!(result, Err(Ok(Error::DelegateNotFound)));
+}
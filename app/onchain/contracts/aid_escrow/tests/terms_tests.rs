@@ -0,0 +1,240 @@
+#![cfg(test)]
+
+mod common;
+
+use aid_escrow::{Error, PackageTerms};
+use common::{Escrow, UNIT};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Bytes, BytesN, Map, String, Symbol, Vec,
+};
+
+type TermsTest = Escrow;
+
+impl TermsTest {
+    fn now(&self) -> u64 {
+        self.env.ledger().timestamp()
+    }
+
+    fn create(
+        &self,
+        id: u64,
+        recipient: &Address,
+        terms: &PackageTerms,
+        metadata: &Map<Symbol, String>,
+    ) -> Result<u64, Error> {
+        self.client
+            .try_create_package_with_terms(
+                &self.admin,
+                &id,
+                recipient,
+                &UNIT,
+                &self.token,
+                &(self.now() + 86400),
+                terms,
+                metadata,
+            )
+            .map(|r| r.unwrap())
+            .map_err(|e| e.unwrap())
+    }
+
+    fn meta(&self, entries: &[(&str, &str)]) -> Map<Symbol, String> {
+        let mut metadata = Map::new(&self.env);
+        for (k, v) in entries {
+            metadata.set(Symbol::new(&self.env, k), String::from_str(&self.env, v));
+        }
+        metadata
+    }
+
+    fn leaf(&self, claimant: &Address) -> BytesN<32> {
+        let addr = claimant.to_string();
+        let len = addr.len() as usize;
+        let mut raw = [0u8; 96];
+        addr.copy_into_slice(&mut raw[..len]);
+        self.env
+            .crypto()
+            .sha256(&Bytes::from_slice(&self.env, &raw[..len]))
+            .into()
+    }
+}
+
+#[test]
+fn typed_claim_start_and_merkle_root_are_enforced() {
+    let t = TermsTest::new();
+    let claimant = Address::generate(&t.env);
+    let terms = PackageTerms {
+        claim_starts_at: Some(t.now() + 100),
+        merkle_root: Some(t.leaf(&claimant)),
+        receipt_hash: None,
+        campaign_id: None,
+        restricted: false,
+        allowed_categories: None,
+        attestor: None,
+        condition_id: None,
+    };
+    let id = t
+        .create(1, &Address::generate(&t.env), &terms, &Map::new(&t.env))
+        .unwrap();
+
+    let proof = Vec::new(&t.env);
+    assert_eq!(
+        t.client.try_claim_with_proof(&id, &claimant, &proof),
+        Err(Ok(Error::ClaimTooEarly))
+    );
+
+    t.env.ledger().set_timestamp(t.now() + 100);
+    assert_eq!(t.client.try_claim(&id), Err(Ok(Error::InvalidProof)));
+    t.client.claim_with_proof(&id, &claimant, &proof);
+
+    assert_eq!(t.client.get_package_terms(&id), terms);
+}
+
+#[test]
+fn typed_campaign_is_used_for_campaign_queries() {
+    let t = TermsTest::new();
+    let campaign = String::from_str(&t.env, "flood-2026");
+    let terms = PackageTerms {
+        campaign_id: Some(campaign.clone()),
+        ..Default::default()
+    };
+    t.create(1, &Address::generate(&t.env), &terms, &Map::new(&t.env))
+        .unwrap();
+    t.create(2, &Address::generate(&t.env), &terms, &Map::new(&t.env))
+        .unwrap();
+    t.client.claim(&1);
+
+    assert_eq!(t.client.get_campaign_package_count(&campaign), 2);
+    assert_eq!(t.client.get_campaign_claim_count(&campaign), 1);
+}
+
+#[test]
+fn legacy_metadata_packages_read_back_as_terms() {
+    let t = TermsTest::new();
+    let claimant = Address::generate(&t.env);
+    let root = t.leaf(&claimant);
+    let mut root_hex = std::string::String::new();
+    for b in root.to_array() {
+        root_hex.push_str(&format!("{:02x}", b));
+    }
+    let start = (t.now() + 10).to_string();
+    let metadata = t.meta(&[
+        ("claim_starts_at", &start),
+        ("merkle_root", &root_hex),
+        ("receipt_hash", "r-1"),
+        ("campaign_ref", "legacy"),
+    ]);
+
+    let id = t.client.create_package(
+        &t.admin,
+        &1,
+        &Address::generate(&t.env),
+        &UNIT,
+        &t.token,
+        &(t.now() + 86400),
+        &metadata,
+    );
+
+    assert_eq!(
+        t.client.get_package_terms(&id),
+        PackageTerms {
+            claim_starts_at: Some(t.now() + 10),
+            merkle_root: Some(root),
+            receipt_hash: Some(String::from_str(&t.env, "r-1")),
+            campaign_id: Some(String::from_str(&t.env, "legacy")),
+            restricted: false,
+            allowed_categories: None,
+            attestor: None,
+            condition_id: None,
+        }
+    );
+    assert_eq!(
+        t.client
+            .get_campaign_package_count(&String::from_str(&t.env, "legacy")),
+        1
+    );
+}
+
+#[test]
+fn invalid_terms_are_rejected_with_explicit_errors() {
+    let t = TermsTest::new();
+    let recipient = Address::generate(&t.env);
+    let none = PackageTerms::default();
+
+    let bad_start = t.meta(&[("claim_starts_at", "soon")]);
+    assert_eq!(
+        t.create(1, &recipient, &none, &bad_start),
+        Err(Error::MalformedTerm)
+    );
+    let bad_root = t.meta(&[("merkle_root", "abcd")]);
+    assert_eq!(
+        t.create(1, &recipient, &none, &bad_root),
+        Err(Error::MalformedTerm)
+    );
+
+    let late = PackageTerms {
+        claim_starts_at: Some(t.now() + 86401),
+        ..Default::default()
+    };
+    assert_eq!(
+        t.create(1, &recipient, &late, &Map::new(&t.env)),
+        Err(Error::InvalidClaimWindow)
+    );
+
+    let typed = PackageTerms {
+        campaign_id: Some(String::from_str(&t.env, "a")),
+        ..Default::default()
+    };
+    assert_eq!(
+        t.create(1, &recipient, &typed, &t.meta(&[("campaign_ref", "b")])),
+        Err(Error::ConflictingTerms)
+    );
+    // The same value given both ways is accepted.
+    assert_eq!(
+        t.create(1, &recipient, &typed, &t.meta(&[("campaign_ref", "a")])),
+        Ok(1)
+    );
+}
+
+#[test]
+fn batch_create_with_terms() {
+    let t = TermsTest::new();
+    let recipients = Vec::from_array(
+        &t.env,
+        [Address::generate(&t.env), Address::generate(&t.env)],
+    );
+    let amounts = Vec::from_array(&t.env, [UNIT, UNIT]);
+    let metadatas = Vec::from_array(&t.env, [Map::new(&t.env), Map::new(&t.env)]);
+    let second = PackageTerms {
+        receipt_hash: Some(String::from_str(&t.env, "r-2")),
+        ..Default::default()
+    };
+
+    let short = Vec::from_array(&t.env, [second.clone()]);
+    assert_eq!(
+        t.client.try_batch_create_packages_with_terms(
+            &t.admin,
+            &recipients,
+            &amounts,
+            &t.token,
+            &3600,
+            &short,
+            &metadatas,
+        ),
+        Err(Ok(Error::MismatchedArrays))
+    );
+
+    let terms = Vec::from_array(&t.env, [PackageTerms::default(), second.clone()]);
+    let ids = t.client.batch_create_packages_with_terms(
+        &t.admin,
+        &recipients,
+        &amounts,
+        &t.token,
+        &3600,
+        &terms,
+        &metadatas,
+    );
+
+    let stored = t.client.get_package_terms(&ids.get(1).unwrap());
+    assert_eq!(stored.receipt_hash, second.receipt_hash);
+    assert_eq!(stored.claim_starts_at, Some(t.now()));
+}
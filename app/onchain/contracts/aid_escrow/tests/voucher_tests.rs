@@ -0,0 +1,192 @@
+#![cfg(all(test, feature = "vouchers"))]
+
+mod common;
+
+use aid_escrow::{ClaimVoucher, Error, PackageStatus};
+use common::{Escrow, UNIT};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Map,
+};
+use std::ops::Deref;
+
+struct VoucherTest {
+    escrow: Escrow,
+    distributor: Address,
+    recipient: Address,
+    signing_key: SigningKey,
+}
+
+impl Deref for VoucherTest {
+    type Target = Escrow;
+
+    fn deref(&self) -> &Escrow {
+        &self.escrow
+    }
+}
+
+impl VoucherTest {
+    fn new() -> Self {
+        let escrow = Escrow::new();
+        let distributor = Address::generate(&escrow.env);
+        let recipient = Address::generate(&escrow.env);
+
+        escrow.client.add_distributor(&distributor);
+
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = BytesN::from_array(&escrow.env, &signing_key.verifying_key().to_bytes());
+        escrow.client.set_distributor_key(&distributor, &public_key);
+
+        Self {
+            escrow,
+            distributor,
+            recipient,
+            signing_key,
+        }
+    }
+
+    fn create_package(&self, id: u64) {
+        self.client.create_package(
+            &self.distributor,
+            &id,
+            &self.recipient,
+            &UNIT,
+            &self.token,
+            &(self.env.ledger().timestamp() + 86400),
+            &Map::new(&self.env),
+        );
+    }
+
+    fn voucher(&self, package_id: u64, nonce: u64) -> ClaimVoucher {
+        ClaimVoucher {
+            package_id,
+            claimant: self.recipient.clone(),
+            distributor: self.distributor.clone(),
+            nonce,
+            expires_at: self.env.ledger().timestamp() + 3600,
+        }
+    }
+
+    fn sign(&self, voucher: &ClaimVoucher) -> BytesN<64> {
+        let payload = self.client.voucher_payload(voucher);
+        let mut message = vec![0u8; payload.len() as usize];
+        payload.copy_into_slice(&mut message);
+        let signature = self.signing_key.sign(&message);
+        BytesN::from_array(&self.env, &signature.to_bytes())
+    }
+}
+
+#[test]
+fn voucher_claim_pays_recipient() {
+    let t = VoucherTest::new();
+    t.create_package(1);
+
+    let voucher = t.voucher(1, 1);
+    let signature = t.sign(&voucher);
+    t.client.claim_with_voucher(&voucher, &signature);
+
+    assert_eq!(t.balance(&t.recipient), UNIT);
+    assert_eq!(t.client.get_package(&1).status, PackageStatus::Claimed);
+    assert!(t.client.is_voucher_nonce_used(&t.distributor, &1));
+    assert_eq!(t.client.get_total_claimed(&t.token), UNIT);
+}
+
+#[test]
+fn voucher_nonce_cannot_be_replayed() {
+    let t = VoucherTest::new();
+    t.create_package(1);
+    t.create_package(2);
+
+    let voucher = t.voucher(1, 9);
+    let signature = t.sign(&voucher);
+    t.client.claim_with_voucher(&voucher, &signature);
+
+    let replay = t.voucher(2, 9);
+    let replay_signature = t.sign(&replay);
+    let result = t.client.try_claim_with_voucher(&replay, &replay_signature);
+    assert_eq!(result, Err(Ok(Error::VoucherAlreadyUsed)));
+    assert_eq!(t.client.get_package(&2).status, PackageStatus::Created);
+}
+
+#[test]
+fn expired_voucher_is_rejected() {
+    let t = VoucherTest::new();
+    t.create_package(1);
+
+    let voucher = t.voucher(1, 1);
+    let signature = t.sign(&voucher);
+    t.env.ledger().set_timestamp(voucher.expires_at + 1);
+
+    let result = t.client.try_claim_with_voucher(&voucher, &signature);
+    assert_eq!(result, Err(Ok(Error::VoucherExpired)));
+}
+
+#[test]
+fn tampered_voucher_fails_signature_check() {
+    let t = VoucherTest::new();
+    t.create_package(1);
+    t.create_package(2);
+
+    let voucher = t.voucher(1, 1);
+    let signature = t.sign(&voucher);
+    let mut tampered = voucher.clone();
+    tampered.package_id = 2;
+
+    assert!(t
+        .client
+        .try_claim_with_voucher(&tampered, &signature)
+        .is_err());
+    assert_eq!(t.client.get_package(&2).status, PackageStatus::Created);
+    assert!(!t.client.is_voucher_nonce_used(&t.distributor, &1));
+}
+
+#[test]
+fn voucher_from_removed_distributor_is_rejected() {
+    let t = VoucherTest::new();
+    t.create_package(1);
+
+    let voucher = t.voucher(1, 1);
+    let signature = t.sign(&voucher);
+    t.client.remove_distributor(&t.distributor);
+
+    let result = t.client.try_claim_with_voucher(&voucher, &signature);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+}
+
+#[test]
+fn voucher_requires_registered_key() {
+    let t = VoucherTest::new();
+    t.create_package(1);
+
+    let mut voucher = t.voucher(1, 1);
+    voucher.distributor = t.admin.clone();
+    let signature = t.sign(&voucher);
+
+    let result = t.client.try_claim_with_voucher(&voucher, &signature);
+    assert_eq!(result, Err(Ok(Error::SigningKeyNotRegistered)));
+}
+
+#[test]
+fn voucher_for_stranger_is_rejected() {
+    let t = VoucherTest::new();
+    t.create_package(1);
+
+    let mut voucher = t.voucher(1, 1);
+    voucher.claimant = Address::generate(&t.env);
+    let signature = t.sign(&voucher);
+
+    let result = t.client.try_claim_with_voucher(&voucher, &signature);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+}
+
+#[test]
+fn non_distributor_cannot_register_key() {
+    let t = VoucherTest::new();
+    let stranger = Address::generate(&t.env);
+    let key = BytesN::from_array(&t.env, &[1u8; 32]);
+
+    let result = t.client.try_set_distributor_key(&stranger, &key);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+    assert_eq!(t.client.get_distributor_key(&stranger), None);
+}
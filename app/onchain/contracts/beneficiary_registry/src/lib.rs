@@ -0,0 +1,163 @@
+#![no_std]
+
+//! Beneficiary registry.
+//!
+//! Maps a salted identity hash to the `Address` a beneficiary receives aid
+//! at.  The hash is computed off-chain, e.g. `sha256(salt || national_id)`,
+//! so no identifier is stored in the clear.  Each hash maps to one address
+//! and each address to one hash, which lets `aid_escrow` recognise the same
+//! person enrolled by different distributors.
+//!
+//! The contract does not link `aid_escrow` (its exports would end up in this
+//! wasm), so the `BeneficiaryRegistry` implementation below is compiled with
+//! the tests and keeps `get_identity` in step with the trait.
+
+use soroban_sdk::{
+    contract, contracterror, contractevent, contractimpl, symbol_short, Address, BytesN, Env,
+};
+
+#[contracterror]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    /// The identity hash is already registered to an address.
+    IdentityTaken = 3,
+    /// The address is already registered under another identity hash.
+    AddressTaken = 4,
+    NotRegistered = 5,
+}
+
+#[contractevent]
+pub struct BeneficiaryRegistered {
+    pub identity_hash: BytesN<32>,
+    pub beneficiary: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+pub struct BeneficiaryDeregistered {
+    pub identity_hash: BytesN<32>,
+    pub beneficiary: Address,
+    pub timestamp: u64,
+}
+
+#[contract]
+pub struct BeneficiaryRegistry;
+
+#[contractimpl]
+impl BeneficiaryRegistry {
+    pub fn init(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&symbol_short!("admin")) {
+            return Err(Error::AlreadyInitialized);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("admin"), &admin);
+        Ok(())
+    }
+
+    /// Admin-only. Registers `beneficiary` under `identity_hash`.
+    pub fn register(
+        env: Env,
+        identity_hash: BytesN<32>,
+        beneficiary: Address,
+    ) -> Result<(), Error> {
+        Self::admin(&env)?.require_auth();
+
+        let id_key = (symbol_short!("id"), identity_hash.clone());
+        let addr_key = (symbol_short!("addr"), beneficiary.clone());
+        if env.storage().persistent().has(&id_key) {
+            return Err(Error::IdentityTaken);
+        }
+        if env.storage().persistent().has(&addr_key) {
+            return Err(Error::AddressTaken);
+        }
+        env.storage().persistent().set(&id_key, &beneficiary);
+        env.storage().persistent().set(&addr_key, &identity_hash);
+
+        BeneficiaryRegistered {
+            identity_hash,
+            beneficiary,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Admin-only. Removes the registration of `identity_hash`, e.g. before
+    /// registering the beneficiary at a new address.
+    pub fn deregister(env: Env, identity_hash: BytesN<32>) -> Result<(), Error> {
+        Self::admin(&env)?.require_auth();
+
+        let id_key = (symbol_short!("id"), identity_hash.clone());
+        let beneficiary: Address = env
+            .storage()
+            .persistent()
+            .get(&id_key)
+            .ok_or(Error::NotRegistered)?;
+        env.storage().persistent().remove(&id_key);
+        env.storage()
+            .persistent()
+            .remove(&(symbol_short!("addr"), beneficiary.clone()));
+
+        BeneficiaryDeregistered {
+            identity_hash,
+            beneficiary,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the address registered under `identity_hash`, if any.
+    pub fn get_beneficiary(env: Env, identity_hash: BytesN<32>) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("id"), identity_hash))
+    }
+
+    /// Returns the identity hash `beneficiary` is registered under, if any.
+    pub fn get_identity(env: Env, beneficiary: Address) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("addr"), beneficiary))
+    }
+
+    fn admin(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(Error::NotInitialized)
+    }
+}
+
+#[cfg(test)]
+impl aid_escrow::BeneficiaryRegistry for BeneficiaryRegistry {
+    fn get_identity(env: Env, beneficiary: Address) -> Option<BytesN<32>> {
+        BeneficiaryRegistry::get_identity(env, beneficiary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aid_escrow::BeneficiaryRegistryClient as EscrowView;
+    use soroban_sdk::testutils::Address as _;
+
+    #[test]
+    fn answers_through_the_escrow_client() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let id = env.register(BeneficiaryRegistry, ());
+        let registry = BeneficiaryRegistryClient::new(&env, &id);
+        registry.init(&Address::generate(&env));
+        let view = EscrowView::new(&env, &id);
+        let beneficiary = Address::generate(&env);
+        let identity = BytesN::from_array(&env, &[1; 32]);
+
+        assert_eq!(view.get_identity(&beneficiary), None);
+        registry.register(&identity, &beneficiary);
+        assert_eq!(view.get_identity(&beneficiary), Some(identity));
+    }
+}
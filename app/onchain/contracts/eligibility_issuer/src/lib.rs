@@ -0,0 +1,132 @@
+#![no_std]
+
+//! Reference eligibility issuer for `aid_escrow` campaigns.
+//!
+//! The issuer's admin grants recipients a credential per campaign, optionally
+//! expiring, and can revoke it.  `aid_escrow` calls `is_eligible` before paying
+//! out a package in a campaign configured with this issuer.
+//!
+//! The contract does not link `aid_escrow` (its exports would end up in this
+//! wasm), so the `EligibilityIssuer` implementation below is compiled with the
+//! tests and keeps `is_eligible` in step with the trait.
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, String,
+};
+
+#[contracterror]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Credential {
+    pub issued_at: u64,
+    /// 0 = never expires.
+    pub expires_at: u64,
+    pub revoked: bool,
+}
+
+#[contract]
+pub struct EligibilityIssuer;
+
+#[contractimpl]
+impl EligibilityIssuer {
+    pub fn init(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&symbol_short!("admin")) {
+            return Err(Error::AlreadyInitialized);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("admin"), &admin);
+        Ok(())
+    }
+
+    /// Admin-only. Issues, or re-issues, the credential of `recipient` for
+    /// `campaign`, valid until `expires_at` (0 = no expiry).
+    pub fn issue(
+        env: Env,
+        recipient: Address,
+        campaign: String,
+        expires_at: u64,
+    ) -> Result<(), Error> {
+        Self::admin(&env)?.require_auth();
+        env.storage().persistent().set(
+            &(recipient, campaign),
+            &Credential {
+                issued_at: env.ledger().timestamp(),
+                expires_at,
+                revoked: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// Admin-only. Revokes the credential of `recipient` for `campaign`, if any.
+    pub fn revoke(env: Env, recipient: Address, campaign: String) -> Result<(), Error> {
+        Self::admin(&env)?.require_auth();
+        let key = (recipient, campaign);
+        if let Some(mut credential) = env.storage().persistent().get::<_, Credential>(&key) {
+            credential.revoked = true;
+            env.storage().persistent().set(&key, &credential);
+        }
+        Ok(())
+    }
+
+    pub fn get_credential(env: Env, recipient: Address, campaign: String) -> Option<Credential> {
+        env.storage().persistent().get(&(recipient, campaign))
+    }
+
+    /// Returns `true` if `recipient` holds an unrevoked, unexpired credential
+    /// for `campaign`.
+    pub fn is_eligible(env: Env, recipient: Address, campaign: String) -> bool {
+        match Self::get_credential(env.clone(), recipient, campaign) {
+            Some(credential) => {
+                !credential.revoked
+                    && (credential.expires_at == 0
+                        || env.ledger().timestamp() <= credential.expires_at)
+            }
+            None => false,
+        }
+    }
+
+    fn admin(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .ok_or(Error::NotInitialized)
+    }
+}
+
+#[cfg(test)]
+impl aid_escrow::EligibilityIssuer for EligibilityIssuer {
+    fn is_eligible(env: Env, recipient: Address, campaign: String) -> bool {
+        EligibilityIssuer::is_eligible(env, recipient, campaign)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aid_escrow::EligibilityIssuerClient as EscrowView;
+    use soroban_sdk::testutils::Address as _;
+
+    #[test]
+    fn answers_through_the_escrow_client() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let id = env.register(EligibilityIssuer, ());
+        let issuer = EligibilityIssuerClient::new(&env, &id);
+        issuer.init(&Address::generate(&env));
+        let view = EscrowView::new(&env, &id);
+        let recipient = Address::generate(&env);
+        let campaign = String::from_str(&env, "flood");
+
+        assert!(!view.is_eligible(&recipient, &campaign));
+        issuer.issue(&recipient, &campaign, &0);
+        assert!(view.is_eligible(&recipient, &campaign));
+    }
+}
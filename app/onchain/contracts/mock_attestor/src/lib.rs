@@ -0,0 +1,59 @@
+#![no_std]
+
+//! Mock attestor for oracle-gated aid packages.
+//!
+//! Implements the `is_attested` view `aid_escrow` calls before releasing a
+//! package that references an attestor (see `aid_escrow::Attestor`).  Anyone
+//! can set a condition, so this contract is for local testing only; a real
+//! attestor would accept attestations from its oracle.
+//!
+//! The contract does not link `aid_escrow` (its exports would end up in this
+//! wasm), so the `Attestor` implementation below is compiled with the tests
+//! and keeps `is_attested` in step with the trait.
+
+use soroban_sdk::{contract, contractimpl, Env, Symbol};
+
+#[contract]
+pub struct MockAttestor;
+
+#[contractimpl]
+impl MockAttestor {
+    /// Marks `condition_id` as attested, or clears it.
+    pub fn set_attested(env: Env, condition_id: Symbol, attested: bool) {
+        env.storage().persistent().set(&condition_id, &attested);
+    }
+
+    /// Returns `true` once `condition_id` has been attested.
+    pub fn is_attested(env: Env, condition_id: Symbol) -> bool {
+        env.storage()
+            .persistent()
+            .get(&condition_id)
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+impl aid_escrow::Attestor for MockAttestor {
+    fn is_attested(env: Env, condition_id: Symbol) -> bool {
+        MockAttestor::is_attested(env, condition_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aid_escrow::AttestorClient;
+    use soroban_sdk::symbol_short;
+
+    #[test]
+    fn answers_through_the_escrow_client() {
+        let env = Env::default();
+        let id = env.register(MockAttestor, ());
+        let attestor = AttestorClient::new(&env, &id);
+        let condition = symbol_short!("drought");
+
+        assert!(!attestor.is_attested(&condition));
+        MockAttestorClient::new(&env, &id).set_attested(&condition, &true);
+        assert!(attestor.is_attested(&condition));
+    }
+}